@@ -1,18 +1,174 @@
-use std::{fs::File, sync::Arc};
+use std::{
+    fs::File,
+    io::{Seek, SeekFrom},
+    sync::Arc,
+};
 
-use arrow::csv::{Reader as CsvArrowReader, ReaderBuilder};
-use arrow::datatypes::{Field, Schema as ArrowSchema};
+use arrow::array::{
+    Array, ArrayRef, Date32Array, Date64Array, StringArray, TimestampMicrosecondArray,
+    TimestampMillisecondArray, TimestampNanosecondArray, TimestampSecondArray,
+};
+use arrow::csv::{Reader as CsvArrowReader, ReaderBuilder, reader::Format};
+use arrow::datatypes::{DataType, Field, Schema as ArrowSchema, TimeUnit};
+use chrono::{NaiveDate, NaiveDateTime};
+use regex::Regex;
 
-use crate::datasource::Iterators;
+use crate::datasource::{Iterators, projection_indices};
 use crate::datatypes::arrow_field_vector::ArrowFieldVector;
 use crate::datatypes::column_vector::ColumnVector;
 use crate::datatypes::schema::schema_from_arrow_schema;
 use crate::datatypes::{record_batch::RecordBatch, schema::Schema};
+use crate::error::{Result, UnakiteError};
+
+/** Options for reading CSV files */
+#[derive(Clone, Debug)]
+pub struct CsvOptions {
+    pub has_header: bool,
+    /** Schema of the file, inferred from the first rows when missing */
+    pub schema: Option<ArrowSchema>,
+    pub delimiter: u8,
+    pub quote: u8,
+    pub escape: Option<u8>,
+    /** Lines starting with this character are skipped */
+    pub comment: Option<u8>,
+    /** Values read as null, such as `NA` or `\N`. Empty values are always null */
+    pub null_values: Vec<String>,
+    pub batch_size: usize,
+    /** Number of rows sampled to infer the schema, see `CsvIterator::infer_rows` when unset */
+    pub infer_rows: Option<usize>,
+    /** `chrono` format of date columns, e.g. `%d/%m/%Y`. ISO 8601 dates are read without one */
+    pub date_format: Option<String>,
+    /** `chrono` format of timestamp columns, e.g. `%d/%m/%Y %H:%M:%S` */
+    pub timestamp_format: Option<String>,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            has_header: false,
+            schema: None,
+            delimiter: b',',
+            quote: b'"',
+            escape: None,
+            comment: None,
+            null_values: vec![],
+            batch_size: 1024,
+            infer_rows: None,
+            date_format: None,
+            timestamp_format: None,
+        }
+    }
+}
+
+impl CsvOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /** Whether the first line of the file holds the column names */
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    pub fn with_schema(mut self, schema: ArrowSchema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    pub fn with_quote(mut self, quote: u8) -> Self {
+        self.quote = quote;
+        self
+    }
+
+    pub fn with_escape(mut self, escape: u8) -> Self {
+        self.escape = Some(escape);
+        self
+    }
+
+    pub fn with_comment(mut self, comment: u8) -> Self {
+        self.comment = Some(comment);
+        self
+    }
+
+    pub fn with_null_values(mut self, null_values: Vec<&str>) -> Self {
+        self.null_values = null_values.iter().map(|it| it.to_string()).collect();
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_infer_rows(mut self, infer_rows: usize) -> Self {
+        self.infer_rows = Some(infer_rows);
+        self
+    }
+
+    pub fn with_date_format(mut self, date_format: &str) -> Self {
+        self.date_format = Some(date_format.to_string());
+        self
+    }
+
+    pub fn with_timestamp_format(mut self, timestamp_format: &str) -> Self {
+        self.timestamp_format = Some(timestamp_format.to_string());
+        self
+    }
+
+    /** Regex matching null values, `None` keeps the reader default of empty values only */
+    fn null_regex(&self) -> Result<Option<Regex>> {
+        if self.null_values.is_empty() {
+            return Ok(None);
+        }
+
+        let values: Vec<String> = self
+            .null_values
+            .iter()
+            .map(|it| regex::escape(it))
+            .collect();
+        let regex = Regex::new(&format!("^(|{})$", values.join("|")))
+            .map_err(|e| UnakiteError::Plan(format!("Invalid CSV null values: {}", e)))?;
+        Ok(Some(regex))
+    }
+
+    fn format(&self) -> Result<Format> {
+        let mut format = Format::default()
+            .with_header(self.has_header)
+            .with_delimiter(self.delimiter)
+            .with_quote(self.quote);
+
+        if let Some(escape) = self.escape {
+            format = format.with_escape(escape);
+        }
+        if let Some(comment) = self.comment {
+            format = format.with_comment(comment);
+        }
+        if let Some(null_regex) = self.null_regex()? {
+            format = format.with_null_regex(null_regex);
+        }
+        Ok(format)
+    }
+
+    /** The format to parse a column of `data_type` with, when it is not read natively */
+    fn format_for(&self, data_type: &DataType) -> Option<&String> {
+        match data_type {
+            DataType::Date32 | DataType::Date64 => self.date_format.as_ref(),
+            DataType::Timestamp(_, _) => self.timestamp_format.as_ref(),
+            _ => None,
+        }
+    }
+}
 
 pub struct CsvDataSource {
     pub file_path: String,
 
-    has_headers: bool,
+    options: CsvOptions,
     schema: Arc<ArrowSchema>,
 }
 
@@ -20,29 +176,223 @@ impl CsvDataSource {
     pub fn new(file_path: String, has_headers: bool, schema: ArrowSchema) -> Self {
         Self {
             file_path,
-            has_headers,
+            options: CsvOptions::new().with_header(has_headers),
             schema: Arc::new(schema),
         }
     }
+
+    /** Create a source from options, inferring the schema from the file when none is given */
+    pub fn try_new(file_path: String, options: CsvOptions) -> Result<Self> {
+        let schema = match options.schema.clone() {
+            Some(schema) => schema,
+            None => infer_schema(&file_path, &options)?,
+        };
+
+        Ok(Self {
+            file_path,
+            options,
+            schema: Arc::new(schema),
+        })
+    }
+
     pub fn schema(&self) -> Arc<Schema> {
         Arc::new(schema_from_arrow_schema(self.schema.clone()))
     }
 
-    pub fn scan(&self, projection: Vec<String>) -> Iterators {
-        let file = match File::open(self.file_path.clone()) {
-            Err(_) => panic!("File Not found {}", self.file_path),
-            Ok(file) => file,
-        };
+    pub fn scan(&self, projection: Vec<String>) -> Result<Iterators> {
+        let file = File::open(self.file_path.clone())?;
 
-        let iter = CsvIterator::new(projection, self.has_headers, file, self.schema.clone());
+        let iter = CsvIterator::new(projection, file, self.schema.clone(), &self.options)?;
 
-        Iterators::Csv(iter)
+        Ok(Iterators::Csv(Box::new(iter)))
     }
 }
 
+/**
+ * Infer the column types from the first rows of the file. With a date or timestamp format set,
+ * text columns whose sampled values all parse with it become date or timestamp columns.
+ */
+fn infer_schema(file_path: &str, options: &CsvOptions) -> Result<ArrowSchema> {
+    let infer_rows = options
+        .infer_rows
+        .unwrap_or_else(|| CsvIterator::infer_rows(options.batch_size));
+
+    let mut file = File::open(file_path)?;
+    let (schema, _) = options
+        .format()?
+        .infer_schema(&mut file, Some(infer_rows))?;
+
+    if options.date_format.is_none() && options.timestamp_format.is_none() {
+        return Ok(schema);
+    }
+
+    // Read the sampled rows again as text to try the formats on them
+    file.seek(SeekFrom::Start(0))?;
+    let text_fields: Vec<Field> = schema
+        .fields()
+        .iter()
+        .map(|it| Field::new(it.name(), DataType::Utf8, true))
+        .collect();
+    let text_options = CsvOptions {
+        batch_size: infer_rows.max(1),
+        ..options.clone()
+    };
+    let sample = match csv_reader(Arc::new(ArrowSchema::new(text_fields)), &text_options)?
+        .build(file)?
+        .next()
+    {
+        Some(batch) => batch?,
+        None => return Ok(schema),
+    };
+
+    let candidates = [
+        (options.date_format.as_ref(), DataType::Date32),
+        (
+            options.timestamp_format.as_ref(),
+            DataType::Timestamp(TimeUnit::Microsecond, None),
+        ),
+    ];
+
+    let fields: Vec<Field> = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            if field.data_type() != &DataType::Utf8 {
+                return field.as_ref().clone();
+            }
+
+            let values = sample.column(i);
+            for (format, data_type) in candidates.iter() {
+                if let Some(format) = format
+                    && values.null_count() < values.len()
+                    && parse_temporal(values, format, data_type).is_ok()
+                {
+                    return field.as_ref().clone().with_data_type(data_type.clone());
+                }
+            }
+            field.as_ref().clone()
+        })
+        .collect();
+
+    Ok(ArrowSchema::new(fields))
+}
+
+/** A reader builder applying every option except the projection */
+fn csv_reader(schema: Arc<ArrowSchema>, options: &CsvOptions) -> Result<ReaderBuilder> {
+    let mut builder = ReaderBuilder::new(schema)
+        .with_header(options.has_header)
+        .with_delimiter(options.delimiter)
+        .with_quote(options.quote)
+        .with_batch_size(options.batch_size);
+
+    if let Some(escape) = options.escape {
+        builder = builder.with_escape(escape);
+    }
+    if let Some(comment) = options.comment {
+        builder = builder.with_comment(comment);
+    }
+    if let Some(null_regex) = options.null_regex()? {
+        builder = builder.with_null_regex(null_regex);
+    }
+    Ok(builder)
+}
+
+/** Parse text values with a `chrono` format into a date or timestamp array */
+fn parse_temporal(values: &ArrayRef, format: &str, data_type: &DataType) -> Result<ArrayRef> {
+    let values = values
+        .as_any()
+        .downcast_ref::<StringArray>()
+        .ok_or_else(|| {
+            UnakiteError::Type(format!("Expected text values, got {}", values.data_type()))
+        })?;
+
+    let error = |value: &str| {
+        UnakiteError::Type(format!(
+            "Could not parse '{}' as {} with format '{}'",
+            value, data_type, format
+        ))
+    };
+    let epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap_or_default();
+
+    let parse_date = |value: &str| -> Result<NaiveDate> {
+        NaiveDate::parse_from_str(value, format).map_err(|_| error(value))
+    };
+    let parse_timestamp = |value: &str| -> Result<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(value, format).map_err(|_| error(value))
+    };
+
+    let array: ArrayRef = match data_type {
+        DataType::Date32 => Arc::new(
+            values
+                .iter()
+                .map(|it| {
+                    it.map(|v| parse_date(v).map(|d| (d - epoch).num_days() as i32))
+                        .transpose()
+                })
+                .collect::<Result<Date32Array>>()?,
+        ),
+        DataType::Date64 => Arc::new(
+            values
+                .iter()
+                .map(|it| {
+                    it.map(|v| parse_date(v).map(|d| (d - epoch).num_milliseconds()))
+                        .transpose()
+                })
+                .collect::<Result<Date64Array>>()?,
+        ),
+        DataType::Timestamp(unit, tz) => {
+            let timestamps = values.iter().map(|it| {
+                it.map(|v| {
+                    let ts = parse_timestamp(v)?.and_utc();
+                    match unit {
+                        TimeUnit::Second => Ok(ts.timestamp()),
+                        TimeUnit::Millisecond => Ok(ts.timestamp_millis()),
+                        TimeUnit::Microsecond => Ok(ts.timestamp_micros()),
+                        TimeUnit::Nanosecond => ts.timestamp_nanos_opt().ok_or_else(|| error(v)),
+                    }
+                })
+                .transpose()
+            });
+
+            match unit {
+                TimeUnit::Second => Arc::new(
+                    timestamps
+                        .collect::<Result<TimestampSecondArray>>()?
+                        .with_timezone_opt(tz.clone()),
+                ),
+                TimeUnit::Millisecond => Arc::new(
+                    timestamps
+                        .collect::<Result<TimestampMillisecondArray>>()?
+                        .with_timezone_opt(tz.clone()),
+                ),
+                TimeUnit::Microsecond => Arc::new(
+                    timestamps
+                        .collect::<Result<TimestampMicrosecondArray>>()?
+                        .with_timezone_opt(tz.clone()),
+                ),
+                TimeUnit::Nanosecond => Arc::new(
+                    timestamps
+                        .collect::<Result<TimestampNanosecondArray>>()?
+                        .with_timezone_opt(tz.clone()),
+                ),
+            }
+        }
+        other => {
+            return Err(UnakiteError::NotImplemented(format!(
+                "Parsing {} with a format is not supported",
+                other
+            )));
+        }
+    };
+    Ok(array)
+}
+
 pub struct CsvIterator {
     reader: CsvArrowReader<File>,
     schema: Arc<ArrowSchema>,
+    /** Format of every projected column read as text and parsed afterwards */
+    formats: Vec<Option<String>>,
 }
 
 impl CsvIterator {
@@ -53,50 +403,73 @@ impl CsvIterator {
 
     pub fn new(
         projected_columns: Vec<String>,
-        has_header: bool,
         file: File,
         schema: Arc<ArrowSchema>,
-    ) -> Self {
-        let fields: Vec<Arc<Field>> = projected_columns
+        options: &CsvOptions,
+    ) -> Result<Self> {
+        let indices = projection_indices(&schema, &projected_columns)?;
+        let projected_schema = Arc::new(schema.project(&indices)?);
+
+        // Columns with a custom date or timestamp format are read as text and parsed here
+        let formats = projected_schema
+            .fields()
             .iter()
-            .filter_map(|name| schema.fields.iter().find(|it| it.name() == name).cloned())
+            .map(|it| options.format_for(it.data_type()).cloned())
+            .collect();
+        let read_fields: Vec<Field> = schema
+            .fields()
+            .iter()
+            .map(|it| match options.format_for(it.data_type()) {
+                Some(_) => it.as_ref().clone().with_data_type(DataType::Utf8),
+                None => it.as_ref().clone(),
+            })
             .collect();
 
-        let projected_schema = Arc::new(ArrowSchema::new(fields));
-
-        let csv = ReaderBuilder::new(schema.clone())
-            .with_header(has_header)
-            .build(file)
-            .unwrap();
+        // Only the projected columns are parsed, in the order they were requested
+        let csv = csv_reader(Arc::new(ArrowSchema::new(read_fields)), options)?
+            .with_projection(indices)
+            .build(file)?;
 
-        Self {
+        Ok(Self {
             reader: csv,
             schema: projected_schema,
-        }
+            formats,
+        })
     }
 }
 impl Iterator for CsvIterator {
-    type Item = RecordBatch;
+    type Item = Result<RecordBatch>;
     fn next(&mut self) -> Option<Self::Item> {
         let local_schema = schema_from_arrow_schema(self.schema.clone());
         match self.reader.next() {
             Some(batch) => {
                 let mut fields: Vec<ColumnVector> = vec![];
 
-                let batches = batch.unwrap();
+                let batches = match batch {
+                    Ok(batches) => batches,
+                    Err(e) => return Some(Err(e.into())),
+                };
+
+                for (i, col) in batches.columns().iter().enumerate() {
+                    let col = match &self.formats[i] {
+                        Some(format) => {
+                            match parse_temporal(col, format, self.schema.field(i).data_type()) {
+                                Ok(col) => col,
+                                Err(e) => return Some(Err(e)),
+                            }
+                        }
+                        None => col.clone(),
+                    };
 
-                for col in batches.columns() {
-                    fields.push(ColumnVector::ArrowVector(ArrowFieldVector {
-                        field: col.clone(),
-                    }));
+                    fields.push(ColumnVector::ArrowVector(ArrowFieldVector { field: col }));
                 }
 
-                return Some(RecordBatch {
+                Some(Ok(RecordBatch {
                     schema: local_schema,
                     fields,
-                });
+                }))
             }
-            None => return None,
+            None => None,
         }
     }
 }
@@ -0,0 +1,122 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    sync::Arc,
+};
+
+use arrow::datatypes::Schema as ArrowSchema;
+use arrow::ipc::reader::{FileReader, StreamReader};
+
+use crate::datasource::{Iterators, projection_indices};
+use crate::datatypes::arrow_field_vector::ArrowFieldVector;
+use crate::datatypes::column_vector::ColumnVector;
+use crate::datatypes::schema::schema_from_arrow_schema;
+use crate::datatypes::{record_batch::RecordBatch, schema::Schema};
+use crate::error::Result;
+
+/** Magic bytes an Arrow IPC file starts and ends with, streams have no such header */
+const ARROW_MAGIC: &[u8; 6] = b"ARROW1";
+
+/** Layout of an Arrow IPC file */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IpcFormat {
+    /** Random access file format, with a footer indexing the batches */
+    File,
+    /** Streaming format, a schema message followed by batches */
+    Stream,
+}
+
+pub struct ArrowIpcDataSource {
+    pub path: String,
+
+    format: IpcFormat,
+    schema: Arc<ArrowSchema>,
+}
+
+impl ArrowIpcDataSource {
+    /** Open an IPC file or stream, telling them apart by the magic bytes of the file format */
+    pub fn try_new(path: String) -> Result<Self> {
+        let mut magic = [0u8; 6];
+        let read = File::open(&path)?.read(&mut magic)?;
+        let format = if read == magic.len() && &magic == ARROW_MAGIC {
+            IpcFormat::File
+        } else {
+            IpcFormat::Stream
+        };
+
+        let file = File::open(&path)?;
+        let schema = match format {
+            IpcFormat::File => FileReader::try_new_buffered(file, None)?.schema(),
+            IpcFormat::Stream => StreamReader::try_new_buffered(file, None)?.schema(),
+        };
+
+        Ok(Self {
+            path,
+            format,
+            schema,
+        })
+    }
+
+    pub fn format(&self) -> IpcFormat {
+        self.format
+    }
+
+    pub fn schema(&self) -> Arc<Schema> {
+        Arc::new(schema_from_arrow_schema(self.schema.clone()))
+    }
+
+    /** Only the buffers of the projected columns are decoded */
+    pub fn scan(&self, projection: Vec<String>) -> Result<Iterators> {
+        let indices = projection_indices(&self.schema, &projection)?;
+        let projected_schema = Arc::new(self.schema.project(&indices)?);
+
+        let file = File::open(&self.path)?;
+        let reader = match self.format {
+            IpcFormat::File => IpcReader::File(FileReader::try_new_buffered(file, Some(indices))?),
+            IpcFormat::Stream => {
+                IpcReader::Stream(StreamReader::try_new_buffered(file, Some(indices))?)
+            }
+        };
+
+        Ok(Iterators::ArrowIpc(Box::new(ArrowIpcIterator {
+            reader,
+            schema: projected_schema,
+        })))
+    }
+}
+
+pub enum IpcReader {
+    File(FileReader<BufReader<File>>),
+    Stream(StreamReader<BufReader<File>>),
+}
+
+pub struct ArrowIpcIterator {
+    reader: IpcReader,
+    schema: Arc<ArrowSchema>,
+}
+
+impl Iterator for ArrowIpcIterator {
+    type Item = Result<RecordBatch>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let batch = match &mut self.reader {
+            IpcReader::File(reader) => reader.next()?,
+            IpcReader::Stream(reader) => reader.next()?,
+        };
+        let batch = match batch {
+            Ok(batch) => batch,
+            Err(e) => return Some(Err(e.into())),
+        };
+
+        // The decoded arrays are wrapped as they are, no buffer is copied
+        let fields: Vec<ColumnVector> = batch
+            .columns()
+            .iter()
+            .map(|col| ColumnVector::ArrowVector(ArrowFieldVector { field: col.clone() }))
+            .collect();
+
+        Some(Ok(RecordBatch {
+            schema: schema_from_arrow_schema(self.schema.clone()),
+            fields,
+        }))
+    }
+}
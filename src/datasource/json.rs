@@ -0,0 +1,151 @@
+use std::{fs::File, io::BufReader, sync::Arc};
+
+use arrow::datatypes::Schema as ArrowSchema;
+use arrow::json::{
+    Reader as JsonArrowReader, ReaderBuilder, reader::infer_json_schema_from_seekable,
+};
+
+use crate::datasource::{Iterators, projection_indices};
+use crate::datatypes::arrow_field_vector::ArrowFieldVector;
+use crate::datatypes::column_vector::ColumnVector;
+use crate::datatypes::schema::schema_from_arrow_schema;
+use crate::datatypes::{record_batch::RecordBatch, schema::Schema};
+use crate::error::Result;
+
+/** Options for reading newline delimited JSON files, one object per line */
+#[derive(Clone, Debug)]
+pub struct JsonOptions {
+    /** Schema of the file, inferred from the first rows when missing */
+    pub schema: Option<ArrowSchema>,
+    pub batch_size: usize,
+    /** Number of lines sampled to infer the schema, every line when unset */
+    pub infer_rows: Option<usize>,
+}
+
+impl Default for JsonOptions {
+    fn default() -> Self {
+        Self {
+            schema: None,
+            batch_size: 1024,
+            infer_rows: None,
+        }
+    }
+}
+
+impl JsonOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_schema(mut self, schema: ArrowSchema) -> Self {
+        self.schema = Some(schema);
+        self
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    pub fn with_infer_rows(mut self, infer_rows: usize) -> Self {
+        self.infer_rows = Some(infer_rows);
+        self
+    }
+}
+
+pub struct JsonDataSource {
+    pub file_path: String,
+
+    options: JsonOptions,
+    schema: Arc<ArrowSchema>,
+}
+
+impl JsonDataSource {
+    /**
+     * Create a source from options, inferring the schema from the file when none is given. Nested
+     * objects become struct columns and arrays become list columns.
+     */
+    pub fn try_new(file_path: String, options: JsonOptions) -> Result<Self> {
+        let schema = match options.schema.clone() {
+            Some(schema) => schema,
+            None => {
+                let mut file = BufReader::new(File::open(&file_path)?);
+                let (schema, _) = infer_json_schema_from_seekable(&mut file, options.infer_rows)?;
+                schema
+            }
+        };
+
+        Ok(Self {
+            file_path,
+            options,
+            schema: Arc::new(schema),
+        })
+    }
+
+    pub fn schema(&self) -> Arc<Schema> {
+        Arc::new(schema_from_arrow_schema(self.schema.clone()))
+    }
+
+    pub fn scan(&self, projection: Vec<String>) -> Result<Iterators> {
+        let file = File::open(self.file_path.clone())?;
+
+        let iter = JsonIterator::new(projection, file, self.schema.clone(), &self.options)?;
+
+        Ok(Iterators::Json(iter))
+    }
+}
+
+pub struct JsonIterator {
+    reader: JsonArrowReader<BufReader<File>>,
+    schema: Arc<ArrowSchema>,
+}
+
+impl JsonIterator {
+    pub fn new(
+        projected_columns: Vec<String>,
+        file: File,
+        schema: Arc<ArrowSchema>,
+        options: &JsonOptions,
+    ) -> Result<Self> {
+        let indices = projection_indices(&schema, &projected_columns)?;
+        let projected_schema = Arc::new(schema.project(&indices)?);
+
+        // Keys missing from the projected schema are skipped without being decoded
+        let reader = ReaderBuilder::new(projected_schema.clone())
+            .with_batch_size(options.batch_size)
+            .build(BufReader::new(file))?;
+
+        Ok(Self {
+            reader,
+            schema: projected_schema,
+        })
+    }
+}
+
+impl Iterator for JsonIterator {
+    type Item = Result<RecordBatch>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let local_schema = schema_from_arrow_schema(self.schema.clone());
+
+        match self.reader.next() {
+            Some(batch) => {
+                let batch = match batch {
+                    Ok(batch) => batch,
+                    Err(e) => return Some(Err(e.into())),
+                };
+
+                let fields: Vec<ColumnVector> = batch
+                    .columns()
+                    .iter()
+                    .map(|col| ColumnVector::ArrowVector(ArrowFieldVector { field: col.clone() }))
+                    .collect();
+
+                Some(Ok(RecordBatch {
+                    schema: local_schema,
+                    fields,
+                }))
+            }
+            None => None,
+        }
+    }
+}
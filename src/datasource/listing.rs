@@ -0,0 +1,565 @@
+use std::{
+    collections::VecDeque,
+    fs,
+    path::Path,
+    sync::{
+        Arc, Mutex,
+        mpsc::{Receiver, sync_channel},
+    },
+    thread,
+};
+
+use arrow::{
+    array::{ArrayRef, StringArray, UInt32Array, new_null_array},
+    compute::take,
+    datatypes::{DataType, Field, Schema as ArrowSchema},
+};
+
+use crate::{
+    datasource::{
+        DataSource, DataSourceTrait, Iterators,
+        csv::{CsvDataSource, CsvOptions},
+        ipc::ArrowIpcDataSource,
+        json::{JsonDataSource, JsonOptions},
+        parquet::ParquetDataSource,
+        partition::{
+            listing_root, parse_partition_values, partition_arrays, partition_fields,
+            partition_may_match,
+        },
+        projection_indices,
+        pruning::PruningPredicate,
+    },
+    datatypes::{
+        arrow_field_vector::ArrowFieldVector,
+        column_vector::ColumnVector,
+        record_batch::RecordBatch,
+        schema::{Schema, schema_from_arrow_schema},
+    },
+    error::{Result, UnakiteError},
+    logical_plan::expr::ExprRef,
+};
+
+/** Format of the files in a listing table */
+#[derive(Clone, Debug)]
+pub enum FileFormat {
+    Csv(CsvOptions),
+    Parquet,
+    Json(JsonOptions),
+    ArrowIpc,
+}
+
+impl FileFormat {
+    fn default_extension(&self) -> &'static str {
+        match self {
+            FileFormat::Csv(_) => "csv",
+            FileFormat::Parquet => "parquet",
+            FileFormat::Json(_) => "json",
+            FileFormat::ArrowIpc => "arrow",
+        }
+    }
+
+    fn open(&self, path: &str) -> Result<DataSource> {
+        match self {
+            FileFormat::Csv(options) => Ok(DataSource::CSV(CsvDataSource::try_new(
+                path.to_string(),
+                options.clone(),
+            )?)),
+            FileFormat::Parquet => Ok(DataSource::Parquet(ParquetDataSource::new(
+                path.to_string(),
+            )?)),
+            FileFormat::Json(options) => Ok(DataSource::Json(JsonDataSource::try_new(
+                path.to_string(),
+                options.clone(),
+            )?)),
+            FileFormat::ArrowIpc => Ok(DataSource::ArrowIpc(ArrowIpcDataSource::try_new(
+                path.to_string(),
+            )?)),
+        }
+    }
+}
+
+/** Options for reading a directory or glob of files as one table */
+#[derive(Clone, Debug)]
+pub struct ListingOptions {
+    pub format: FileFormat,
+    /** Extension of the files picked up from a directory, defaults to the one of the format */
+    pub file_extension: Option<String>,
+    /** Scan several files at once, batches then arrive in no particular order */
+    pub parallel: bool,
+    /** Name of an extra column holding the path of the file each row was read from */
+    pub file_name_column: Option<String>,
+    /** Types of `key=value` partition columns, undeclared ones are inferred from the values */
+    pub partition_types: Vec<(String, DataType)>,
+}
+
+impl ListingOptions {
+    pub fn new(format: FileFormat) -> Self {
+        Self {
+            format,
+            file_extension: None,
+            parallel: false,
+            file_name_column: None,
+            partition_types: vec![],
+        }
+    }
+
+    pub fn with_file_extension(mut self, file_extension: &str) -> Self {
+        self.file_extension = Some(file_extension.trim_start_matches('.').to_string());
+        self
+    }
+
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    pub fn with_file_name_column(mut self, name: &str) -> Self {
+        self.file_name_column = Some(name.to_string());
+        self
+    }
+
+    /** Read a partition column as the given type, e.g. `dt=2026-10-18` as Date32 */
+    pub fn with_partition_type(mut self, name: &str, data_type: DataType) -> Self {
+        self.partition_types.push((name.to_string(), data_type));
+        self
+    }
+}
+
+/**
+ * A table made of every file matching a directory or glob pattern. The schema is the union of
+ * the file schemas, a column missing from a file reads as null for the rows of that file.
+ *
+ * Hive style directories such as `year=2026/month=10` add partition columns after the file
+ * columns. Filters on partition columns skip every file of a non matching directory without
+ * opening it.
+ */
+pub struct ListingDataSource {
+    pub path: String,
+
+    options: ListingOptions,
+    files: Vec<Arc<ListedFile>>,
+    partitions: Vec<Field>,
+    schema: Arc<ArrowSchema>,
+}
+
+/** One file of a listing table, opened when the table is created */
+pub struct ListedFile {
+    path: String,
+    source: DataSource,
+    schema: Arc<Schema>,
+    /** One row array per partition column */
+    partitions: Vec<ArrayRef>,
+}
+
+impl ListingDataSource {
+    /** List and open every file, fails if none match or their schemas conflict */
+    pub fn try_new(path: &str, options: ListingOptions) -> Result<Self> {
+        let extension = options
+            .file_extension
+            .clone()
+            .unwrap_or_else(|| options.format.default_extension().to_string());
+
+        let root = listing_root(path);
+        let listed: Vec<_> = list_files(path, &extension)?
+            .into_iter()
+            .map(|file| {
+                let values = parse_partition_values(&root, Path::new(&file));
+                (file, values)
+            })
+            .collect();
+        let partitions = partition_fields(&listed, &options.partition_types)?;
+
+        let files: Vec<Arc<ListedFile>> = listed
+            .into_iter()
+            .map(|(path, values)| {
+                let partitions = partition_arrays(&path, &values, &partitions)?;
+                let source = options.format.open(&path)?;
+                let schema = source.schema();
+                Ok(Arc::new(ListedFile {
+                    path,
+                    source,
+                    schema,
+                    partitions,
+                }))
+            })
+            .collect::<Result<_>>()?;
+
+        let schema = unify_schemas(&files, &partitions, &options)?;
+
+        Ok(Self {
+            path: path.to_string(),
+            options,
+            files,
+            partitions,
+            schema: Arc::new(schema),
+        })
+    }
+
+    /** Paths of the files making up the table, in scan order */
+    pub fn files(&self) -> Vec<String> {
+        self.files.iter().map(|it| it.path.clone()).collect()
+    }
+
+    pub fn schema(&self) -> Arc<Schema> {
+        Arc::new(schema_from_arrow_schema(self.schema.clone()))
+    }
+
+    pub fn scan(&self, projection: Vec<String>) -> Result<Iterators> {
+        self.scan_with_filters(projection, &[])
+    }
+
+    /** Files that may hold rows matching the filters, judged by their partition values alone */
+    pub fn files_matching(&self, filters: &[ExprRef]) -> Result<Vec<String>> {
+        Ok(self
+            .prune_files(filters)?
+            .iter()
+            .map(|it| it.path.clone())
+            .collect())
+    }
+
+    fn prune_files(&self, filters: &[ExprRef]) -> Result<Vec<Arc<ListedFile>>> {
+        let predicates = PruningPredicate::from_filters(filters);
+
+        let mut files = vec![];
+        for file in self.files.iter() {
+            if partition_may_match(&predicates, &self.partitions, &file.partitions)? {
+                files.push(file.clone());
+            }
+        }
+        Ok(files)
+    }
+
+    /**
+     * Files in partitions ruled out by the filters are skipped, the filters are handed to the
+     * remaining files so Parquet files can skip row groups
+     */
+    pub fn scan_with_filters(
+        &self,
+        projection: Vec<String>,
+        filters: &[ExprRef],
+    ) -> Result<Iterators> {
+        let indices = projection_indices(&self.schema, &projection)?;
+        let schema = Arc::new(self.schema.project(&indices)?);
+
+        let scans: VecDeque<FileScan> = self
+            .prune_files(filters)?
+            .into_iter()
+            .map(|file| FileScan::new(file, &schema, &self.partitions, &self.options, filters))
+            .collect();
+
+        let iter = if self.options.parallel && scans.len() > 1 {
+            ListingIterator::parallel(scans)
+        } else {
+            ListingIterator::Sequential {
+                scans,
+                current: None,
+            }
+        };
+
+        Ok(Iterators::Listing(Box::new(iter)))
+    }
+}
+
+/**
+ * Files matching `path`. A path with `*`, `?` or `[` is a glob pattern, a directory is walked
+ * recursively for files with the given extension, skipping hidden files and names starting with
+ * `_` such as `_SUCCESS` markers.
+ */
+fn list_files(path: &str, extension: &str) -> Result<Vec<String>> {
+    let mut files = vec![];
+
+    if path.contains(['*', '?', '[']) {
+        let paths = glob::glob(path)
+            .map_err(|e| UnakiteError::Plan(format!("Invalid glob pattern {}: {}", path, e)))?;
+        for entry in paths {
+            let entry = entry.map_err(|e| UnakiteError::Io(e.into()))?;
+            if entry.is_file() {
+                files.push(entry.to_string_lossy().to_string());
+            }
+        }
+    } else if Path::new(path).is_dir() {
+        walk(Path::new(path), extension, &mut files)?;
+    } else {
+        fs::metadata(path)?;
+        files.push(path.to_string());
+    }
+
+    if files.is_empty() {
+        return Err(UnakiteError::Plan(format!("No files found at {}", path)));
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn walk(dir: &Path, extension: &str, files: &mut Vec<String>) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        let name = path.file_name().map(|it| it.to_string_lossy().to_string());
+        if name.is_none_or(|it| it.starts_with('.') || it.starts_with('_')) {
+            continue;
+        }
+
+        if path.is_dir() {
+            walk(&path, extension, files)?;
+        } else if path.extension().is_some_and(|it| it == extension) {
+            files.push(path.to_string_lossy().to_string());
+        }
+    }
+    Ok(())
+}
+
+/**
+ * Merge the file schemas by column name in order of first appearance. Columns missing from any
+ * file are nullable, a column with different types in two files is an error.
+ */
+fn unify_schemas(
+    files: &[Arc<ListedFile>],
+    partitions: &[Field],
+    options: &ListingOptions,
+) -> Result<ArrowSchema> {
+    let mut fields: Vec<Field> = vec![];
+
+    for file in files.iter() {
+        for field in file.schema.fields.iter() {
+            match fields.iter_mut().find(|it| it.name() == &field.name) {
+                Some(existing) if existing.data_type() != &field.data_type => {
+                    return Err(UnakiteError::Schema(format!(
+                        "Column {} is {} in {} but {} in an earlier file",
+                        field.name,
+                        field.data_type,
+                        file.path,
+                        existing.data_type()
+                    )));
+                }
+                Some(existing) => {
+                    let nullable = existing.is_nullable() || field.nullable;
+                    existing.set_nullable(nullable);
+                }
+                None => fields.push(field.clone().to_arrow()),
+            }
+        }
+    }
+
+    for field in fields.iter_mut() {
+        if files
+            .iter()
+            .any(|file| !file.schema.fields.iter().any(|it| it.name == *field.name()))
+        {
+            field.set_nullable(true);
+        }
+    }
+
+    for partition in partitions.iter() {
+        if fields.iter().any(|it| it.name() == partition.name()) {
+            return Err(UnakiteError::Schema(format!(
+                "Partition column {} clashes with a file column",
+                partition.name()
+            )));
+        }
+        fields.push(partition.clone());
+    }
+
+    if let Some(name) = &options.file_name_column {
+        if fields.iter().any(|it| it.name() == name) {
+            return Err(UnakiteError::Schema(format!(
+                "File name column {} clashes with a file or partition column",
+                name
+            )));
+        }
+        fields.push(Field::new(name, DataType::Utf8, false));
+    }
+
+    Ok(ArrowSchema::new(fields))
+}
+
+/** Where a column of the table comes from for one file */
+enum ColumnSource {
+    /** Position in the batches read from the file */
+    File(usize),
+    Null(DataType),
+    /** Position in the partition values of the file */
+    Partition(usize),
+    FileName,
+}
+
+/** The scan of one file, adapting its batches to the projected table schema */
+pub struct FileScan {
+    file: Arc<ListedFile>,
+    projection: Vec<String>,
+    columns: Vec<ColumnSource>,
+    schema: Schema,
+    filters: Vec<ExprRef>,
+}
+
+impl FileScan {
+    fn new(
+        file: Arc<ListedFile>,
+        schema: &ArrowSchema,
+        partitions: &[Field],
+        options: &ListingOptions,
+        filters: &[ExprRef],
+    ) -> Self {
+        let mut projection: Vec<String> = vec![];
+        let columns = schema
+            .fields()
+            .iter()
+            .map(|field| {
+                if options.file_name_column.as_ref() == Some(field.name()) {
+                    return ColumnSource::FileName;
+                }
+                if let Some(i) = partitions.iter().position(|it| it.name() == field.name()) {
+                    return ColumnSource::Partition(i);
+                }
+                if !file.schema.fields.iter().any(|it| it.name == *field.name()) {
+                    return ColumnSource::Null(field.data_type().clone());
+                }
+
+                let position = match projection.iter().position(|it| it == field.name()) {
+                    Some(position) => position,
+                    None => {
+                        projection.push(field.name().clone());
+                        projection.len() - 1
+                    }
+                };
+                ColumnSource::File(position)
+            })
+            .collect();
+
+        // Read a single column when none is needed, an empty projection reads every column
+        if projection.is_empty()
+            && let Some(first) = file.schema.fields.first()
+        {
+            projection.push(first.name.clone());
+        }
+
+        Self {
+            file,
+            projection,
+            columns,
+            schema: schema_from_arrow_schema(Arc::new(schema.clone())),
+            filters: filters.to_vec(),
+        }
+    }
+
+    fn open(&self) -> Result<Iterators> {
+        self.file
+            .source
+            .scan_with_filters(self.projection.clone(), &self.filters)
+    }
+
+    fn adapt(&self, batch: RecordBatch) -> Result<RecordBatch> {
+        let rows = batch.row_count();
+
+        let fields = self
+            .columns
+            .iter()
+            .map(|column| {
+                let field = match column {
+                    ColumnSource::File(i) => return Ok(batch.fields[*i].clone()),
+                    ColumnSource::Null(data_type) => new_null_array(data_type, rows),
+                    ColumnSource::Partition(i) => {
+                        // Repeat the single partition value for every row
+                        let indices = UInt32Array::from(vec![0; rows]);
+                        take(&self.file.partitions[*i], &indices, None)?
+                    }
+                    ColumnSource::FileName => {
+                        Arc::new(StringArray::from(vec![self.file.path.as_str(); rows]))
+                    }
+                };
+                Ok(ColumnVector::ArrowVector(ArrowFieldVector { field }))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(RecordBatch {
+            schema: self.schema.clone(),
+            fields,
+        })
+    }
+
+    /** Scan the whole file, handing every adapted batch to `send` until it returns false */
+    fn run(&self, send: impl Fn(Result<RecordBatch>) -> bool) {
+        let batches = match self.open() {
+            Ok(batches) => batches,
+            Err(e) => {
+                send(Err(e));
+                return;
+            }
+        };
+
+        for batch in batches {
+            if !send(batch.and_then(|it| self.adapt(it))) {
+                return;
+            }
+        }
+    }
+}
+
+pub enum ListingIterator {
+    /** Files are read one after another, in listing order */
+    Sequential {
+        scans: VecDeque<FileScan>,
+        current: Option<Box<(FileScan, Iterators)>>,
+    },
+    /** Worker threads read files and send their batches over a channel */
+    Parallel {
+        batches: Receiver<Result<RecordBatch>>,
+    },
+}
+
+impl ListingIterator {
+    fn parallel(scans: VecDeque<FileScan>) -> Self {
+        let workers = thread::available_parallelism()
+            .map_or(1, |it| it.get())
+            .min(scans.len());
+        let scans = Arc::new(Mutex::new(scans));
+        let (sender, receiver) = sync_channel(workers * 2);
+
+        for _ in 0..workers {
+            let scans = scans.clone();
+            let sender = sender.clone();
+
+            thread::spawn(move || {
+                loop {
+                    let scan = scans
+                        .lock()
+                        .unwrap_or_else(|poisoned| poisoned.into_inner())
+                        .pop_front();
+                    let Some(scan) = scan else {
+                        return;
+                    };
+
+                    // A closed channel means the consumer stopped reading
+                    scan.run(|batch| sender.send(batch).is_ok());
+                }
+            });
+        }
+
+        ListingIterator::Parallel { batches: receiver }
+    }
+}
+
+impl Iterator for ListingIterator {
+    type Item = Result<RecordBatch>;
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            ListingIterator::Sequential { scans, current } => loop {
+                if let Some(scanning) = current {
+                    let (scan, batches) = scanning.as_mut();
+                    match batches.next() {
+                        Some(batch) => return Some(batch.and_then(|it| scan.adapt(it))),
+                        None => *current = None,
+                    }
+                }
+
+                let scan = scans.pop_front()?;
+                match scan.open() {
+                    Ok(batches) => *current = Some(Box::new((scan, batches))),
+                    Err(e) => return Some(Err(e)),
+                }
+            },
+            ListingIterator::Parallel { batches } => batches.recv().ok(),
+        }
+    }
+}
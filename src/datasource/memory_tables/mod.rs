@@ -1,63 +1,128 @@
-use arrow::datatypes::Schema as ArrowSchema;
-use std::{collections::BTreeMap, sync::Arc};
-
-use arrow::datatypes::Schema;
-
-use crate::datatypes::{column_vector::ColumnVector, record_batch::RecordBatch};
-
-macro_rules! define_datasource_enum {
-    (
-        $(
-            $variant:ident({
-                $($field:ident : $ty:ty),* $(,)?
-            })
-        ),* $(,)?
-    ) => {
-        pub enum InMemoryDataSource {
-            $(
-                $variant {
-                    $(
-                        $field: $ty,
-                    )*
-                },
-            )*
-        }
-    };
-}
+use std::sync::Arc;
+
+use arrow::{array::RecordBatch as ArrowRecordBatch, datatypes::Schema as ArrowSchema};
+
+use crate::{
+    datasource::{Iterators, projection_indices},
+    datatypes::{
+        column_vector::ColumnVectorTrait,
+        record_batch::RecordBatch,
+        schema::{Schema, schema_from_arrow_schema},
+    },
+    error::{Result, UnakiteError},
+};
 
-define_datasource_enum! {
-    BTree({
-        data: BTreeMap<i32, ColumnVector>,
-        schema: Schema
-    }),
-    DynList({
-        data: Vec<ColumnVector>,
-        schema: Schema
-    }),
+/**
+ * A table held in memory as a list of record batches. Scans share the batches instead of
+ * copying them, which makes the source cheap to use in tests and for caching query results.
+ */
+pub struct InMemoryDataSource {
+    schema: Arc<Schema>,
+    batches: Arc<Vec<RecordBatch>>,
+    batch_size: Option<usize>,
 }
 
 impl InMemoryDataSource {
-    pub fn schema(&self) -> Schema {
-        todo!()
+    /** Fails if a batch does not have the columns and types of `schema` */
+    pub fn new(schema: Schema, batches: Vec<RecordBatch>) -> Result<Self> {
+        for batch in batches.iter() {
+            check_batch(&schema, batch)?;
+        }
+
+        Ok(Self {
+            schema: Arc::new(schema),
+            batches: Arc::new(batches),
+            batch_size: None,
+        })
     }
 
-    pub fn scan(&self, _projection: Vec<String>) {
-        match self {
-            InMemoryDataSource::BTree { data: _, schema: _ } => todo!(),
-            InMemoryDataSource::DynList { data: _, schema: _ } => todo!(),
-        }
+    /** Build a table from arrow record batches sharing `schema` */
+    pub fn from_arrow(schema: Arc<ArrowSchema>, batches: Vec<ArrowRecordBatch>) -> Result<Self> {
+        let batches = batches.into_iter().map(RecordBatch::from).collect();
+
+        Self::new(schema_from_arrow_schema(schema), batches)
+    }
+
+    /** Split stored batches so a scan returns at most `batch_size` rows at a time */
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = Some(batch_size.max(1));
+        self
     }
+
+    pub fn schema(&self) -> Arc<Schema> {
+        self.schema.clone()
+    }
+
+    pub fn scan(&self, projection: Vec<String>) -> Result<Iterators> {
+        let indices = projection_indices(&self.schema.to_arrow(), &projection)?;
+
+        let iter = InMemoryDataSourceIterator {
+            batches: self.batches.clone(),
+            projected_schema: self.schema.project(indices.clone()),
+            indices,
+            batch_size: self.batch_size,
+            batch: 0,
+            offset: 0,
+        };
+
+        Ok(Iterators::Memory(iter))
+    }
+}
+
+fn check_batch(schema: &Schema, batch: &RecordBatch) -> Result<()> {
+    let types_match = batch.fields.len() == schema.fields.len()
+        && batch
+            .fields
+            .iter()
+            .zip(schema.fields.iter())
+            .all(|(column, field)| column.get_type() == field.data_type);
+
+    if !types_match {
+        return Err(UnakiteError::Schema(format!(
+            "Batch with schema {:?} does not match table schema {:?}",
+            batch.schema, schema
+        )));
+    }
+    Ok(())
 }
 
 pub struct InMemoryDataSourceIterator {
-    _reader: InMemoryDataSource,
+    batches: Arc<Vec<RecordBatch>>,
+
+    projected_schema: Schema,
+    indices: Vec<usize>,
+    batch_size: Option<usize>,
 
-    _projected_schema: Arc<ArrowSchema>,
+    /** Position of the next rows to return, as a batch index and a row offset inside it */
+    batch: usize,
+    offset: usize,
 }
 
 impl Iterator for InMemoryDataSourceIterator {
-    type Item = RecordBatch;
+    type Item = Result<RecordBatch>;
     fn next(&mut self) -> Option<Self::Item> {
-        unimplemented!()
+        loop {
+            let batch = self.batches.get(self.batch)?;
+            let remaining = batch.row_count().saturating_sub(self.offset);
+
+            if remaining == 0 {
+                self.batch += 1;
+                self.offset = 0;
+                continue;
+            }
+
+            let len = self.batch_size.map_or(remaining, |it| it.min(remaining));
+            let fields = self
+                .indices
+                .iter()
+                .map(|i| batch.fields[*i].slice(self.offset, len))
+                .collect();
+            self.offset += len;
+
+            return Some(Ok(RecordBatch {
+                schema: self.projected_schema.clone(),
+                fields,
+            }));
+        }
     }
 }
@@ -1,29 +1,56 @@
 pub mod csv;
+pub mod ipc;
+pub mod json;
+pub mod listing;
 
 pub mod memory_tables;
 pub mod parquet;
+pub mod partition;
+pub mod pruning;
+pub mod sink;
 pub mod test;
 
 use std::sync::Arc;
 
+use arrow::datatypes::Schema as ArrowSchema;
+
 use crate::{
     datasource::{
         csv::{CsvDataSource, CsvIterator},
+        ipc::{ArrowIpcDataSource, ArrowIpcIterator},
+        json::{JsonDataSource, JsonIterator},
+        listing::{ListingDataSource, ListingIterator},
+        memory_tables::{InMemoryDataSource, InMemoryDataSourceIterator},
         parquet::{ParquetDataSource, ParquetIterator},
     },
     datatypes::{record_batch::RecordBatch, schema::Schema},
+    error::{Result, UnakiteError},
+    logical_plan::expr::ExprRef,
 };
 
 pub trait DataSourceTrait {
     /** Return the schema for the underlying data source */
     fn schema(&self) -> Arc<Schema>;
 
-    /** Scan the data source, selecting the specified columns */
-    fn scan(&self, projection: Vec<String>) -> Iterators;
+    /**
+     * Scan the data source, selecting the specified columns. Fails if the source can not be
+     * opened, errors while reading are returned by the iterator.
+     */
+    fn scan(&self, projection: Vec<String>) -> Result<Iterators>;
+
+    /**
+     * Like `scan`, also passing predicates the source may use to skip data that can not match.
+     * Skipping is best effort, the returned rows still have to be filtered.
+     */
+    fn scan_with_filters(&self, projection: Vec<String>, filters: &[ExprRef]) -> Result<Iterators>;
 }
 pub enum DataSource {
     CSV(CsvDataSource),
     Parquet(ParquetDataSource),
+    Memory(InMemoryDataSource),
+    Listing(ListingDataSource),
+    Json(JsonDataSource),
+    ArrowIpc(ArrowIpcDataSource),
 }
 impl DataSourceTrait for DataSource {
     /** Return the schema for the underlying data source */
@@ -31,30 +58,78 @@ impl DataSourceTrait for DataSource {
         match self {
             DataSource::CSV(csv) => csv.schema(),
             DataSource::Parquet(parquet) => parquet.schema(),
+            DataSource::Memory(memory) => memory.schema(),
+            DataSource::Listing(listing) => listing.schema(),
+            DataSource::Json(json) => json.schema(),
+            DataSource::ArrowIpc(ipc) => ipc.schema(),
         }
     }
 
     /** Scan the data source, selecting the specified columns */
-    fn scan(&self, projection: Vec<String>) -> Iterators {
-        let iter = match self {
+    fn scan(&self, projection: Vec<String>) -> Result<Iterators> {
+        match self {
             DataSource::CSV(csv) => csv.scan(projection),
             DataSource::Parquet(parquet) => parquet.scan(projection),
-        };
-        iter
+            DataSource::Memory(memory) => memory.scan(projection),
+            DataSource::Listing(listing) => listing.scan(projection),
+            DataSource::Json(json) => json.scan(projection),
+            DataSource::ArrowIpc(ipc) => ipc.scan(projection),
+        }
+    }
+
+    fn scan_with_filters(&self, projection: Vec<String>, filters: &[ExprRef]) -> Result<Iterators> {
+        match self {
+            DataSource::CSV(csv) => csv.scan(projection),
+            DataSource::Parquet(parquet) => parquet.scan_with_filters(projection, filters),
+            DataSource::Memory(memory) => memory.scan(projection),
+            DataSource::Listing(listing) => listing.scan_with_filters(projection, filters),
+            DataSource::Json(json) => json.scan(projection),
+            DataSource::ArrowIpc(ipc) => ipc.scan(projection),
+        }
     }
 }
 
+/**
+ * Positions of the projected columns in `schema`, in the order they were requested. An empty
+ * projection selects every column.
+ */
+pub(crate) fn projection_indices(
+    schema: &ArrowSchema,
+    projection: &[String],
+) -> Result<Vec<usize>> {
+    if projection.is_empty() {
+        return Ok((0..schema.fields().len()).collect());
+    }
+
+    projection
+        .iter()
+        .map(|name| {
+            schema
+                .index_of(name)
+                .map_err(|_| UnakiteError::Schema(format!("No column named {}", name)))
+        })
+        .collect()
+}
+
 pub enum Iterators {
-    Csv(CsvIterator),
+    Csv(Box<CsvIterator>),
     Parquet(ParquetIterator),
+    Memory(InMemoryDataSourceIterator),
+    Listing(Box<ListingIterator>),
+    Json(JsonIterator),
+    ArrowIpc(Box<ArrowIpcIterator>),
 }
 
 impl Iterator for Iterators {
-    type Item = RecordBatch;
+    type Item = Result<RecordBatch>;
     fn next(&mut self) -> Option<Self::Item> {
         match self {
             Iterators::Csv(csv_iterator) => csv_iterator.next(),
             Iterators::Parquet(parquet_iterator) => parquet_iterator.next(),
+            Iterators::Memory(memory_iterator) => memory_iterator.next(),
+            Iterators::Listing(listing_iterator) => listing_iterator.next(),
+            Iterators::Json(json_iterator) => json_iterator.next(),
+            Iterators::ArrowIpc(ipc_iterator) => ipc_iterator.next(),
         }
     }
 }
@@ -1,109 +1,143 @@
-use std::{
-    fs::File,
-    sync::{Arc, Mutex},
-};
+use std::{fs::File, sync::Arc};
 
-use arrow::datatypes::{Field, Schema as ArrowSchema};
-use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
+use arrow::datatypes::Schema as ArrowSchema;
+use parquet::arrow::{
+    ProjectionMask,
+    arrow_reader::{ArrowReaderOptions, ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder},
+};
 
 use crate::{
-    datasource::Iterators,
+    datasource::{
+        Iterators, projection_indices,
+        pruning::{PruningPredicate, prune_pages, prune_row_groups},
+    },
     datatypes::{
         arrow_field_vector::ArrowFieldVector,
         column_vector::ColumnVector,
         record_batch::RecordBatch,
         schema::{Schema, schema_from_arrow_schema},
     },
+    error::Result,
+    logical_plan::expr::ExprRef,
 };
 
 pub struct ParquetDataSource {
     pub path: String,
     schema: Arc<ArrowSchema>,
-    data: Arc<Mutex<ParquetRecordBatchReader>>,
 }
 
 impl ParquetDataSource {
-    pub fn new(path: String) -> Self {
-        let file = File::open(path.clone()).unwrap();
-        let builder = ParquetRecordBatchReaderBuilder::try_new(file).unwrap();
+    /** Reads the schema from the file footer, the data is only read when scanned */
+    pub fn new(path: String) -> Result<Self> {
+        let file = File::open(path.clone())?;
+        let builder = ParquetRecordBatchReaderBuilder::try_new(file)?;
         let schema = builder.schema().clone();
-        let reader = builder.build().unwrap();
 
-        Self {
-            path: path,
-            schema,
-            data: Arc::new(Mutex::new(reader)),
-        }
+        Ok(Self { path, schema })
     }
     pub fn schema(&self) -> Arc<Schema> {
         Arc::new(schema_from_arrow_schema(self.schema.clone()))
     }
 
-    pub fn scan(&self, projection: Vec<String>) -> Iterators {
-        match File::open(self.path.clone()) {
-            Err(_) => panic!("File Not found {}", self.path),
-            Ok(_) => {}
-        };
+    /** Every scan opens its own reader that only decodes the projected columns */
+    pub fn scan(&self, projection: Vec<String>) -> Result<Iterators> {
+        self.scan_with_filters(projection, &[])
+    }
+
+    /**
+     * Scan, skipping row groups whose min/max statistics show no row can match the filters. When
+     * the file has a page index, pages inside the remaining row groups are skipped the same way.
+     */
+    pub fn scan_with_filters(
+        &self,
+        projection: Vec<String>,
+        filters: &[ExprRef],
+    ) -> Result<Iterators> {
+        let indices = projection_indices(&self.schema, &projection)?;
+
+        let file = File::open(self.path.clone())?;
+        let options = ArrowReaderOptions::new().with_page_index(true);
+        let mut builder = ParquetRecordBatchReaderBuilder::try_new_with_options(file, options)?;
+
+        let predicates = PruningPredicate::from_filters(filters);
+        if !predicates.is_empty() {
+            let metadata = builder.metadata().clone();
+            let row_groups = prune_row_groups(&predicates, &self.schema, &metadata)?;
+
+            if let Some(selection) = prune_pages(&predicates, &self.schema, &metadata, &row_groups)?
+            {
+                builder = builder.with_row_selection(selection);
+            }
+            builder = builder.with_row_groups(row_groups);
+        }
 
-        let iter = ParquetIterator::new(self.schema.clone(), self.data.clone(), projection);
+        let mask = ProjectionMask::roots(builder.parquet_schema(), indices.iter().cloned());
+        let reader = builder.with_projection(mask).build()?;
 
-        Iterators::Parquet(iter)
+        let iter = ParquetIterator::new(self.schema.clone(), reader, indices)?;
+
+        Ok(Iterators::Parquet(iter))
     }
 }
 
 pub struct ParquetIterator {
-    reader: Arc<Mutex<ParquetRecordBatchReader>>,
+    reader: ParquetRecordBatchReader,
 
     projected_schema: Arc<ArrowSchema>,
+    /** Position in the decoded batch of every projected column */
+    order: Vec<usize>,
 }
 
 impl ParquetIterator {
     pub fn new(
         schema: Arc<ArrowSchema>,
-        reader: Arc<Mutex<ParquetRecordBatchReader>>,
-        projected_columns: Vec<String>,
-    ) -> Self {
-        let projected_schema: Arc<ArrowSchema>;
-
-        if !projected_columns.is_empty() {
-            let fields: Vec<Arc<Field>> = projected_columns
-                .iter()
-                .filter_map(|name| schema.fields.iter().find(|it| it.name() == name).cloned())
-                .collect();
-
-            projected_schema = Arc::new(ArrowSchema::new(fields));
-        } else {
-            projected_schema = schema.clone();
-        }
-
-        Self {
+        reader: ParquetRecordBatchReader,
+        indices: Vec<usize>,
+    ) -> Result<Self> {
+        let projected_schema = Arc::new(schema.project(&indices)?);
+
+        // The reader returns the masked columns in file order, not in the requested order
+        let mut decoded = indices.clone();
+        decoded.sort_unstable();
+        decoded.dedup();
+        let order = indices
+            .iter()
+            .map(|index| decoded.binary_search(index).unwrap_or_default())
+            .collect();
+
+        Ok(Self {
             reader,
             projected_schema,
-        }
+            order,
+        })
     }
 }
 impl Iterator for ParquetIterator {
-    type Item = RecordBatch;
+    type Item = Result<RecordBatch>;
     fn next(&mut self) -> Option<Self::Item> {
         let local_schema = schema_from_arrow_schema(self.projected_schema.clone());
-        let mut reader = self.reader.lock().unwrap();
 
-        match reader.next() {
+        match self.reader.next() {
             Some(reader_batch) => {
-                let mut fields: Vec<ColumnVector> = vec![];
-
-                let batches = reader_batch.unwrap();
-
-                for col in batches.columns() {
-                    fields.push(ColumnVector::ArrowVector(ArrowFieldVector {
-                        field: col.clone(),
-                    }));
-                }
-
-                return Some(RecordBatch {
+                let batches = match reader_batch {
+                    Ok(batches) => batches,
+                    Err(e) => return Some(Err(e.into())),
+                };
+
+                let fields: Vec<ColumnVector> = self
+                    .order
+                    .iter()
+                    .map(|i| {
+                        ColumnVector::ArrowVector(ArrowFieldVector {
+                            field: batches.column(*i).clone(),
+                        })
+                    })
+                    .collect();
+
+                Some(Ok(RecordBatch {
                     schema: local_schema,
                     fields,
-                });
+                }))
             }
             None => None,
         }
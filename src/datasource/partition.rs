@@ -0,0 +1,192 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use arrow::{
+    array::{ArrayRef, StringArray},
+    compute::{CastOptions, cast_with_options},
+    datatypes::{DataType, Field},
+};
+
+use crate::{
+    datasource::pruning::PruningPredicate,
+    error::{Result, UnakiteError},
+};
+
+/** Directory value Hive writes for a null partition key */
+pub const DEFAULT_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/** `key=value` pairs of the directories between the listing root and a file */
+pub type PartitionValues = Vec<(String, Option<String>)>;
+
+/**
+ * The directory the partition directories start from. For a glob pattern this is the part of the
+ * path before the first component holding a wildcard or a `key=value` pair, so partitions can be
+ * picked by the pattern and still be read as columns.
+ */
+pub fn listing_root(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_dir() {
+        return path.to_path_buf();
+    }
+
+    let mut root = PathBuf::new();
+    for component in path.components() {
+        if component
+            .as_os_str()
+            .to_string_lossy()
+            .contains(['*', '?', '[', '='])
+        {
+            return root;
+        }
+        root.push(component);
+    }
+
+    // A single file
+    path.parent().map(Path::to_path_buf).unwrap_or_default()
+}
+
+/** Partition values found in the directory names between `root` and `file` */
+pub fn parse_partition_values(root: &Path, file: &Path) -> PartitionValues {
+    let relative = file.strip_prefix(root).unwrap_or(file);
+    let Some(directories) = relative.parent() else {
+        return vec![];
+    };
+
+    directories
+        .components()
+        .filter_map(|component| {
+            let name = component.as_os_str().to_string_lossy();
+            let (key, value) = name.split_once('=')?;
+            let value = match value {
+                DEFAULT_PARTITION | "" => None,
+                value => Some(unescape_partition_value(value)),
+            };
+            Some((key.to_string(), value))
+        })
+        .collect()
+}
+
+/** Undo the `%XX` escapes of characters that can not appear in a directory name */
+fn unescape_partition_value(value: &str) -> String {
+    let mut bytes = vec![];
+    let mut rest = value.as_bytes();
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = tail
+            .get(..2)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        match escaped {
+            Some(decoded) if byte == b'%' => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            _ => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+/**
+ * The partition columns shared by every file. A declared type wins, otherwise a column whose
+ * values are all integers is Int64 and any other column is Utf8. Files partitioned by different
+ * keys can not be read as one table.
+ */
+pub fn partition_fields(
+    files: &[(String, PartitionValues)],
+    declared: &[(String, DataType)],
+) -> Result<Vec<Field>> {
+    let Some((_, first)) = files.first() else {
+        return Ok(vec![]);
+    };
+    let keys: Vec<&String> = first.iter().map(|(key, _)| key).collect();
+
+    for (path, values) in files.iter() {
+        if !values.iter().map(|(key, _)| key).eq(keys.iter().cloned()) {
+            return Err(UnakiteError::Schema(format!(
+                "File {} is not partitioned by [{}]",
+                path,
+                keys.iter()
+                    .map(|it| it.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )));
+        }
+    }
+
+    let fields = keys
+        .iter()
+        .enumerate()
+        .map(|(i, key)| {
+            let values = files.iter().map(|(_, values)| values[i].1.as_ref());
+
+            let data_type = match declared.iter().find(|(name, _)| name == *key) {
+                Some((_, data_type)) => data_type.clone(),
+                None if values.flatten().all(|it| it.parse::<i64>().is_ok()) => DataType::Int64,
+                None => DataType::Utf8,
+            };
+
+            let nullable = files.iter().any(|(_, values)| values[i].1.is_none());
+            Field::new(key.as_str(), data_type, nullable)
+        })
+        .collect();
+
+    Ok(fields)
+}
+
+/** Each partition value of a file as a one row array of the type of its column */
+pub fn partition_arrays(
+    path: &str,
+    values: &PartitionValues,
+    fields: &[Field],
+) -> Result<Vec<ArrayRef>> {
+    let options = CastOptions {
+        safe: false,
+        ..Default::default()
+    };
+
+    fields
+        .iter()
+        .zip(values.iter())
+        .map(|(field, (key, value))| {
+            let raw: ArrayRef = Arc::new(StringArray::from(vec![value.clone()]));
+            cast_with_options(&raw, field.data_type(), &options).map_err(|e| {
+                UnakiteError::Type(format!(
+                    "Partition {}={} of {} is not a {}: {}",
+                    key,
+                    value.as_deref().unwrap_or(DEFAULT_PARTITION),
+                    path,
+                    field.data_type(),
+                    e
+                ))
+            })
+        })
+        .collect()
+}
+
+/**
+ * Whether a file with the given partition values may hold rows matching the predicates. The
+ * value of a partition is the minimum and the maximum of the whole file.
+ */
+pub fn partition_may_match(
+    predicates: &[PruningPredicate],
+    fields: &[Field],
+    values: &[ArrayRef],
+) -> Result<bool> {
+    for predicate in predicates.iter() {
+        let Some(i) = fields.iter().position(|it| it.name() == predicate.column()) else {
+            continue;
+        };
+
+        if !predicate.may_match(&values[i], &values[i])?[0] {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
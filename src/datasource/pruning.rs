@@ -0,0 +1,266 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{
+        Array, ArrayRef, BooleanArray, Float32Array, Float64Array, Int8Array, Int16Array,
+        Int32Array, Int64Array, Scalar, StringArray, UInt8Array, UInt16Array, UInt32Array,
+        UInt64Array, new_null_array,
+    },
+    compute::{
+        cast,
+        kernels::{boolean, cmp},
+    },
+    datatypes::{DataType, Schema as ArrowSchema},
+};
+use parquet::{
+    arrow::arrow_reader::{RowSelection, RowSelector, statistics::StatisticsConverter},
+    file::metadata::ParquetMetaData,
+};
+
+use crate::{
+    error::Result,
+    logical_plan::expr::{Expr, ExprRef, LiteralExpression, NumericExpression},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Operator {
+    Eq,
+    Neq,
+    Gt,
+    GtEq,
+    Lt,
+    LtEq,
+}
+
+impl Operator {
+    /** The operator that gives the same result with its operands swapped */
+    fn swap(self) -> Self {
+        match self {
+            Operator::Eq => Operator::Eq,
+            Operator::Neq => Operator::Neq,
+            Operator::Gt => Operator::Lt,
+            Operator::GtEq => Operator::LtEq,
+            Operator::Lt => Operator::Gt,
+            Operator::LtEq => Operator::GtEq,
+        }
+    }
+}
+
+/**
+ * A `column <op> literal` comparison that can be checked against min/max statistics. Pushed down
+ * filters of any other shape are ignored, they are still applied by the selection above the
+ * scan.
+ */
+#[derive(Debug)]
+pub struct PruningPredicate {
+    column: String,
+    op: Operator,
+    value: ArrayRef,
+}
+
+impl PruningPredicate {
+    /** Every prunable comparison found in the conjunction of `filters` */
+    pub fn from_filters(filters: &[ExprRef]) -> Vec<PruningPredicate> {
+        let mut predicates = vec![];
+        for filter in filters.iter() {
+            collect(&filter.state, &mut predicates);
+        }
+        predicates
+    }
+
+    /** Name of the column the predicate compares */
+    pub fn column(&self) -> &str {
+        &self.column
+    }
+
+    /**
+     * For every container (row group or page) whether it may hold a matching row, given the
+     * minimum and maximum value of the column in each container. Missing statistics never prune.
+     */
+    pub(crate) fn may_match(&self, mins: &ArrayRef, maxes: &ArrayRef) -> Result<Vec<bool>> {
+        let keep_all = vec![true; mins.len()];
+
+        let Some(value) = self.value_as(mins.data_type()) else {
+            return Ok(keep_all);
+        };
+        let value = Scalar::new(value);
+
+        let mask = match self.op {
+            Operator::Eq => boolean::and(&cmp::lt_eq(mins, &value)?, &cmp::gt_eq(maxes, &value)?)?,
+            Operator::Neq => boolean::not(&boolean::and(
+                &cmp::eq(mins, &value)?,
+                &cmp::eq(maxes, &value)?,
+            )?)?,
+            Operator::Gt => cmp::gt(maxes, &value)?,
+            Operator::GtEq => cmp::gt_eq(maxes, &value)?,
+            Operator::Lt => cmp::lt(mins, &value)?,
+            Operator::LtEq => cmp::lt_eq(mins, &value)?,
+        };
+
+        Ok(mask_or_keep(&mask))
+    }
+
+    /**
+     * The literal converted to the type of the column. Conversions that lose information, like
+     * `4.5` to an integer, would make pruning unsound and are rejected.
+     */
+    fn value_as(&self, data_type: &arrow::datatypes::DataType) -> Option<ArrayRef> {
+        if self.value.data_type() == data_type {
+            return Some(self.value.clone());
+        }
+
+        let converted = cast(&self.value, data_type).ok()?;
+        let round_trip = cast(&converted, self.value.data_type()).ok()?;
+        if converted.is_null(0) || round_trip.to_data() != self.value.to_data() {
+            return None;
+        }
+        Some(converted)
+    }
+}
+
+fn collect(expr: &Expr, accum: &mut Vec<PruningPredicate>) {
+    let (op, l, r) = match expr {
+        Expr::AndExpr(and) => {
+            collect(&and.l, accum);
+            collect(&and.r, accum);
+            return;
+        }
+        Expr::EqOpExpr(it) => (Operator::Eq, &it.l, &it.r),
+        Expr::NeqExpr(it) => (Operator::Neq, &it.l, &it.r),
+        Expr::GtExpr(it) => (Operator::Gt, &it.l, &it.r),
+        Expr::GtEqExpr(it) => (Operator::GtEq, &it.l, &it.r),
+        Expr::LtExpr(it) => (Operator::Lt, &it.l, &it.r),
+        Expr::LtEqExpr(it) => (Operator::LtEq, &it.l, &it.r),
+        _ => return,
+    };
+
+    let predicate = match (l.as_ref(), r.as_ref()) {
+        (Expr::ColumnExpr(column), Expr::LiteralExpr(literal)) => PruningPredicate {
+            column: column.name.clone(),
+            op,
+            value: literal_array(literal),
+        },
+        (Expr::LiteralExpr(literal), Expr::ColumnExpr(column)) => PruningPredicate {
+            column: column.name.clone(),
+            op: op.swap(),
+            value: literal_array(literal),
+        },
+        _ => return,
+    };
+    accum.push(predicate);
+}
+
+fn literal_array(literal: &LiteralExpression) -> ArrayRef {
+    match literal {
+        LiteralExpression::StringExpr(lit) => Arc::new(StringArray::from(vec![lit.value.clone()])),
+        // Converts to a null of the column type, which keeps every container
+        LiteralExpression::NullExpr(_) => new_null_array(&DataType::Null, 1),
+        LiteralExpression::Numeric(numeric) => match numeric {
+            NumericExpression::Integer8Expr(lit) => Arc::new(Int8Array::from(vec![lit.value])),
+            NumericExpression::Integer16Expr(lit) => Arc::new(Int16Array::from(vec![lit.value])),
+            NumericExpression::Integer32Expr(lit) => Arc::new(Int32Array::from(vec![lit.value])),
+            NumericExpression::Integer64Expr(lit) => Arc::new(Int64Array::from(vec![lit.value])),
+            NumericExpression::UInteger8Expr(lit) => Arc::new(UInt8Array::from(vec![lit.value])),
+            NumericExpression::UInteger16Expr(lit) => Arc::new(UInt16Array::from(vec![lit.value])),
+            NumericExpression::UInteger32Expr(lit) => Arc::new(UInt32Array::from(vec![lit.value])),
+            NumericExpression::UInteger64Expr(lit) => Arc::new(UInt64Array::from(vec![lit.value])),
+            NumericExpression::FloatExpr(lit) => Arc::new(Float32Array::from(vec![lit.value])),
+            NumericExpression::DoubleExpr(lit) => Arc::new(Float64Array::from(vec![lit.value])),
+        },
+    }
+}
+
+/** A null comparison means the statistics were missing, so the container is kept */
+fn mask_or_keep(mask: &BooleanArray) -> Vec<bool> {
+    (0..mask.len())
+        .map(|i| mask.is_null(i) || mask.value(i))
+        .collect()
+}
+
+/** Indices of the row groups whose statistics do not rule out every predicate */
+pub fn prune_row_groups(
+    predicates: &[PruningPredicate],
+    schema: &ArrowSchema,
+    metadata: &ParquetMetaData,
+) -> Result<Vec<usize>> {
+    let row_groups = metadata.row_groups();
+    let mut keep = vec![true; row_groups.len()];
+
+    for predicate in predicates.iter() {
+        let Ok(converter) = StatisticsConverter::try_new(
+            &predicate.column,
+            schema,
+            metadata.file_metadata().schema_descr(),
+        ) else {
+            continue;
+        };
+
+        let mins = converter.row_group_mins(row_groups.iter())?;
+        let maxes = converter.row_group_maxes(row_groups.iter())?;
+
+        for (keep, may_match) in keep.iter_mut().zip(predicate.may_match(&mins, &maxes)?) {
+            *keep &= may_match;
+        }
+    }
+
+    Ok((0..row_groups.len()).filter(|i| keep[*i]).collect())
+}
+
+/**
+ * Rows of the given row groups that sit in pages the page index can not rule out. Returns
+ * `None` when the file has no page index or no predicate could use it.
+ */
+pub fn prune_pages(
+    predicates: &[PruningPredicate],
+    schema: &ArrowSchema,
+    metadata: &ParquetMetaData,
+    row_groups: &[usize],
+) -> Result<Option<RowSelection>> {
+    let (Some(column_index), Some(offset_index)) =
+        (metadata.column_index(), metadata.offset_index())
+    else {
+        return Ok(None);
+    };
+
+    let mut selection: Option<RowSelection> = None;
+
+    for predicate in predicates.iter() {
+        let Ok(converter) = StatisticsConverter::try_new(
+            &predicate.column,
+            schema,
+            metadata.file_metadata().schema_descr(),
+        ) else {
+            continue;
+        };
+
+        let Some(row_counts) =
+            converter.data_page_row_counts(offset_index, metadata.row_groups(), row_groups)?
+        else {
+            continue;
+        };
+        let mins = converter.data_page_mins(column_index, offset_index, row_groups)?;
+        let maxes = converter.data_page_maxes(column_index, offset_index, row_groups)?;
+        let may_match = predicate.may_match(&mins, &maxes)?;
+
+        let selectors: Vec<RowSelector> = may_match
+            .iter()
+            .zip(row_counts.iter())
+            .map(|(may_match, rows)| {
+                let rows = rows.unwrap_or_default() as usize;
+                if *may_match {
+                    RowSelector::select(rows)
+                } else {
+                    RowSelector::skip(rows)
+                }
+            })
+            .collect();
+        let page_selection = RowSelection::from(selectors);
+
+        selection = Some(match selection {
+            Some(selection) => selection.intersection(&page_selection),
+            None => page_selection,
+        });
+    }
+
+    Ok(selection)
+}
@@ -0,0 +1,493 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use arrow::{
+    array::{Array, ArrayRef, RecordBatch as ArrowRecordBatch, UInt32Array},
+    compute::take_record_batch,
+    csv::{Writer as CsvWriter, WriterBuilder as CsvWriterBuilder},
+    datatypes::{Field, Schema as ArrowSchema},
+    ipc::{
+        CompressionType,
+        writer::{FileWriter as IpcWriter, IpcWriteOptions},
+    },
+    json::LineDelimitedWriter,
+    util::display::array_value_to_string,
+};
+use flate2::{Compression as GzipLevel, write::GzEncoder};
+use parquet::{
+    arrow::ArrowWriter,
+    basic::{Compression as ParquetCompression, GzipLevel as ParquetGzipLevel, ZstdLevel},
+    file::properties::WriterProperties,
+};
+
+use crate::{
+    datasource::partition::DEFAULT_PARTITION,
+    datatypes::{record_batch::RecordBatch, schema::Schema},
+    error::{Result, UnakiteError},
+    physical_plan::RecordBatchStream,
+};
+
+/** File format written by a sink */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WriteFormat {
+    Csv,
+    Parquet,
+    /** Newline delimited JSON, one object per row */
+    Json,
+    /** Arrow IPC file format */
+    ArrowIpc,
+}
+
+impl WriteFormat {
+    /** Format named in SQL, e.g. `FORMAT parquet` */
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "csv" => Ok(WriteFormat::Csv),
+            "parquet" => Ok(WriteFormat::Parquet),
+            "json" | "ndjson" => Ok(WriteFormat::Json),
+            "arrow" | "ipc" => Ok(WriteFormat::ArrowIpc),
+            _ => Err(UnakiteError::Plan(format!("Unknown file format {}", name))),
+        }
+    }
+
+    /** Format implied by the extension of a path, ignoring a trailing `.gz` */
+    pub fn from_path(path: &str) -> Option<Self> {
+        let path = path.strip_suffix(".gz").unwrap_or(path);
+        let extension = Path::new(path).extension()?.to_string_lossy().to_string();
+        Self::from_name(&extension).ok()
+    }
+
+    /** Extension of the files the engine names, gzipped CSV and JSON files end in `.gz` */
+    fn extension(&self, compression: Compression) -> String {
+        let extension = match self {
+            WriteFormat::Csv => "csv",
+            WriteFormat::Parquet => "parquet",
+            WriteFormat::Json => "json",
+            WriteFormat::ArrowIpc => "arrow",
+        };
+        match (self, compression) {
+            (WriteFormat::Csv | WriteFormat::Json, Compression::Gzip) => {
+                format!("{}.gz", extension)
+            }
+            _ => extension.to_string(),
+        }
+    }
+}
+
+/**
+ * Compression codec of written files. CSV and JSON files can be gzipped, Parquet supports every
+ * codec and Arrow IPC files support LZ4 and Zstd.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    Uncompressed,
+    Snappy,
+    Gzip,
+    Lz4,
+    Zstd,
+}
+
+impl Compression {
+    /** Codec named in SQL, e.g. `COMPRESSION zstd` */
+    pub fn from_name(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "none" | "uncompressed" => Ok(Compression::Uncompressed),
+            "snappy" => Ok(Compression::Snappy),
+            "gzip" => Ok(Compression::Gzip),
+            "lz4" => Ok(Compression::Lz4),
+            "zstd" => Ok(Compression::Zstd),
+            _ => Err(UnakiteError::Plan(format!("Unknown compression {}", name))),
+        }
+    }
+
+    fn unsupported(&self, format: WriteFormat) -> UnakiteError {
+        UnakiteError::NotImplemented(format!(
+            "{:?} compression is not supported for {:?} files",
+            self, format
+        ))
+    }
+}
+
+/** Options for writing query results to files */
+#[derive(Clone, Debug)]
+pub struct WriteOptions {
+    pub compression: Compression,
+    /** Maximum number of rows in a Parquet row group, the Parquet default when unset */
+    pub row_group_size: Option<usize>,
+    /**
+     * Columns whose values split the output into `column=value` directories, with the path as
+     * the root directory. The partition columns are not repeated inside the files.
+     */
+    pub partition_by: Vec<String>,
+    /**
+     * Maximum number of partition files open at once. Past it the file opened first is finished
+     * and later rows of its partition go to a new `part-N` file.
+     */
+    pub max_open_files: usize,
+    /** Whether CSV files start with a header line */
+    pub has_header: bool,
+    pub delimiter: u8,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            compression: Compression::Uncompressed,
+            row_group_size: None,
+            partition_by: vec![],
+            max_open_files: 64,
+            has_header: true,
+            delimiter: b',',
+        }
+    }
+}
+
+impl WriteOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    pub fn with_row_group_size(mut self, row_group_size: usize) -> Self {
+        self.row_group_size = Some(row_group_size);
+        self
+    }
+
+    pub fn with_partition_by(mut self, columns: Vec<&str>) -> Self {
+        self.partition_by = columns.iter().map(|it| it.to_string()).collect();
+        self
+    }
+
+    pub fn with_max_open_files(mut self, max_open_files: usize) -> Self {
+        self.max_open_files = max_open_files.max(1);
+        self
+    }
+
+    pub fn with_header(mut self, has_header: bool) -> Self {
+        self.has_header = has_header;
+        self
+    }
+
+    pub fn with_delimiter(mut self, delimiter: u8) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+}
+
+/**
+ * Write every batch of `batches` to `path`, returning the number of rows written. Batches are
+ * written as they arrive, so the result never has to fit in memory.
+ */
+pub fn write_batches(
+    batches: RecordBatchStream,
+    schema: &Schema,
+    path: &str,
+    format: WriteFormat,
+    options: &WriteOptions,
+) -> Result<usize> {
+    // Like Spark, every written column is nullable, the plan does not always know better
+    let fields: Vec<Field> = schema
+        .fields
+        .iter()
+        .map(|it| Field::new(&it.name, it.data_type.clone(), true))
+        .collect();
+    let schema = Arc::new(ArrowSchema::new(fields));
+
+    let partitions = options
+        .partition_by
+        .iter()
+        .map(|name| {
+            schema
+                .index_of(name)
+                .map_err(|_| UnakiteError::Schema(format!("No column named {}", name)))
+        })
+        .collect::<Result<Vec<usize>>>()?;
+
+    let mut rows = 0;
+    if partitions.is_empty() {
+        if let Some(parent) = Path::new(path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut writer = BatchWriter::try_new(Path::new(path), schema.clone(), format, options)?;
+        for batch in batches {
+            let batch = to_arrow(&batch?, &schema)?;
+            rows += batch.num_rows();
+            writer.write(&batch)?;
+        }
+        writer.finish()?;
+        return Ok(rows);
+    }
+
+    let data_columns: Vec<usize> = (0..schema.fields().len())
+        .filter(|it| !partitions.contains(it))
+        .collect();
+    // Files need at least one column, refuse before any directory is created
+    if data_columns.is_empty() {
+        return Err(UnakiteError::Plan(format!(
+            "Can not partition by every column of {}, no columns would be left to write",
+            path
+        )));
+    }
+    let data_schema = Arc::new(schema.project(&data_columns)?);
+
+    let mut writers: HashMap<PartitionKey, BatchWriter> = HashMap::new();
+    // Keys of the open writers, oldest first
+    let mut opened: VecDeque<PartitionKey> = VecDeque::new();
+    // Number of files started for each partition
+    let mut parts: HashMap<PartitionKey, usize> = HashMap::new();
+    for batch in batches {
+        let batch = to_arrow(&batch?, &schema)?;
+        rows += batch.num_rows();
+
+        let keys: Vec<ArrayRef> = partitions
+            .iter()
+            .map(|i| batch.column(*i).clone())
+            .collect();
+        let data = batch.project(&data_columns)?;
+
+        for (key, indices) in group_rows(&keys, batch.num_rows())? {
+            let writer = match writers.get_mut(&key) {
+                Some(writer) => writer,
+                None => {
+                    // Finish the oldest file rather than run out of file descriptors
+                    if writers.len() >= options.max_open_files {
+                        let oldest = opened.pop_front().and_then(|it| writers.remove(&it));
+                        if let Some(writer) = oldest {
+                            writer.finish()?;
+                        }
+                    }
+
+                    let part = parts.entry(key.clone()).or_insert(0);
+                    let file = partition_file(path, &key, *part, format, options);
+                    *part += 1;
+                    fs::create_dir_all(file.parent().unwrap_or(Path::new(path)))?;
+                    let writer = BatchWriter::try_new(&file, data_schema.clone(), format, options)?;
+                    opened.push_back(key.clone());
+                    writers.entry(key).or_insert(writer)
+                }
+            };
+            writer.write(&take_record_batch(&data, &UInt32Array::from(indices))?)?;
+        }
+    }
+
+    for (_, writer) in writers {
+        writer.finish()?;
+    }
+    Ok(rows)
+}
+
+fn to_arrow(batch: &RecordBatch, schema: &Arc<ArrowSchema>) -> Result<ArrowRecordBatch> {
+    let columns = batch.fields.iter().map(|it| it.to_array()).collect();
+    Ok(ArrowRecordBatch::try_new(schema.clone(), columns)?)
+}
+
+type PartitionKey = Vec<Option<String>>;
+
+/** Row indices of every distinct combination of partition values, in order of first appearance */
+fn group_rows(keys: &[ArrayRef], rows: usize) -> Result<Vec<(PartitionKey, Vec<u32>)>> {
+    let mut groups: Vec<(PartitionKey, Vec<u32>)> = vec![];
+    let mut positions: HashMap<PartitionKey, usize> = HashMap::new();
+
+    for row in 0..rows {
+        let key = keys
+            .iter()
+            .map(|it| {
+                if it.is_null(row) {
+                    return Ok(None);
+                }
+                Ok(Some(array_value_to_string(it, row)?))
+            })
+            .collect::<Result<PartitionKey>>()?;
+
+        match positions.get(&key) {
+            Some(i) => groups[*i].1.push(row as u32),
+            None => {
+                positions.insert(key.clone(), groups.len());
+                groups.push((key, vec![row as u32]));
+            }
+        }
+    }
+    Ok(groups)
+}
+
+/** `root/a=1/b=x/part-0.parquet` for partition columns `a` and `b` and the first part */
+fn partition_file(
+    root: &str,
+    key: &PartitionKey,
+    part: usize,
+    format: WriteFormat,
+    options: &WriteOptions,
+) -> PathBuf {
+    let mut file = PathBuf::from(root);
+    for (column, value) in options.partition_by.iter().zip(key.iter()) {
+        let value = match value {
+            Some(value) => escape_partition_value(value),
+            None => DEFAULT_PARTITION.to_string(),
+        };
+        file.push(format!("{}={}", column, value));
+    }
+    let extension = format.extension(options.compression);
+    file.push(format!("part-{}.{}", part, extension));
+    file
+}
+
+/** Percent encode the characters that can not appear in a directory name */
+fn escape_partition_value(value: &str) -> String {
+    let mut escaped = String::new();
+    for c in value.chars() {
+        if c.is_control() || "%/\\=:*?\"<>|#".contains(c) {
+            escaped.push_str(&format!("%{:02X}", c as u32));
+        } else {
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+/** Buffered file, gzipped when asked to */
+enum Output {
+    Plain(BufWriter<File>),
+    Gzip(Box<GzEncoder<BufWriter<File>>>),
+}
+
+impl Output {
+    fn create(path: &Path, gzip: bool) -> Result<Self> {
+        let file = BufWriter::new(File::create(path)?);
+        Ok(match gzip {
+            true => Output::Gzip(Box::new(GzEncoder::new(file, GzipLevel::default()))),
+            false => Output::Plain(file),
+        })
+    }
+
+    fn finish(self) -> Result<()> {
+        match self {
+            Output::Plain(mut file) => file.flush()?,
+            Output::Gzip(encoder) => encoder.finish()?.flush()?,
+        }
+        Ok(())
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match self {
+            Output::Plain(file) => file.write(buf),
+            Output::Gzip(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match self {
+            Output::Plain(file) => file.flush(),
+            Output::Gzip(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/** Writer of one output file */
+enum BatchWriter {
+    Csv(Box<CsvWriter<Output>>),
+    Json(LineDelimitedWriter<Output>),
+    Parquet(ArrowWriter<File>),
+    ArrowIpc(IpcWriter<BufWriter<File>>),
+}
+
+impl BatchWriter {
+    fn try_new(
+        path: &Path,
+        schema: Arc<ArrowSchema>,
+        format: WriteFormat,
+        options: &WriteOptions,
+    ) -> Result<Self> {
+        let compression = options.compression;
+
+        let writer = match format {
+            WriteFormat::Csv | WriteFormat::Json => {
+                let gzip = match compression {
+                    Compression::Uncompressed => false,
+                    Compression::Gzip => true,
+                    _ => return Err(compression.unsupported(format)),
+                };
+                let output = Output::create(path, gzip)?;
+
+                match format {
+                    WriteFormat::Csv => BatchWriter::Csv(Box::new(
+                        CsvWriterBuilder::new()
+                            .with_header(options.has_header)
+                            .with_delimiter(options.delimiter)
+                            .build(output),
+                    )),
+                    _ => BatchWriter::Json(LineDelimitedWriter::new(output)),
+                }
+            }
+            WriteFormat::Parquet => {
+                let codec = match compression {
+                    Compression::Uncompressed => ParquetCompression::UNCOMPRESSED,
+                    Compression::Snappy => ParquetCompression::SNAPPY,
+                    Compression::Gzip => ParquetCompression::GZIP(ParquetGzipLevel::default()),
+                    Compression::Lz4 => ParquetCompression::LZ4_RAW,
+                    Compression::Zstd => ParquetCompression::ZSTD(ZstdLevel::default()),
+                };
+                let mut props = WriterProperties::builder().set_compression(codec);
+                if let Some(row_group_size) = options.row_group_size {
+                    props = props.set_max_row_group_size(row_group_size);
+                }
+
+                let file = File::create(path)?;
+                BatchWriter::Parquet(ArrowWriter::try_new(file, schema, Some(props.build()))?)
+            }
+            WriteFormat::ArrowIpc => {
+                let codec = match compression {
+                    Compression::Uncompressed => None,
+                    Compression::Lz4 => Some(CompressionType::LZ4_FRAME),
+                    Compression::Zstd => Some(CompressionType::ZSTD),
+                    _ => return Err(compression.unsupported(format)),
+                };
+                let ipc_options = IpcWriteOptions::default().try_with_compression(codec)?;
+
+                let file = BufWriter::new(File::create(path)?);
+                BatchWriter::ArrowIpc(IpcWriter::try_new_with_options(file, &schema, ipc_options)?)
+            }
+        };
+        Ok(writer)
+    }
+
+    fn write(&mut self, batch: &ArrowRecordBatch) -> Result<()> {
+        match self {
+            BatchWriter::Csv(writer) => writer.write(batch)?,
+            BatchWriter::Json(writer) => writer.write(batch)?,
+            BatchWriter::Parquet(writer) => writer.write(batch)?,
+            BatchWriter::ArrowIpc(writer) => writer.write(batch)?,
+        }
+        Ok(())
+    }
+
+    /** Write footers and flush, a file is only valid once this returns */
+    fn finish(self) -> Result<()> {
+        match self {
+            BatchWriter::Csv(writer) => writer.into_inner().finish(),
+            BatchWriter::Json(mut writer) => {
+                writer.finish()?;
+                writer.into_inner().finish()
+            }
+            BatchWriter::Parquet(writer) => {
+                writer.close()?;
+                Ok(())
+            }
+            BatchWriter::ArrowIpc(mut writer) => {
+                writer.finish()?;
+                writer.into_inner()?.flush()?;
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+#[cfg(test)]
+pub mod test {
+    use arrow::{
+        array::{Array, Date32Array},
+        datatypes::{DataType, TimeUnit},
+    };
+
+    use crate::{
+        datasource::csv::{CsvDataSource, CsvOptions},
+        datatypes::{column_vector::ColumnVectorTrait, record_batch::RecordBatch},
+        error::{Result, UnakiteError},
+        test_util::collect,
+    };
+
+    const ORDERS: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/src/test_data/vendor_orders.csv"
+    );
+    const STOCK: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/src/test_data/vendor_stock.tsv"
+    );
+
+    #[test]
+    fn infer_schema_with_vendor_options() {
+        let data = CsvDataSource::try_new(ORDERS.to_string(), orders_options()).unwrap();
+
+        let fields: Vec<(String, DataType)> = data
+            .schema()
+            .fields
+            .iter()
+            .map(|it| (it.name.clone(), it.data_type.clone()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("order_id".to_string(), DataType::Int64),
+                ("customer".to_string(), DataType::Utf8),
+                ("amount".to_string(), DataType::Float64),
+                ("ordered_on".to_string(), DataType::Date32),
+                (
+                    "shipped_at".to_string(),
+                    DataType::Timestamp(TimeUnit::Microsecond, None)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn read_quotes_escapes_nulls_and_dates() {
+        let data = CsvDataSource::try_new(ORDERS.to_string(), orders_options()).unwrap();
+        let batches = collect(data.scan(vec![]));
+        let batch = &batches[0];
+
+        assert_eq!(batch.row_count(), 4);
+
+        let customer: String = batch
+            .field(1)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(customer, "Smith; Jones");
+        let customer: String = batch
+            .field(1)
+            .unwrap()
+            .get_value(2)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(customer, "Quote \"Co\"");
+
+        let amount = batch.field(2).unwrap().to_array();
+        assert!(amount.is_null(1));
+        let shipped_at = batch.field(4).unwrap().to_array();
+        assert!(shipped_at.is_null(1) && shipped_at.is_null(3));
+
+        // 2026-10-03 is day 20729 after the epoch
+        let ordered_on = batch.field(3).unwrap().to_array();
+        let ordered_on = ordered_on.as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(ordered_on.value(0), 20729);
+    }
+
+    #[test]
+    fn tab_delimited_with_batch_size() {
+        let options = CsvOptions::new()
+            .with_header(true)
+            .with_delimiter(b'\t')
+            .with_batch_size(2);
+        let data = CsvDataSource::try_new(STOCK.to_string(), options).unwrap();
+
+        let batches = collect(data.scan(vec!["qty".to_string(), "sku".to_string()]));
+
+        let sizes: Vec<usize> = batches.iter().map(|it| it.row_count()).collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+        assert_eq!(batches[0].field(0).unwrap().get_type(), DataType::Int64);
+
+        let sku: String = batches[2]
+            .field(1)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(sku, "E-5");
+    }
+
+    #[test]
+    fn values_not_matching_the_date_format_are_errors() {
+        let options = orders_options().with_date_format("%Y-%m-%d");
+        let schema = CsvDataSource::try_new(ORDERS.to_string(), orders_options())
+            .unwrap()
+            .schema()
+            .to_arrow();
+        let data = CsvDataSource::try_new(ORDERS.to_string(), options.with_schema(schema)).unwrap();
+
+        let result: Result<Vec<RecordBatch>> = data.scan(vec![]).unwrap().collect();
+        assert!(matches!(result, Err(UnakiteError::Type(_))));
+    }
+
+    fn orders_options() -> CsvOptions {
+        CsvOptions::new()
+            .with_header(true)
+            .with_delimiter(b';')
+            .with_escape(b'\\')
+            .with_comment(b'#')
+            .with_null_values(vec!["NA"])
+            .with_date_format("%d/%m/%Y")
+            .with_timestamp_format("%d/%m/%Y %H:%M:%S")
+    }
+}
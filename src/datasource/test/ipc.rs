@@ -0,0 +1,132 @@
+#[cfg(test)]
+pub mod test {
+    use std::{fs::File, path::PathBuf, sync::Arc};
+
+    use arrow::{
+        array::{Array, Float64Array, Int32Array, RecordBatch as ArrowRecordBatch, StringArray},
+        datatypes::{DataType, Field, Schema},
+        ipc::writer::{FileWriter, StreamWriter},
+    };
+
+    use crate::{
+        datasource::ipc::{ArrowIpcDataSource, IpcFormat},
+        error::UnakiteError,
+        execution::ExecutionContext,
+        test_util::collect,
+    };
+
+    #[test]
+    fn read_file_and_stream_formats() {
+        for format in [IpcFormat::File, IpcFormat::Stream] {
+            let data = ArrowIpcDataSource::try_new(readings(format)).unwrap();
+            assert_eq!(data.format(), format);
+
+            let names: Vec<String> = data
+                .schema()
+                .fields
+                .iter()
+                .map(|it| it.name.clone())
+                .collect();
+            assert_eq!(names, vec!["sensor", "reading", "site"]);
+
+            let rows: usize = collect(data.scan(vec![]))
+                .iter()
+                .map(|it| it.row_count())
+                .sum();
+            assert_eq!(rows, 5);
+        }
+    }
+
+    #[test]
+    fn scan_with_projection() {
+        for format in [IpcFormat::File, IpcFormat::Stream] {
+            let data = ArrowIpcDataSource::try_new(readings(format)).unwrap();
+
+            let batches = collect(data.scan(vec!["site".to_string(), "sensor".to_string()]));
+            assert_eq!(batches.len(), 2);
+            assert_eq!(batches[1].column_count(), 2);
+
+            let sites = batches[1].field(0).unwrap().to_array();
+            let sites = sites.as_any().downcast_ref::<StringArray>().unwrap();
+            let sensors = batches[1].field(1).unwrap().to_array();
+            let sensors = sensors.as_any().downcast_ref::<Int32Array>().unwrap();
+            assert_eq!((sites.value(0), sensors.value(0)), ("dock", 4));
+
+            assert!(matches!(
+                data.scan(vec!["missing".to_string()]),
+                Err(UnakiteError::Schema(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn query_ipc_table() {
+        let mut ctx = ExecutionContext::new();
+        ctx.register_ipc("readings", &readings(IpcFormat::Stream))
+            .unwrap();
+
+        let df = ctx
+            .sql("SELECT MAX(reading) FROM readings WHERE site = 'dock'")
+            .unwrap();
+        let batches = ctx.collect(&df).unwrap();
+        let max: f64 = batches[0]
+            .field(0)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(max, 21.5);
+    }
+
+    /** Five sensor readings written as two batches in the given IPC format */
+    fn readings(format: IpcFormat) -> String {
+        let path: PathBuf = std::env::temp_dir().join(format!(
+            "unakite_readings_{:?}_{}.arrow",
+            format,
+            std::process::id()
+        ));
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("sensor", DataType::Int32, false),
+            Field::new("reading", DataType::Float64, false),
+            Field::new("site", DataType::Utf8, false),
+        ]));
+        let batch = |sensors: Vec<i32>, readings: Vec<f64>, sites: Vec<&str>| {
+            ArrowRecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from(sensors)),
+                    Arc::new(Float64Array::from(readings)),
+                    Arc::new(StringArray::from(sites)),
+                ],
+            )
+            .unwrap()
+        };
+        let batches = [
+            batch(
+                vec![1, 2, 3],
+                vec![18.0, 19.5, 20.0],
+                vec!["yard", "dock", "yard"],
+            ),
+            batch(vec![4, 5], vec![21.5, 17.0], vec!["dock", "office"]),
+        ];
+
+        let file = File::create(&path).unwrap();
+        match format {
+            IpcFormat::File => {
+                let mut writer = FileWriter::try_new(file, &schema).unwrap();
+                batches.iter().for_each(|it| writer.write(it).unwrap());
+                writer.finish().unwrap();
+            }
+            IpcFormat::Stream => {
+                let mut writer = StreamWriter::try_new(file, &schema).unwrap();
+                batches.iter().for_each(|it| writer.write(it).unwrap());
+                writer.finish().unwrap();
+            }
+        }
+
+        path.to_string_lossy().to_string()
+    }
+}
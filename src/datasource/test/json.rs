@@ -0,0 +1,130 @@
+#[cfg(test)]
+pub mod test {
+    use arrow::{
+        array::{Array, ListArray, StringArray, StructArray},
+        datatypes::DataType,
+    };
+
+    use crate::{
+        datasource::json::{JsonDataSource, JsonOptions},
+        error::UnakiteError,
+        execution::ExecutionContext,
+        test_util::collect,
+    };
+
+    const TELEMETRY: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/test_data/telemetry.json");
+
+    #[test]
+    fn infer_nested_schema() {
+        let data = JsonDataSource::try_new(TELEMETRY.to_string(), JsonOptions::new()).unwrap();
+
+        let fields: Vec<(String, String)> = data
+            .schema()
+            .fields
+            .iter()
+            .map(|it| (it.name.clone(), short_type(&it.data_type)))
+            .collect();
+        // Keys of the first line are ordered by name, keys first seen later are appended
+        assert_eq!(
+            fields,
+            vec![
+                ("host".to_string(), "Struct<name, region>".to_string()),
+                ("latency_ms".to_string(), "Int64".to_string()),
+                ("ok".to_string(), "Boolean".to_string()),
+                ("service".to_string(), "Utf8".to_string()),
+                ("tags".to_string(), "List<Utf8>".to_string()),
+                ("error".to_string(), "Utf8".to_string()),
+            ]
+        );
+
+        // A sample too small to see the error key leaves it out of the schema
+        let sampled =
+            JsonDataSource::try_new(TELEMETRY.to_string(), JsonOptions::new().with_infer_rows(2))
+                .unwrap();
+        assert_eq!(sampled.schema().fields.len(), 5);
+    }
+
+    #[test]
+    fn scan_nested_columns_with_projection() {
+        let data = JsonDataSource::try_new(TELEMETRY.to_string(), JsonOptions::new()).unwrap();
+
+        let batches = collect(data.scan(vec!["tags".to_string(), "host".to_string()]));
+        assert_eq!(batches.len(), 1);
+
+        let tags = batches[0].field(0).unwrap().to_array();
+        let tags = tags.as_any().downcast_ref::<ListArray>().unwrap();
+        assert_eq!(tags.value_length(0), 2);
+        assert_eq!(tags.value_length(1), 0);
+
+        let host = batches[0].field(1).unwrap().to_array();
+        let host = host.as_any().downcast_ref::<StructArray>().unwrap();
+        let regions = host.column_by_name("region").unwrap();
+        let regions = regions.as_any().downcast_ref::<StringArray>().unwrap();
+        assert_eq!(regions.value(3), "us-east");
+
+        assert!(matches!(
+            data.scan(vec!["missing".to_string()]),
+            Err(UnakiteError::Schema(_))
+        ));
+    }
+
+    #[test]
+    fn batch_size_splits_batches() {
+        let options = JsonOptions::new().with_batch_size(2);
+        let data = JsonDataSource::try_new(TELEMETRY.to_string(), options).unwrap();
+
+        let sizes: Vec<usize> = collect(data.scan(vec!["service".to_string()]))
+            .iter()
+            .map(|it| it.row_count())
+            .collect();
+        assert_eq!(sizes, vec![2, 2, 1]);
+
+        let errors = collect(data.scan(vec!["error".to_string()]));
+        assert_eq!(errors[1].field(0).unwrap().to_array().null_count(), 1);
+
+        // A batch size of zero still reads every row, one at a time
+        let options = JsonOptions::new().with_batch_size(0);
+        let data = JsonDataSource::try_new(TELEMETRY.to_string(), options).unwrap();
+        let rows: Vec<usize> = collect(data.scan(vec!["service".to_string()]))
+            .iter()
+            .map(|it| it.row_count())
+            .collect();
+        assert_eq!(rows, vec![1, 1, 1, 1, 1]);
+    }
+
+    #[test]
+    fn query_json_table() {
+        let mut ctx = ExecutionContext::new();
+        ctx.register_json("telemetry", TELEMETRY, JsonOptions::new())
+            .unwrap();
+
+        let df = ctx
+            .sql("SELECT SUM(latency_ms) FROM telemetry WHERE service = 'checkout'")
+            .unwrap();
+        let batches = ctx.collect(&df).unwrap();
+        let total: i64 = batches[0]
+            .field(0)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(total, 1280);
+    }
+
+    fn short_type(data_type: &DataType) -> String {
+        match data_type {
+            DataType::Struct(fields) => format!(
+                "Struct<{}>",
+                fields
+                    .iter()
+                    .map(|it| it.name().as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            DataType::List(field) => format!("List<{}>", field.data_type()),
+            other => other.to_string(),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+#[cfg(test)]
+pub mod test {
+    use std::{fs, path::PathBuf};
+
+    use arrow::{
+        array::{Array, Int64Array, StringArray},
+        datatypes::DataType,
+    };
+
+    use crate::{
+        datasource::listing::ListingDataSource,
+        datatypes::record_batch::RecordBatch,
+        error::UnakiteError,
+        execution::ExecutionContext,
+        logical_plan::{data_frame::DataFrame, helper::column, macro_utils::literal_i64},
+        test_util::{collect, csv_options},
+    };
+
+    #[test]
+    fn unify_schemas_across_hourly_files() {
+        let dir = exports("unify");
+        let data = ListingDataSource::try_new(&dir, csv_options()).unwrap();
+
+        assert_eq!(data.files().len(), 3);
+        let fields: Vec<(String, DataType, bool)> = data
+            .schema()
+            .fields
+            .iter()
+            .map(|it| (it.name.clone(), it.data_type.clone(), it.nullable))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("id".to_string(), DataType::Int64, true),
+                ("amount".to_string(), DataType::Int64, true),
+                ("region".to_string(), DataType::Utf8, true),
+            ]
+        );
+
+        // The first hour has no region column, its rows read as null
+        let batches = collect(data.scan(vec!["region".to_string(), "id".to_string()]));
+        let regions = column_values(&batches, 0);
+        let nulls = regions.iter().filter(|it| it.is_none()).count();
+        assert_eq!((regions.len(), nulls), (6, 2));
+    }
+
+    #[test]
+    fn glob_and_file_name_column() {
+        let dir = exports("glob");
+        let pattern = format!("{}/2026-10-1[7]/hour-*.csv", dir);
+        let data =
+            ListingDataSource::try_new(&pattern, csv_options().with_file_name_column("file"))
+                .unwrap();
+
+        assert_eq!(data.files().len(), 2);
+
+        let batches = collect(data.scan(vec!["file".to_string()]));
+        let files = column_values(&batches, 0);
+        assert_eq!(files.len(), 4);
+        assert!(
+            files
+                .iter()
+                .all(|it| it.as_ref().is_some_and(|it| it.ends_with(".csv")))
+        );
+        assert!(files[0].as_ref().unwrap().ends_with("hour-00.csv"));
+    }
+
+    #[test]
+    fn parallel_scan_returns_every_row() {
+        let dir = exports("parallel");
+        let data = ListingDataSource::try_new(&dir, csv_options().with_parallel(true)).unwrap();
+
+        let batches = collect(data.scan(vec!["id".to_string()]));
+        let mut ids: Vec<i64> = batches
+            .iter()
+            .flat_map(|batch| {
+                let array = batch.field(0).unwrap().to_array();
+                let ids = array.as_any().downcast_ref::<Int64Array>().unwrap();
+                ids.values().to_vec()
+            })
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec![1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn listing_errors() {
+        let dir = exports("errors");
+
+        let missing = ListingDataSource::try_new(&format!("{}/*.parquet", dir), csv_options());
+        assert!(matches!(missing, Err(UnakiteError::Plan(_))));
+
+        fs::write(
+            format!("{}/2026-10-18/hour-00.csv", dir),
+            "id,amount\nx,1\n",
+        )
+        .unwrap();
+        let conflict = ListingDataSource::try_new(&dir, csv_options());
+        assert!(matches!(conflict, Err(UnakiteError::Schema(_))));
+    }
+
+    #[test]
+    fn query_listing_table() {
+        let dir = exports("query");
+        let mut ctx = ExecutionContext::new();
+        ctx.register_listing("exports", &dir, csv_options())
+            .unwrap();
+
+        let df = ctx
+            .table("exports")
+            .unwrap()
+            .filter(column("amount").gteq(literal_i64(20)))
+            .project(vec![column("id"), column("region")]);
+
+        let rows: usize = ctx
+            .collect(&df)
+            .unwrap()
+            .iter()
+            .map(|it| it.row_count())
+            .sum();
+        assert_eq!(rows, 5);
+    }
+
+    fn column_values(batches: &[RecordBatch], i: usize) -> Vec<Option<String>> {
+        batches
+            .iter()
+            .flat_map(|batch| {
+                let array = batch.field(i).unwrap().to_array();
+                let values = array.as_any().downcast_ref::<StringArray>().unwrap();
+                values
+                    .iter()
+                    .map(|it| it.map(|it| it.to_string()))
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /**
+     * Two days of hourly exports. The first hour predates the region column, and a `_SUCCESS`
+     * marker and a hidden file sit next to the data.
+     */
+    fn exports(name: &str) -> String {
+        let root: PathBuf =
+            std::env::temp_dir().join(format!("unakite_listing_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        let files = [
+            ("2026-10-17/hour-00.csv", "id,amount\n1,10\n2,20\n"),
+            (
+                "2026-10-17/hour-01.csv",
+                "id,amount,region\n3,30,north\n4,40,south\n",
+            ),
+            (
+                "2026-10-18/hour-01.csv",
+                "id,region,amount\n5,east,50\n6,west,60\n",
+            ),
+            ("2026-10-18/_SUCCESS", ""),
+            ("2026-10-18/.hour-02.csv", "id\nnot-a-number\n"),
+        ];
+        for (file, contents) in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        root.to_string_lossy().to_string()
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+pub mod test {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{Float64Array, Int32Array, RecordBatch as ArrowRecordBatch, StringArray},
+        datatypes::{DataType, Field, Schema as ArrowSchema},
+    };
+
+    use crate::{
+        datasource::{DataSource, memory_tables::InMemoryDataSource},
+        datatypes::{
+            arrow_vector_builder::{TypeVector, build_vector},
+            record_batch::RecordBatch,
+            schema::{Field as UnakiteField, Schema},
+        },
+        error::UnakiteError,
+        execution::ExecutionContext,
+        logical_plan::{data_frame::DataFrame, helper::column, macro_utils::literal_string},
+        test_util::collect,
+    };
+
+    #[test]
+    fn scan_with_projection() {
+        let data = cities();
+
+        let batches = collect(data.scan(vec!["lat".to_string(), "city".to_string()]));
+
+        assert_eq!(batches.len(), 2);
+        let names: Vec<String> = batches[0]
+            .schema
+            .fields
+            .iter()
+            .map(|it| it.name.clone())
+            .collect();
+        assert_eq!(names, vec!["lat", "city"]);
+
+        let city: String = batches[1]
+            .field(1)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let lat: f64 = batches[1]
+            .field(0)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!((city.as_str(), lat), ("Leeds", 53.8));
+
+        assert!(matches!(
+            data.scan(vec!["missing".to_string()]),
+            Err(UnakiteError::Schema(_))
+        ));
+    }
+
+    #[test]
+    fn batch_size_splits_batches() {
+        let data = cities().with_batch_size(2);
+
+        let sizes: Vec<usize> = collect(data.scan(vec![]))
+            .iter()
+            .map(|it| it.row_count())
+            .collect();
+        assert_eq!(sizes, vec![2, 1, 2]);
+
+        // Every scan starts from the first row again
+        assert_eq!(collect(data.scan(vec![])).len(), 3);
+    }
+
+    #[test]
+    fn batches_must_match_the_schema() {
+        let batch = RecordBatch {
+            schema: Schema {
+                fields: vec![UnakiteField::new("city", DataType::Int32)],
+            },
+            fields: vec![build_vector(DataType::Int32, &TypeVector::Int32(vec![1, 2])).unwrap()],
+        };
+
+        let table = InMemoryDataSource::new(
+            Schema {
+                fields: vec![UnakiteField::new("city", DataType::Utf8)],
+            },
+            vec![batch],
+        );
+        assert!(matches!(table, Err(UnakiteError::Schema(_))));
+    }
+
+    #[test]
+    fn query_memory_table() {
+        let mut ctx = ExecutionContext::new();
+        ctx.register_data_source("cities", DataSource::Memory(cities()))
+            .unwrap();
+
+        let df = ctx
+            .table("cities")
+            .unwrap()
+            .filter(column("country").eq(literal_string("England")))
+            .project(vec![column("city")]);
+
+        let rows: usize = ctx
+            .collect(&df)
+            .unwrap()
+            .iter()
+            .map(|it| it.row_count())
+            .sum();
+        assert_eq!(rows, 4);
+    }
+
+    /** Five cities stored in two arrow batches of three and two rows */
+    fn cities() -> InMemoryDataSource {
+        let schema = Arc::new(ArrowSchema::new(vec![
+            Field::new("city", DataType::Utf8, false),
+            Field::new("country", DataType::Utf8, false),
+            Field::new("lat", DataType::Float64, false),
+            Field::new("id", DataType::Int32, false),
+        ]));
+
+        let batch = |cities: Vec<&str>, countries: Vec<&str>, lats: Vec<f64>, ids: Vec<i32>| {
+            ArrowRecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(StringArray::from(cities)),
+                    Arc::new(StringArray::from(countries)),
+                    Arc::new(Float64Array::from(lats)),
+                    Arc::new(Int32Array::from(ids)),
+                ],
+            )
+            .unwrap()
+        };
+
+        InMemoryDataSource::from_arrow(
+            schema.clone(),
+            vec![
+                batch(
+                    vec!["London", "Cardiff", "York"],
+                    vec!["England", "Wales", "England"],
+                    vec![51.5, 51.5, 53.9],
+                    vec![1, 2, 3],
+                ),
+                batch(
+                    vec!["Leeds", "Bath"],
+                    vec!["England", "England"],
+                    vec![53.8, 51.4],
+                    vec![4, 5],
+                ),
+            ],
+        )
+        .unwrap()
+    }
+}
@@ -1,19 +1,36 @@
+pub mod csv;
+pub mod ipc;
+pub mod json;
+pub mod listing;
+pub mod memory_tables;
+pub mod partition;
+pub mod pruning;
+pub mod sink;
+
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 pub mod test {
 
     use arrow::datatypes::{DataType, Field, Schema};
 
-    use crate::datasource::{csv::CsvDataSource, parquet::ParquetDataSource};
+    use crate::{
+        datasource::{csv::CsvDataSource, parquet::ParquetDataSource},
+        datatypes::{column_vector::ColumnVectorTrait, record_batch::RecordBatch},
+        error::{Result, UnakiteError},
+    };
 
     #[test]
     fn parquet_test() {
-        let file_path = String::from("/home/spaceriot/unakitesql/src/test_data/mtcars.parquet");
+        let file_path = String::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/test_data/mtcars.parquet"
+        ));
 
-        let data = ParquetDataSource::new(file_path);
-        let batch_iter = data.scan(vec![]);
+        let data = ParquetDataSource::new(file_path).unwrap();
+        let batch_iter = data.scan(vec![]).unwrap();
 
         for batch in batch_iter {
-            for field in batch.fields {
+            for field in batch.unwrap().fields {
                 println!("{:?}", field)
             }
         }
@@ -22,7 +39,10 @@ pub mod test {
     #[test]
     fn csv_test() {
         let has_headers = false;
-        let file_path = String::from("/home/spaceriot/unakitesql/src/test_data/uk_cities.csv");
+        let file_path = String::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/test_data/uk_cities.csv"
+        ));
 
         let data = CsvDataSource::new(
             file_path,
@@ -34,12 +54,103 @@ pub mod test {
             ]),
         );
 
-        let mut batch_iter = data.scan(vec![]);
+        let batch_iter = data.scan(vec![]).unwrap();
 
-        while let Some(batch) = batch_iter.next() {
-            for col in batch.fields {
+        for batch in batch_iter {
+            for col in batch.unwrap().fields {
                 println!("Col: {:?}", col)
             }
         }
     }
+
+    #[test]
+    fn missing_files_are_errors() {
+        let data = CsvDataSource::new(
+            "does/not/exist.csv".to_string(),
+            true,
+            Schema::new(vec![Field::new("city", DataType::Utf8, false)]),
+        );
+        assert!(matches!(data.scan(vec![]), Err(UnakiteError::Io(_))));
+
+        let parquet = ParquetDataSource::new("does/not/exist.parquet".to_string());
+        assert!(matches!(parquet, Err(UnakiteError::Io(_))));
+    }
+
+    #[test]
+    fn csv_reads_projected_columns_in_requested_order() {
+        let file_path = String::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/test_data/uk_cities.csv"
+        ));
+
+        let data = CsvDataSource::new(
+            file_path,
+            false,
+            Schema::new(vec![
+                Field::new("city", DataType::Utf8, false),
+                Field::new("lat", DataType::Float64, false),
+                Field::new("lng", DataType::Float64, false),
+            ]),
+        );
+
+        let batches: Vec<RecordBatch> = data
+            .scan(vec!["lng".to_string(), "city".to_string()])
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        for batch in batches.iter() {
+            assert_projected(batch, &["lng", "city"]);
+        }
+
+        assert!(matches!(
+            data.scan(vec!["missing".to_string()]),
+            Err(UnakiteError::Schema(_))
+        ));
+    }
+
+    #[test]
+    fn parquet_reads_projected_columns_in_requested_order() {
+        let file_path = String::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/test_data/mtcars.parquet"
+        ));
+
+        let data = ParquetDataSource::new(file_path).unwrap();
+        let names: Vec<String> = data
+            .schema()
+            .fields
+            .iter()
+            .map(|it| it.name.clone())
+            .collect();
+        let projection = vec![names[names.len() - 1].clone(), names[0].clone()];
+
+        let batches: Vec<RecordBatch> = data
+            .scan(projection.clone())
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+
+        let rows: usize = batches.iter().map(|it| it.row_count()).sum();
+        assert_eq!(rows, 32);
+        for batch in batches.iter() {
+            assert_projected(batch, &[projection[0].as_str(), projection[1].as_str()]);
+        }
+    }
+
+    /** The batch holds exactly the named columns and every column matches its schema field */
+    fn assert_projected(batch: &RecordBatch, names: &[&str]) {
+        let schema_names: Vec<&str> = batch
+            .schema
+            .fields
+            .iter()
+            .map(|it| it.name.as_str())
+            .collect();
+        assert_eq!(schema_names, names);
+        assert_eq!(batch.fields.len(), names.len());
+
+        for (field, column) in batch.schema.fields.iter().zip(batch.fields.iter()) {
+            assert_eq!(field.data_type, column.get_type());
+        }
+    }
 }
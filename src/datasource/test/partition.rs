@@ -0,0 +1,133 @@
+#[cfg(test)]
+pub mod test {
+    use std::{fs, path::PathBuf};
+
+    use arrow::{
+        array::{Array, Date32Array},
+        datatypes::DataType,
+    };
+
+    use crate::{
+        datasource::listing::ListingDataSource,
+        error::UnakiteError,
+        execution::ExecutionContext,
+        logical_plan::{
+            data_frame::DataFrame,
+            helper::column,
+            macro_utils::{eq, literal_i64},
+        },
+        test_util::{collect, csv_options, int_values},
+    };
+
+    #[test]
+    fn discover_partition_columns() {
+        let root = events("discover");
+        let data = ListingDataSource::try_new(&root, csv_options()).unwrap();
+
+        let fields: Vec<(String, DataType)> = data
+            .schema()
+            .fields
+            .iter()
+            .map(|it| (it.name.clone(), it.data_type.clone()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("id".to_string(), DataType::Int64),
+                ("year".to_string(), DataType::Int64),
+                ("month".to_string(), DataType::Int64),
+                ("day".to_string(), DataType::Int64),
+            ]
+        );
+
+        // Files are listed in path order, so month=10 comes before month=9
+        let batches = collect(data.scan(vec!["day".to_string(), "id".to_string()]));
+        assert_eq!(int_values(&batches, 0), vec![31, 17, 17, 18, 30]);
+        assert_eq!(int_values(&batches, 1), vec![1, 3, 4, 5, 2]);
+    }
+
+    #[test]
+    fn partition_filters_select_files() {
+        let root = events("select");
+        let data = ListingDataSource::try_new(&root, csv_options()).unwrap();
+
+        let october = vec![
+            eq(column("year"), literal_i64(2026)),
+            column("month").gteq(literal_i64(10)),
+        ];
+        let files = data.files_matching(&october).unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files.iter().all(|it| it.contains("month=10")));
+
+        // Filters on file columns never rule out a file
+        let by_id = vec![eq(column("id"), literal_i64(1))];
+        assert_eq!(data.files_matching(&by_id).unwrap().len(), 4);
+    }
+
+    #[test]
+    fn pruned_partitions_are_never_opened() {
+        let root = events("pruned");
+        let mut ctx = ExecutionContext::new();
+        ctx.register_listing("events", &root, csv_options())
+            .unwrap();
+
+        // Only October is left readable, any other file would fail to open
+        fs::remove_dir_all(format!("{}/year=2025", root)).unwrap();
+        fs::remove_dir_all(format!("{}/year=2026/month=9", root)).unwrap();
+
+        let df = ctx
+            .sql("SELECT id, day FROM events WHERE year = 2026 AND month = 10")
+            .unwrap();
+        let batches = ctx.collect(&df).unwrap();
+        assert_eq!(int_values(&batches, 0), vec![3, 4, 5]);
+
+        let all = ctx.table("events").unwrap().project(vec![column("id")]);
+        assert!(matches!(ctx.collect(&all), Err(UnakiteError::Io(_))));
+    }
+
+    #[test]
+    fn glob_and_declared_partition_types() {
+        let root = PathBuf::from(events("typed"));
+        let file = root.join("dt=2026-10-18/part-0.csv");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "id\n6\n").unwrap();
+
+        let pattern = format!("{}/dt=*/*.csv", root.to_string_lossy());
+        let options = csv_options().with_partition_type("dt", DataType::Date32);
+        let data = ListingDataSource::try_new(&pattern, options).unwrap();
+
+        let batches = collect(data.scan(vec!["dt".to_string()]));
+        let array = batches[0].field(0).unwrap().to_array();
+        let days = array.as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(days.value(0), 20744);
+
+        // The other files sit under year=, not dt=
+        let mixed = ListingDataSource::try_new(&root.to_string_lossy(), csv_options());
+        assert!(matches!(mixed, Err(UnakiteError::Schema(_))));
+
+        let bad_type = csv_options().with_partition_type("dt", DataType::Int32);
+        let invalid = ListingDataSource::try_new(&pattern, bad_type);
+        assert!(matches!(invalid, Err(UnakiteError::Type(_))));
+    }
+
+    /** Daily event files partitioned by year, month and day */
+    fn events(name: &str) -> String {
+        let root: PathBuf =
+            std::env::temp_dir().join(format!("unakite_events_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+
+        let files = [
+            ("year=2025/month=12/day=31/part-0.csv", "id\n1\n"),
+            ("year=2026/month=9/day=30/part-0.csv", "id\n2\n"),
+            ("year=2026/month=10/day=17/part-0.csv", "id\n3\n4\n"),
+            ("year=2026/month=10/day=18/part-0.csv", "id\n5\n"),
+        ];
+        for (file, contents) in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        root.to_string_lossy().to_string()
+    }
+}
@@ -0,0 +1,129 @@
+#[cfg(test)]
+pub mod test {
+    use std::{fs::File, path::PathBuf, sync::Arc};
+
+    use arrow::{
+        array::{Int64Array, RecordBatch as ArrowRecordBatch, StringArray},
+        datatypes::{DataType, Field, Schema},
+    };
+    use parquet::{arrow::ArrowWriter, file::properties::WriterProperties};
+
+    use crate::{
+        datasource::parquet::ParquetDataSource,
+        datatypes::record_batch::RecordBatch,
+        error::Result,
+        execution::ExecutionContext,
+        logical_plan::{
+            expr::ExprRef,
+            helper::column,
+            macro_utils::{literal_i64, literal_string},
+        },
+    };
+
+    const ROWS: i64 = 1000;
+
+    #[test]
+    fn every_scan_reads_the_whole_file() {
+        let data = ParquetDataSource::new(events("rescan")).unwrap();
+
+        assert_eq!(rows(&data, &[]), ROWS as usize);
+        assert_eq!(rows(&data, &[]), ROWS as usize);
+    }
+
+    #[test]
+    fn row_groups_and_pages_are_skipped() {
+        let data = ParquetDataSource::new(events("skip")).unwrap();
+
+        // Only the last row group can match, and only its last five pages
+        assert_eq!(rows(&data, &[column("id").gteq(literal_i64(950))]), 50);
+
+        // Literal on the left, and a conjunction narrowing it further
+        assert_eq!(rows(&data, &[literal_i64(10).gt(column("id"))]), 10);
+        assert_eq!(
+            rows(
+                &data,
+                &[column("id")
+                    .gteq(literal_i64(300))
+                    .and(column("id").lt(literal_i64(320)))]
+            ),
+            20
+        );
+
+        assert_eq!(
+            rows(&data, &[column("name").eq(literal_string("event-0505"))]),
+            10
+        );
+        assert_eq!(rows(&data, &[column("id").gt(literal_i64(ROWS))]), 0);
+    }
+
+    #[test]
+    fn unusable_filters_read_everything() {
+        let data = ParquetDataSource::new(events("unusable")).unwrap();
+
+        assert_eq!(rows(&data, &[column("id").eq(column("id"))]), ROWS as usize);
+        assert_eq!(
+            rows(&data, &[column("missing").eq(literal_i64(1))]),
+            ROWS as usize
+        );
+    }
+
+    #[test]
+    fn queries_only_return_matching_rows() {
+        let mut ctx = ExecutionContext::new();
+        ctx.register_parquet("events", &events("query")).unwrap();
+
+        let df = ctx
+            .sql("SELECT id FROM events WHERE id >= 955 AND name <> 'event-0999'")
+            .unwrap();
+        let batches = ctx.collect(&df).unwrap();
+
+        let total: usize = batches.iter().map(|it| it.row_count()).sum();
+        assert_eq!(total, 44);
+    }
+
+    fn rows(data: &ParquetDataSource, filters: &[ExprRef]) -> usize {
+        let batches: Vec<RecordBatch> = data
+            .scan_with_filters(vec![], filters)
+            .unwrap()
+            .collect::<Result<_>>()
+            .unwrap();
+        batches.iter().map(|it| it.row_count()).sum()
+    }
+
+    /**
+     * Write ids 0 to 999 in order, with row groups of 100 rows and pages of 10 rows so the
+     * statistics of every row group and page cover a distinct range.
+     */
+    fn events(name: &str) -> String {
+        let path: PathBuf =
+            std::env::temp_dir().join(format!("unakite_{}_{}.parquet", name, std::process::id()));
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let batch = ArrowRecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int64Array::from_iter_values(0..ROWS)),
+                Arc::new(StringArray::from_iter_values(
+                    (0..ROWS).map(|it| format!("event-{:04}", it)),
+                )),
+            ],
+        )
+        .unwrap();
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(100)
+            .set_data_page_row_count_limit(10)
+            .set_write_batch_size(10)
+            .build();
+
+        let file = File::create(&path).unwrap();
+        let mut writer = ArrowWriter::try_new(file, schema, Some(props)).unwrap();
+        writer.write(&batch).unwrap();
+        writer.close().unwrap();
+
+        path.to_string_lossy().to_string()
+    }
+}
@@ -0,0 +1,348 @@
+#[cfg(test)]
+pub mod test {
+    use std::{fs, io::Read, path::PathBuf};
+
+    use arrow::array::{Array, StringArray, UInt64Array};
+    use flate2::read::GzDecoder;
+    use parquet::{
+        basic::Compression as ParquetCompression,
+        file::reader::{FileReader, SerializedFileReader},
+    };
+
+    use crate::{
+        datasource::{
+            csv::CsvOptions,
+            json::JsonOptions,
+            listing::{FileFormat, ListingOptions},
+            sink::{Compression, WriteOptions},
+        },
+        error::UnakiteError,
+        execution::ExecutionContext,
+        test_util::{csv_options, float_values, int_values},
+    };
+
+    #[test]
+    fn write_csv_plain_and_gzipped() {
+        let dir = output("csv");
+        let ctx = sales();
+        let df = ctx
+            .sql("SELECT region, quantity FROM sales WHERE quantity > 4")
+            .unwrap();
+
+        let plain = dir.join("big.csv");
+        let rows = df
+            .write_csv(plain.to_str().unwrap(), WriteOptions::new())
+            .unwrap();
+        assert_eq!(rows, 6);
+
+        let text = fs::read_to_string(&plain).unwrap();
+        assert!(text.starts_with("region,quantity\nNorth,10\nEast,7\n"));
+
+        let gzipped = dir.join("big.csv.gz");
+        let options = WriteOptions::new().with_compression(Compression::Gzip);
+        df.write_csv(gzipped.to_str().unwrap(), options).unwrap();
+
+        let mut decoded = String::new();
+        GzDecoder::new(fs::File::open(&gzipped).unwrap())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, text);
+    }
+
+    #[test]
+    fn write_parquet_row_groups_and_compression() {
+        let dir = output("parquet");
+        let ctx = sales();
+        let path = dir.join("sales.parquet");
+
+        let options = WriteOptions::new()
+            .with_compression(Compression::Zstd)
+            .with_row_group_size(4);
+        let rows = ctx
+            .table("sales")
+            .unwrap()
+            .write_parquet(path.to_str().unwrap(), options)
+            .unwrap();
+        assert_eq!(rows, 10);
+
+        let reader = SerializedFileReader::new(fs::File::open(&path).unwrap()).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.num_row_groups(), 3);
+        assert_eq!(metadata.row_group(0).num_rows(), 4);
+        assert!(matches!(
+            metadata.row_group(0).column(0).compression(),
+            ParquetCompression::ZSTD(_)
+        ));
+
+        let mut copy = ExecutionContext::new();
+        copy.register_parquet("written", path.to_str().unwrap())
+            .unwrap();
+        let df = copy
+            .sql("SELECT price FROM written WHERE region = 'East'")
+            .unwrap();
+        let prices = float_values(&copy.collect(&df).unwrap(), 0);
+        assert_eq!(prices, vec![2.75, 1.25, 2.75]);
+    }
+
+    #[test]
+    fn write_json_and_ipc_round_trip() {
+        let dir = output("json_ipc");
+        let ctx = sales();
+        let df = ctx
+            .sql("SELECT product, quantity FROM sales WHERE region = 'South'")
+            .unwrap();
+
+        let json = dir.join("south.json");
+        df.write_json(json.to_str().unwrap(), WriteOptions::new())
+            .unwrap();
+        let first = fs::read_to_string(&json).unwrap();
+        assert_eq!(
+            first.lines().next(),
+            Some(r#"{"product":"Widget","quantity":4}"#)
+        );
+
+        let ipc = dir.join("south.arrow");
+        let options = WriteOptions::new().with_compression(Compression::Lz4);
+        df.write_ipc(ipc.to_str().unwrap(), options).unwrap();
+
+        let mut copy = ExecutionContext::new();
+        copy.register_json("json", json.to_str().unwrap(), JsonOptions::new())
+            .unwrap();
+        copy.register_ipc("ipc", ipc.to_str().unwrap()).unwrap();
+
+        for table in ["json", "ipc"] {
+            let df = copy
+                .sql(&format!("SELECT quantity FROM {}", table))
+                .unwrap();
+            assert_eq!(int_values(&copy.collect(&df).unwrap(), 0), vec![4, 1, 6]);
+        }
+
+        // Snappy is a Parquet codec only
+        let options = WriteOptions::new().with_compression(Compression::Snappy);
+        assert!(matches!(
+            df.write_ipc(ipc.to_str().unwrap(), options),
+            Err(UnakiteError::NotImplemented(_))
+        ));
+    }
+
+    #[test]
+    fn partitioned_write_reads_back_as_listing() {
+        let root = output("partitioned");
+        let ctx = sales();
+
+        let options = WriteOptions::new().with_partition_by(vec!["region", "product"]);
+        let rows = ctx
+            .table("sales")
+            .unwrap()
+            .write_parquet(root.to_str().unwrap(), options)
+            .unwrap();
+        assert_eq!(rows, 10);
+        assert!(
+            root.join("region=East/product=Gizmo/part-0.parquet")
+                .is_file()
+        );
+
+        let mut copy = ExecutionContext::new();
+        copy.register_listing(
+            "sales",
+            root.to_str().unwrap(),
+            ListingOptions::new(FileFormat::Parquet),
+        )
+        .unwrap();
+
+        // The partition columns come back from the directory names
+        let names: Vec<String> = copy
+            .table("sales")
+            .unwrap()
+            .plan
+            .schema()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|it| it.name.clone())
+            .collect();
+        assert_eq!(names, vec!["quantity", "price", "region", "product"]);
+
+        let df = copy
+            .sql("SELECT quantity FROM sales WHERE region = 'North' AND product = 'Widget'")
+            .unwrap();
+        assert_eq!(int_values(&copy.collect(&df).unwrap(), 0), vec![10, 5]);
+
+        let missing = WriteOptions::new().with_partition_by(vec!["missing"]);
+        let result = ctx
+            .table("sales")
+            .unwrap()
+            .write_parquet(root.to_str().unwrap(), missing);
+        assert!(matches!(result, Err(UnakiteError::Schema(_))));
+    }
+
+    #[test]
+    fn open_partition_files_are_capped() {
+        let root = output("capped");
+        let mut ctx = ExecutionContext::new();
+        ctx.register_csv(
+            "sales",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/src/test_data/sales.csv"),
+            CsvOptions::new().with_header(true).with_batch_size(2),
+        )
+        .unwrap();
+
+        // Every batch of two rows meets a partition whose file was already finished
+        let options = WriteOptions::new()
+            .with_partition_by(vec!["region"])
+            .with_max_open_files(1);
+        let rows = ctx
+            .table("sales")
+            .unwrap()
+            .write_csv(root.to_str().unwrap(), options)
+            .unwrap();
+        assert_eq!(rows, 10);
+        for part in 0..4 {
+            assert!(
+                root.join(format!("region=North/part-{}.csv", part))
+                    .is_file()
+            );
+        }
+        assert!(!root.join("region=North/part-4.csv").exists());
+
+        let mut copy = ExecutionContext::new();
+        copy.register_listing("sales", root.to_str().unwrap(), csv_options())
+            .unwrap();
+        let df = copy
+            .sql("SELECT quantity FROM sales WHERE region = 'North'")
+            .unwrap();
+        let mut quantities = int_values(&copy.collect(&df).unwrap(), 0);
+        quantities.sort();
+        assert_eq!(quantities, vec![2, 3, 5, 10]);
+    }
+
+    #[test]
+    fn partition_values_are_escaped() {
+        let root = output("escaped");
+        let data = root.join("data.csv");
+        fs::write(&data, "path,size\na/b=c,1\n,2\n").unwrap();
+
+        let mut ctx = ExecutionContext::new();
+        ctx.register_csv(
+            "files",
+            data.to_str().unwrap(),
+            CsvOptions::new().with_header(true),
+        )
+        .unwrap();
+
+        let out = root.join("out");
+        let options = WriteOptions::new().with_partition_by(vec!["path"]);
+        ctx.table("files")
+            .unwrap()
+            .write_csv(out.to_str().unwrap(), options)
+            .unwrap();
+        assert!(out.join("path=a%2Fb%3Dc/part-0.csv").is_file());
+        assert!(
+            out.join("path=__HIVE_DEFAULT_PARTITION__/part-0.csv")
+                .is_file()
+        );
+
+        // Gzipped files are named for what they hold
+        let gzipped = root.join("gzipped");
+        let options = WriteOptions::new()
+            .with_partition_by(vec!["path"])
+            .with_compression(Compression::Gzip);
+        ctx.table("files")
+            .unwrap()
+            .write_csv(gzipped.to_str().unwrap(), options)
+            .unwrap();
+        let file = gzipped.join("path=a%2Fb%3Dc/part-0.csv.gz");
+        assert!(!gzipped.join("path=a%2Fb%3Dc/part-0.csv").exists());
+        let mut decoded = String::new();
+        GzDecoder::new(fs::File::open(&file).unwrap())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, "size\n1\n");
+
+        let mut copy = ExecutionContext::new();
+        let format = FileFormat::Csv(CsvOptions::new().with_header(true));
+        copy.register_listing("files", out.to_str().unwrap(), ListingOptions::new(format))
+            .unwrap();
+        let df = copy.sql("SELECT path FROM files WHERE size = 1").unwrap();
+        let paths: Vec<String> = copy
+            .collect(&df)
+            .unwrap()
+            .iter()
+            .flat_map(|batch| {
+                let array = batch.field(0).unwrap().to_array();
+                let values = array.as_any().downcast_ref::<StringArray>().unwrap();
+                values
+                    .iter()
+                    .flatten()
+                    .map(String::from)
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+        assert_eq!(paths, vec!["a/b=c"]);
+    }
+
+    #[test]
+    fn sql_copy_to() {
+        let dir = output("sql");
+        let ctx = sales();
+        let path = dir.join("gadgets.txt");
+
+        let df = ctx
+            .sql(&format!(
+                "COPY (SELECT region, price FROM sales WHERE product = 'Gadget') TO '{}' \
+                 WITH (FORMAT csv, HEADER false, DELIMITER ';')",
+                path.to_str().unwrap()
+            ))
+            .unwrap();
+
+        // Nothing is written until the frame is executed
+        assert!(!path.exists());
+        let batches = ctx.collect(&df).unwrap();
+        let counts = batches[0].field(0).unwrap().to_array();
+        let counts = counts.as_any().downcast_ref::<UInt64Array>().unwrap();
+        assert_eq!(counts.value(0), 3);
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "North;10.0\nSouth;9.5\nNorth;10.5\n"
+        );
+
+        // Without a FORMAT option the extension decides
+        let unknown = format!("COPY (SELECT price FROM sales) TO '{}'", path.display());
+        assert!(matches!(ctx.sql(&unknown), Err(UnakiteError::Plan(_))));
+        let bad_option = "COPY (SELECT price FROM sales) TO 'x.csv' WITH (ROW_GROUP_SIZE 'a')";
+        assert!(matches!(ctx.sql(bad_option), Err(UnakiteError::Plan(_))));
+
+        // Partitioning by every column would leave the files without columns
+        let only_partitions = dir.join("products");
+        let df = ctx
+            .sql(&format!(
+                "COPY (SELECT product FROM sales) TO '{}' \
+                 WITH (FORMAT parquet, PARTITION_BY (product))",
+                only_partitions.display()
+            ))
+            .unwrap();
+        assert!(matches!(ctx.collect(&df), Err(UnakiteError::Plan(_))));
+        assert!(!only_partitions.exists());
+    }
+
+    fn sales() -> ExecutionContext {
+        let mut ctx = ExecutionContext::new();
+        ctx.register_csv(
+            "sales",
+            concat!(env!("CARGO_MANIFEST_DIR"), "/src/test_data/sales.csv"),
+            CsvOptions::new().with_header(true),
+        )
+        .unwrap();
+        ctx
+    }
+
+    /** An empty directory to write to */
+    fn output(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("unakite_sink_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+}
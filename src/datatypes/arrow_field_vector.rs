@@ -1,4 +1,4 @@
-use crate::{datatypes::value::ArrowValue, downcast_arry, match_and};
+use crate::{datatypes::value::ArrowValue, downcast_arry, error::Result, match_and};
 
 use arrow::{
     array::{
@@ -20,17 +20,17 @@ pub struct ArrowFieldVector {
 }
 impl ArrowFieldVector {
     pub fn get_type(&self) -> DataType {
-        return self.field.data_type().clone();
+        self.field.data_type().clone()
     }
 
-    pub fn get_value(&self, i: usize) -> Option<ArrowValue> {
+    pub fn get_value(&self, i: usize) -> Result<Option<ArrowValue>> {
         if self.field.is_null(i) {
-            return None;
+            Ok(None)
         } else {
             let field = self.field.clone();
-            let value = match_and!(self.field.data_type(), downcast_arry, field, i);
+            let value = match_and!(self.field.data_type(), downcast_arry, field, i)?;
 
-            return Some(value);
+            Ok(Some(value))
         }
     }
 
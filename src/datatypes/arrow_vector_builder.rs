@@ -15,7 +15,9 @@ use crate::{
         arrow_field_vector::ArrowFieldVector, column_vector::ColumnVector,
         concrete_type::ConcreteType, value::ArrowValue,
     },
-    dispatch_builder_array_ref, init_builder, match_and,
+    dispatch_builder_array_ref,
+    error::{Result, UnakiteError},
+    init_builder, match_and,
 };
 
 pub enum VectorBuilder {
@@ -40,13 +42,13 @@ struct Stager {
 impl Stager {
     pub fn new() -> Self {
         Self {
-            buffer: vec![None; 10],
+            buffer: vec![],
         }
     }
 
     pub fn set(&mut self, index: usize, value: Option<ArrowValue>) {
         if index >= self.buffer.len() {
-            panic!("Index out of bounds");
+            self.buffer.resize(index + 1, None);
         }
 
         self.buffer[index] = value;
@@ -59,44 +61,36 @@ pub struct ArrowVectorBuilder {
 }
 
 impl ArrowVectorBuilder {
-    pub fn new(datatype: &DataType) -> Self {
-        let builder = match_and!(init_builder, datatype);
+    pub fn new(datatype: &DataType) -> Result<Self> {
+        let builder = match_and!(init_builder, datatype)?;
         let stager = Stager::new();
 
-        Self { builder, stager }
+        Ok(Self { builder, stager })
     }
-    pub fn set(&mut self, i: usize, value: Option<ArrowValue>) {
-        if value.is_none() {
-            self.stager.set(i, value);
-            return;
+    /** Stage `value` at index `i`, failing when its type differs from the builder's */
+    pub fn set(&mut self, i: usize, value: Option<ArrowValue>) -> Result<()> {
+        if let Some(ref arrow_value) = value
+            && arrow_value.get_conc_type() != self.builder.get_conc_type()
+        {
+            return Err(UnakiteError::Type(format!(
+                "cannot set {} in a vector of type {}",
+                arrow_value,
+                self.builder.get_conc_type()
+            )));
         }
 
-        if let Some(ref arrow_value) = value {
-            if arrow_value.get_conc_type() == self.builder.get_conc_type() {
-                self.stager.set(i, value);
-            }
-        }
+        self.stager.set(i, value);
+        Ok(())
     }
 
-    pub fn set_all(&mut self, values: &mut Vec<Option<ArrowValue>>) {
-        if values.is_empty() {
-            return;
-        }
-
+    pub fn set_all(&mut self, values: &mut [Option<ArrowValue>]) -> Result<()> {
         for (i, value) in values.iter_mut().enumerate() {
-            if let Some(inner) = value.take() {
-                if inner.get_conc_type() == self.builder.get_conc_type() {
-                    self.stager.set(i, Some(inner));
-                } else {
-                    self.stager.set(i, None);
-                }
-            } else {
-                self.stager.set(i, None);
-            }
+            self.set(i, value.take())?;
         }
+        Ok(())
     }
 
-    fn append(&mut self, value: Option<ArrowValue>) {
+    fn append(&mut self, value: Option<ArrowValue>) -> Result<()> {
         append_dispatch!(
             &mut self.builder,
             value,
@@ -111,12 +105,12 @@ impl ArrowVectorBuilder {
             (UInt64, UInt64Type),
             (Float, FloatType),
             (Double, DoubleType),
-        );
+        )
     }
 
-    pub fn build(mut self) -> ColumnVector {
+    pub fn build(mut self) -> Result<ColumnVector> {
         for arrow_value in self.stager.buffer.clone().into_iter() {
-            self.append(arrow_value);
+            self.append(arrow_value)?;
         }
 
         let array_ref = dispatch_builder_array_ref!(
@@ -135,7 +129,7 @@ impl ArrowVectorBuilder {
             String,
         );
 
-        ColumnVector::ArrowVector(ArrowFieldVector { field: array_ref })
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector { field: array_ref }))
     }
 }
 
@@ -183,22 +177,22 @@ macro_rules! generate_match_arms {
             $(
                 TypeVector::$variant(items) => {
                     for (i, value) in items.iter().enumerate() {
-                        $builder.set(i, Some(ArrowValue::$arrow_type(*value)));
+                        $builder.set(i, Some(ArrowValue::$arrow_type(*value)))?;
                     }
                 }
             )*
             // Special case for String
             TypeVector::String(items) => {
                 for (i, value) in items.iter().enumerate() {
-                    $builder.set(i, Some(ArrowValue::StringType(value.to_string())));
+                    $builder.set(i, Some(ArrowValue::StringType(value.to_string())))?;
                 }
             }
         }
     };
 }
 
-pub fn build_vector(data_type: DataType, vector: &TypeVector) -> ColumnVector {
-    let mut builder = ArrowVectorBuilder::new(&data_type);
+pub fn build_vector(data_type: DataType, vector: &TypeVector) -> Result<ColumnVector> {
+    let mut builder = ArrowVectorBuilder::new(&data_type)?;
 
     generate_match_arms!(
         builder,
@@ -0,0 +1,142 @@
+use arrow::{
+    array::{ArrayRef, new_null_array},
+    compute::{can_cast_types, cast},
+    datatypes::DataType,
+};
+
+use crate::error::{Result, UnakiteError};
+
+/**
+ * The type both operands of an arithmetic operator are converted to before it is applied.
+ *
+ * Integers of the same signedness widen to the wider of the two. A signed and an unsigned integer
+ * meet in the smallest signed type holding both, which is at most Int64. Floats win over
+ * integers, Float32 is only kept when the other operand fits in it exactly.
+ */
+pub fn numeric_promotion(l: &DataType, r: &DataType) -> Result<DataType> {
+    use DataType::*;
+
+    let width = |data_type: &DataType| match data_type {
+        Int8 | UInt8 => 8,
+        Int16 | UInt16 => 16,
+        Int32 | UInt32 | Float32 => 32,
+        _ => 64,
+    };
+    let signed = |width: usize| match width {
+        8 => Int8,
+        16 => Int16,
+        32 => Int32,
+        _ => Int64,
+    };
+    let unsigned = |width: usize| match width {
+        8 => UInt8,
+        16 => UInt16,
+        32 => UInt32,
+        _ => UInt64,
+    };
+
+    if !is_arithmetic(l) || !is_arithmetic(r) {
+        return Err(UnakiteError::Type(format!(
+            "Arithmetic expects numeric operands, got {} and {}",
+            l, r
+        )));
+    }
+
+    let promoted = match (l, r) {
+        _ if l == r => l.clone(),
+        (Float64, _) | (_, Float64) => Float64,
+        (Float32, other) | (other, Float32) => match width(other) {
+            8 | 16 => Float32,
+            _ => Float64,
+        },
+        _ if l.is_signed_integer() && r.is_signed_integer() => signed(width(l).max(width(r))),
+        _ if l.is_unsigned_integer() && r.is_unsigned_integer() => unsigned(width(l).max(width(r))),
+        _ => {
+            let (signed_type, unsigned_type) = match l.is_signed_integer() {
+                true => (l, r),
+                false => (r, l),
+            };
+            signed(width(signed_type).max(width(unsigned_type) * 2))
+        }
+    };
+    Ok(promoted)
+}
+
+/** Integer and floating point types, Float16 and decimals have no arithmetic kernels to use */
+pub fn is_arithmetic(data_type: &DataType) -> bool {
+    data_type.is_integer() || matches!(data_type, DataType::Float32 | DataType::Float64)
+}
+
+/**
+ * Whether values of type `from` can be cast to `to`. Only numeric, boolean, string, date,
+ * timestamp and decimal targets are supported, whether a single value converts is only known when
+ * the cast is evaluated.
+ */
+pub fn check_cast(from: &DataType, to: &DataType) -> Result<()> {
+    let supported = is_arithmetic(to)
+        || matches!(
+            to,
+            DataType::Boolean
+                | DataType::Utf8
+                | DataType::Date32
+                | DataType::Date64
+                | DataType::Timestamp(_, _)
+                | DataType::Decimal128(_, _)
+                | DataType::Decimal256(_, _)
+        );
+    if !supported {
+        return Err(UnakiteError::NotImplemented(format!(
+            "Casting to {} is not supported yet",
+            to
+        )));
+    }
+
+    if !can_cast_types(from, to) {
+        return Err(UnakiteError::Type(format!(
+            "Cannot cast {} to {}",
+            from, to
+        )));
+    }
+    Ok(())
+}
+
+/**
+ * The type values of all the given types are converted to when they end up in one column, like
+ * the arguments of COALESCE. NULL takes the type of the other values, numbers meet in their
+ * promoted type and any other types have to be the same.
+ */
+pub fn common_type(types: &[DataType]) -> Result<DataType> {
+    types
+        .iter()
+        .try_fold(DataType::Null, |acc, data_type| match (&acc, data_type) {
+            (_, DataType::Null) => Ok(acc),
+            (DataType::Null, _) => Ok(data_type.clone()),
+            _ if acc == *data_type => Ok(acc),
+            _ if is_arithmetic(&acc) && is_arithmetic(data_type) => {
+                numeric_promotion(&acc, data_type)
+            }
+            _ => Err(UnakiteError::Type(format!(
+                "Values of type {} and {} cannot be combined",
+                acc, data_type
+            ))),
+        })
+}
+
+/** The common type values are compared in, NULLs on their own compare as booleans */
+pub fn comparison_type(types: &[DataType]) -> Result<DataType> {
+    Ok(match common_type(types)? {
+        DataType::Null => DataType::Boolean,
+        data_type => data_type,
+    })
+}
+
+/** The values of `array` as `data_type`, usually the common type of the values they meet */
+pub fn coerce_array(array: &ArrayRef, data_type: &DataType) -> Result<ArrayRef> {
+    if array.data_type() == data_type {
+        return Ok(array.clone());
+    }
+    if array.data_type() == &DataType::Null {
+        return Ok(new_null_array(data_type, array.len()));
+    }
+    Ok(cast(array, data_type)?)
+}
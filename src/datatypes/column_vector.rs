@@ -1,15 +1,22 @@
 use std::sync::Arc;
 
-use arrow::datatypes::DataType;
+use arrow::{
+    array::{ArrayRef, BooleanArray},
+    compute::kernels::filter,
+    datatypes::DataType,
+};
 
-use crate::datatypes::{
-    arrow_field_vector::ArrowFieldVector, literal_value_vector::LiteralValueVector,
-    value::ArrowValue,
+use crate::{
+    datatypes::{
+        arrow_field_vector::ArrowFieldVector, literal_value_vector::LiteralValueVector,
+        value::ArrowValue,
+    },
+    error::{Result, UnakiteError},
 };
 
 pub trait ColumnVectorTrait {
     fn get_type(&self) -> DataType;
-    fn get_value_inner(&self, i: usize) -> Option<ArrowValue>;
+    fn get_value_inner(&self, i: usize) -> Result<Option<ArrowValue>>;
     fn size(&self) -> usize;
 }
 
@@ -20,29 +27,67 @@ pub enum ColumnVector {
 }
 
 impl ColumnVector {
-    pub fn get_value(&self, i: usize) -> ArrowValue {
-        if let Some(inner) = self.get_value_inner(i) {
-            return inner;
-        }
-
-        panic!("Unwarp on a null value")
+    /** Value at row `i`, None when the entry is null */
+    pub fn get_value(&self, i: usize) -> Result<Option<ArrowValue>> {
+        self.get_value_inner(i)
     }
 
-    pub fn get_vector(&self) -> &ArrowFieldVector {
+    pub fn get_vector(&self) -> Result<&ArrowFieldVector> {
         if let ColumnVector::ArrowVector(vec) = self {
-            return vec;
+            Ok(vec)
         } else {
-            panic!("Fall getting Field Vector")
+            Err(UnakiteError::Type(
+                "A literal vector is not backed by a field vector".to_string(),
+            ))
         }
     }
 
-     pub fn get_mut_vector(self) -> ArrowFieldVector {
+     pub fn get_mut_vector(self) -> Result<ArrowFieldVector> {
         if let ColumnVector::ArrowVector(vec) = self {
-            return vec;
+            Ok(vec)
         } else {
-            panic!("Fall getting Field Vector")
+            Err(UnakiteError::Type(
+                "A literal vector is not backed by a field vector".to_string(),
+            ))
+        }
+    }
+
+    /** Arrow array backing this vector, materializing literal values when needed */
+    pub fn to_array(&self) -> ArrayRef {
+        match self {
+            ColumnVector::Literal(literal) => literal.to_array(),
+            ColumnVector::ArrowVector(vector) => vector.field.clone(),
         }
     }
+
+    /** Zero-copy slice of `len` values starting at `offset` */
+    pub fn slice(&self, offset: usize, len: usize) -> ColumnVector {
+        match self {
+            ColumnVector::Literal(literal) => ColumnVector::Literal(Arc::new(LiteralValueVector {
+                arrow_type: literal.arrow_type.clone(),
+                value: literal.value.clone(),
+                size: len,
+            })),
+            ColumnVector::ArrowVector(vector) => ColumnVector::ArrowVector(ArrowFieldVector {
+                field: vector.field.slice(offset, len),
+            }),
+        }
+    }
+
+    /** Keep only the values whose predicate entry is true */
+    pub fn filter(&self, predicate: &BooleanArray) -> Result<ColumnVector> {
+        let filtered = match self {
+            ColumnVector::Literal(literal) => ColumnVector::Literal(Arc::new(LiteralValueVector {
+                arrow_type: literal.arrow_type.clone(),
+                value: literal.value.clone(),
+                size: predicate.true_count(),
+            })),
+            ColumnVector::ArrowVector(vector) => ColumnVector::ArrowVector(ArrowFieldVector {
+                field: filter::filter(&vector.field, predicate)?,
+            }),
+        };
+        Ok(filtered)
+    }
 }
 impl ColumnVectorTrait for ColumnVector {
     fn get_type(&self) -> DataType {
@@ -52,7 +97,7 @@ impl ColumnVectorTrait for ColumnVector {
         }
     }
 
-    fn get_value_inner(&self, i: usize) -> Option<ArrowValue> {
+    fn get_value_inner(&self, i: usize) -> Result<Option<ArrowValue>> {
         match self {
             ColumnVector::Literal(literal_value_vector) => literal_value_vector.get_value(i),
             ColumnVector::ArrowVector(arrow_field_vector) => arrow_field_vector.get_value(i),
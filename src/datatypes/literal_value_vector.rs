@@ -1,5 +1,11 @@
-use crate::datatypes::value::ArrowValue;
-use arrow::datatypes::DataType;
+use crate::{
+    datatypes::value::ArrowValue,
+    error::{Result, UnakiteError},
+};
+use arrow::{
+    array::{ArrayRef, new_null_array},
+    datatypes::DataType,
+};
 
 #[allow(dead_code)]
 
@@ -15,16 +21,24 @@ impl LiteralValueVector {
         self.arrow_type.clone()
     }
 
-    pub fn get_value(&self, i: usize) -> Option<ArrowValue> {
+    pub fn get_value(&self, i: usize) -> Result<Option<ArrowValue>> {
         if i >= self.size {
             let literal = format!("Index {} out of bounds", i);
-            panic!("{literal}");
+            return Err(UnakiteError::Execution(literal));
         }
 
-        self.value.clone()
+        Ok(self.value.clone())
     }
 
     pub fn size(&self) -> usize {
         self.size
     }
+
+    /** Materialize the literal as an Arrow array with one entry per row */
+    pub fn to_array(&self) -> ArrayRef {
+        match &self.value {
+            Some(value) => value.to_array_of_size(self.size),
+            None => new_null_array(&self.arrow_type, self.size),
+        }
+    }
 }
@@ -29,9 +29,14 @@ macro_rules! downcast_arry {
         let array = $field
             .as_any()
             .downcast_ref::<StringArray>()
-            .expect("Downcast failed");
+            .ok_or_else(|| {
+                $crate::error::UnakiteError::Type("Failed to downcast to StringArray".to_string())
+            })?;
         if array.is_null($index) {
-            panic!("Illegal Type");
+            return Err($crate::error::UnakiteError::Type(format!(
+                "Value at index {} is null",
+                $index
+            )));
         }
         super::value::ArrowValue::$variant(array.value($index).to_string())
     }};
@@ -40,7 +45,11 @@ macro_rules! downcast_arry {
         let array = $field
             .as_any()
             .downcast_ref::<$array_type>()
-            .expect("Downcast failed");
+            .ok_or_else(|| {
+                $crate::error::UnakiteError::Type(
+                    concat!("Failed to downcast to ", stringify!($array_type)).to_string(),
+                )
+            })?;
         super::value::ArrowValue::$variant(array.value($index))
     }};
 }
@@ -58,57 +67,66 @@ macro_rules! init_builder {
 macro_rules! match_and {
       ($macro:ident, $dt:expr) => {{
         match $dt {
-            DataType::Boolean => $macro!(BooleanBuilder, Boolean),
-            DataType::Int8 => $macro!(Int8Builder, Int8),
-            DataType::Int16 => $macro!(Int16Builder, Int16),
-            DataType::Int32 => $macro!(Int32Builder, Int32),
-            DataType::Int64 => $macro!(Int64Builder, Int64),
-            DataType::UInt8 => $macro!(UInt8Builder, UInt8),
-            DataType::UInt16 => $macro!(UInt16Builder, UInt16),
-            DataType::UInt32 => $macro!(UInt32Builder, UInt32),
-            DataType::UInt64 => $macro!(UInt64Builder, UInt64),
-            DataType::Float32 => $macro!(Float32Builder, Float),
-            DataType::Float64 => $macro!(Float64Builder, Double),
-            DataType::Utf8 => $macro!(StringBuilder, String),
-            _ => panic!("Unsupported data type: {:?}", $dt),
+            DataType::Boolean => Ok($macro!(BooleanBuilder, Boolean)),
+            DataType::Int8 => Ok($macro!(Int8Builder, Int8)),
+            DataType::Int16 => Ok($macro!(Int16Builder, Int16)),
+            DataType::Int32 => Ok($macro!(Int32Builder, Int32)),
+            DataType::Int64 => Ok($macro!(Int64Builder, Int64)),
+            DataType::UInt8 => Ok($macro!(UInt8Builder, UInt8)),
+            DataType::UInt16 => Ok($macro!(UInt16Builder, UInt16)),
+            DataType::UInt32 => Ok($macro!(UInt32Builder, UInt32)),
+            DataType::UInt64 => Ok($macro!(UInt64Builder, UInt64)),
+            DataType::Float32 => Ok($macro!(Float32Builder, Float)),
+            DataType::Float64 => Ok($macro!(Float64Builder, Double)),
+            DataType::Utf8 => Ok($macro!(StringBuilder, String)),
+            _ => Err($crate::error::UnakiteError::NotImplemented(format!(
+                "Unsupported data type: {:?}",
+                $dt
+            ))),
         }
     }};
 
     // Match and get arrow data
     ($dt:expr, $macro:ident, $field:ident, $index:expr) => {{
         match $dt {
-            DataType::Boolean => $macro!(BooleanType, BooleanArray, $field, $index),
-            DataType::Int8 => $macro!(Int8Type, Int8Array, $field, $index),
-            DataType::Int16 => $macro!(Int16Type, Int16Array, $field, $index),
-            DataType::Int32 => $macro!(Int32Type, Int32Array, $field, $index),
-            DataType::Int64 => $macro!(Int64Type, Int64Array, $field, $index),
-            DataType::UInt8 => $macro!(UInt8Type, UInt8Array, $field, $index),
-            DataType::UInt16 => $macro!(UInt16Type, UInt16Array, $field, $index),
-            DataType::UInt32 => $macro!(UInt32Type, UInt32Array, $field, $index),
-            DataType::UInt64 => $macro!(UInt64Type, UInt64Array, $field, $index),
-            DataType::Float32 => $macro!(FloatType, Float32Array, $field, $index),
-            DataType::Float64 => $macro!(DoubleType, Float64Array, $field, $index),
-            DataType::Utf8 => $macro!(StringType, StringArray, $field, $index),
-            _ => panic!("Unsupported data type: {:?}", $dt),
+            DataType::Boolean => Ok($macro!(BooleanType, BooleanArray, $field, $index)),
+            DataType::Int8 => Ok($macro!(Int8Type, Int8Array, $field, $index)),
+            DataType::Int16 => Ok($macro!(Int16Type, Int16Array, $field, $index)),
+            DataType::Int32 => Ok($macro!(Int32Type, Int32Array, $field, $index)),
+            DataType::Int64 => Ok($macro!(Int64Type, Int64Array, $field, $index)),
+            DataType::UInt8 => Ok($macro!(UInt8Type, UInt8Array, $field, $index)),
+            DataType::UInt16 => Ok($macro!(UInt16Type, UInt16Array, $field, $index)),
+            DataType::UInt32 => Ok($macro!(UInt32Type, UInt32Array, $field, $index)),
+            DataType::UInt64 => Ok($macro!(UInt64Type, UInt64Array, $field, $index)),
+            DataType::Float32 => Ok($macro!(FloatType, Float32Array, $field, $index)),
+            DataType::Float64 => Ok($macro!(DoubleType, Float64Array, $field, $index)),
+            DataType::Utf8 => Ok($macro!(StringType, StringArray, $field, $index)),
+            _ => Err($crate::error::UnakiteError::NotImplemented(format!(
+                "Unsupported data type: {:?}",
+                $dt
+            ))),
         }
     }};
 
     // Builder with max capacity
     ($dt:expr, $macro:ident, $($args:tt)*) => {
         match $dt {
-            DataType::Boolean => $macro!(BooleanBuilder, $($args)*),
-            DataType::Int8 => $macro!(Int8Builder, $($args)*),
-            DataType::Int16 => $macro!(Int16Builder, $($args)*),
-            DataType::Int32 => $macro!(Int32Builder, $($args)*),
-            DataType::Int64 => $macro!(Int64Builder, $($args)*),
-            DataType::UInt8 => $macro!(UInt8Builder, $($args)*),
-            DataType::UInt16 => $macro!(UInt16Builder, $($args)*),
-            DataType::UInt32 => $macro!(UInt32Builder, $($args)*),
-            DataType::UInt64 => $macro!(UInt64Builder, $($args)*),
-            DataType::Float32 => $macro!(Float32Builder, $($args)*),
-            DataType::Float64 => $macro!(Float64Builder, $($args)*),
-            DataType::Utf8 => $macro!(StringBuilder, $($args)*),
-            _ => panic!("Unsupported data type: {:?}", $dt),
+            DataType::Boolean => Ok($macro!(BooleanBuilder, $($args)*)),
+            DataType::Int8 => Ok($macro!(Int8Builder, $($args)*)),
+            DataType::Int16 => Ok($macro!(Int16Builder, $($args)*)),
+            DataType::Int32 => Ok($macro!(Int32Builder, $($args)*)),
+            DataType::Int64 => Ok($macro!(Int64Builder, $($args)*)),
+            DataType::UInt8 => Ok($macro!(UInt8Builder, $($args)*)),
+            DataType::UInt16 => Ok($macro!(UInt16Builder, $($args)*)),
+            DataType::UInt32 => Ok($macro!(UInt32Builder, $($args)*)),
+            DataType::UInt64 => Ok($macro!(UInt64Builder, $($args)*)),
+            DataType::Float32 => Ok($macro!(Float32Builder, $($args)*)),
+            DataType::Float64 => Ok($macro!(Float64Builder, $($args)*)),
+            DataType::Utf8 => Ok($macro!(StringBuilder, $($args)*)),
+            _ => Err($crate::error::UnakiteError::NotImplemented(format!(
+                "Unsupported data type: {:?}",
+                $dt
+            ))),
         }
     };
 
@@ -134,13 +152,28 @@ macro_rules! append_dispatch {
     ($builder:expr, $value:expr, $( ($builder_variant:ident, $value_variant:ident) ),* $(,)? ) => {
         match ($builder, $value) {
             $(
-                (VectorBuilder::$builder_variant(b), Some(ArrowValue::$value_variant(v))) => b.append_value(v),
-                (VectorBuilder::$builder_variant(b), None) => b.append_null(),
+                (VectorBuilder::$builder_variant(b), Some(ArrowValue::$value_variant(v))) => {
+                    b.append_value(v);
+                    Ok(())
+                }
+                (VectorBuilder::$builder_variant(b), None) => {
+                    b.append_null();
+                    Ok(())
+                }
             )*
             // Special case for String which needs reference
-            (VectorBuilder::String(b), Some(ArrowValue::StringType(s))) => b.append_value(&s),
-            (VectorBuilder::String(b), None) => b.append_null(),
-            _ => panic!("Type mismatch"),
+            (VectorBuilder::String(b), Some(ArrowValue::StringType(s))) => {
+                b.append_value(&s);
+                Ok(())
+            }
+            (VectorBuilder::String(b), None) => {
+                b.append_null();
+                Ok(())
+            }
+            (_, Some(value)) => Err($crate::error::UnakiteError::Type(format!(
+                "Type mismatch for {}",
+                value
+            ))),
         }
     };
 }
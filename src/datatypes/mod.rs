@@ -7,6 +7,6 @@ pub mod schema;
 pub mod value;
 pub mod macro_utils;
 pub mod arrow_vector_builder;
+pub mod coercion;
 pub mod concrete_type;
 pub mod test;
-pub mod cast_accross;
\ No newline at end of file
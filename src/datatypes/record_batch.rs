@@ -1,8 +1,14 @@
 
 
-use crate::datatypes::{
-    column_vector::{ColumnVector, ColumnVectorTrait},
-    schema::Schema,
+use arrow::array::{BooleanArray, RecordBatch as ArrowRecordBatch};
+
+use crate::{
+    datatypes::{
+        arrow_field_vector::ArrowFieldVector,
+        column_vector::{ColumnVector, ColumnVectorTrait},
+        schema::{Schema, schema_from_arrow_schema},
+    },
+    error::{Result, UnakiteError},
 };
 
 #[derive(Debug,Clone,)]
@@ -13,7 +19,7 @@ pub struct RecordBatch {
 
 impl RecordBatch {
     pub fn row_count(&self) -> usize {
-        self.fields.first().unwrap().size()
+        self.fields.first().map_or(0, |it| it.size())
     }
 
     pub fn column_count(&self) -> usize {
@@ -21,8 +27,46 @@ impl RecordBatch {
     }
 
     /** Access one column by index */
-    pub fn field(&self, i: usize) -> ColumnVector {
-        let field = self.fields.get(i).unwrap().clone();
-        field
+    pub fn field(&self, i: usize) -> Result<ColumnVector> {
+        self.fields.get(i).cloned().ok_or_else(|| {
+            UnakiteError::Schema(format!(
+                "Column index {} out of range for a batch of {} columns",
+                i,
+                self.fields.len()
+            ))
+        })
+    }
+
+    /** Zero-copy slice of `len` rows starting at `offset` */
+    pub fn slice(&self, offset: usize, len: usize) -> RecordBatch {
+        RecordBatch {
+            schema: self.schema.clone(),
+            fields: self.fields.iter().map(|it| it.slice(offset, len)).collect(),
+        }
+    }
+
+    /** The rows where `predicate` is true, a null predicate drops the row */
+    pub fn filter(&self, predicate: &BooleanArray) -> Result<RecordBatch> {
+        Ok(RecordBatch {
+            schema: self.schema.clone(),
+            fields: self
+                .fields
+                .iter()
+                .map(|it| it.filter(predicate))
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+impl From<ArrowRecordBatch> for RecordBatch {
+    fn from(batch: ArrowRecordBatch) -> Self {
+        RecordBatch {
+            schema: schema_from_arrow_schema(batch.schema()),
+            fields: batch
+                .columns()
+                .iter()
+                .map(|it| ColumnVector::ArrowVector(ArrowFieldVector { field: it.clone() }))
+                .collect(),
+        }
     }
 }
@@ -1,8 +1,9 @@
 use std::sync::Arc;
 
-use anyhow::Error;
 use arrow::datatypes::{DataType, Field as ArrowField, Schema as ArrowSchema};
 
+use crate::error::{Result, UnakiteError};
+
 pub fn schema_from_arrow_schema(arrow_schema: Arc<ArrowSchema>) -> Schema {
     let fields: Vec<Field> = arrow_schema
         .fields
@@ -10,6 +11,7 @@ pub fn schema_from_arrow_schema(arrow_schema: Arc<ArrowSchema>) -> Schema {
         .map(|f| Field {
             name: f.name().to_string(),
             data_type: f.data_type().clone(),
+            nullable: f.is_nullable(),
         })
         .collect();
 
@@ -38,20 +40,27 @@ impl Schema {
         }
     }
 
-    pub fn select(&self, names: Arc<Vec<String>>) -> anyhow::Result<Schema> {
+    pub fn select(&self, names: Arc<Vec<String>>) -> Result<Schema> {
         let mut f: Vec<Field> = Vec::new();
 
         for name in names.iter() {
             let m: Vec<Field> = self
                 .fields
                 .iter()
-                .cloned()
                 .filter(|it| it.name == *name)
+                .cloned()
                 .collect();
-            if m.len() == 1 {
-                f.push(m[0].clone());
-            } else {
-                return anyhow::Result::Err(Error::msg("Multiple Fields with the same name"));
+            match m.len() {
+                1 => f.push(m[0].clone()),
+                0 => {
+                    return Err(UnakiteError::Schema(format!("No column named {}", name)));
+                }
+                _ => {
+                    return Err(UnakiteError::Schema(format!(
+                        "Multiple fields named {}",
+                        name
+                    )));
+                }
             }
         }
 
@@ -63,6 +72,7 @@ impl Schema {
 pub struct Field {
     pub name: String,
     pub data_type: DataType,
+    pub nullable: bool,
 }
 
 impl Field {
@@ -70,10 +80,16 @@ impl Field {
         Field {
             name: string.to_string(),
             data_type,
+            nullable: true,
         }
     }
+
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+
     pub fn to_arrow(self) -> ArrowField {
-        let field = ArrowField::new(self.name, self.data_type, true);
-        field
+        ArrowField::new(self.name, self.data_type, self.nullable)
     }
 }
@@ -1,31 +1,94 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 pub mod test {
-    use arrow::datatypes::DataType;
+    use std::sync::Arc;
 
-    use crate::datatypes::{
-        arrow_vector_builder::ArrowVectorBuilder, column_vector::ColumnVectorTrait,
-        value::ArrowValue,
+    use arrow::{
+        array::{Date32Array, RecordBatch as ArrowRecordBatch},
+        datatypes::{DataType, Field as ArrowField, Schema as ArrowSchema},
+    };
+
+    use crate::{
+        datatypes::{
+            arrow_vector_builder::ArrowVectorBuilder, coercion::numeric_promotion,
+            column_vector::ColumnVectorTrait, record_batch::RecordBatch, value::ArrowValue,
+        },
+        error::UnakiteError,
     };
 
     #[test]
     fn build_int64_vector() {
         let size = 10;
-        let mut b = ArrowVectorBuilder::new(&DataType::Int64);
+        let mut b = ArrowVectorBuilder::new(&DataType::Int64).unwrap();
         for i in 0..size {
-            b.set(i, Some(ArrowValue::Int64Type(i as i64)));
+            b.set(i, Some(ArrowValue::Int64Type(i as i64))).unwrap();
         }
-        let v = b.build();
+        // A value of another type is rejected instead of being stored as null
+        assert!(matches!(
+            b.set(0, Some(ArrowValue::DoubleType(0.5))),
+            Err(UnakiteError::Type(_))
+        ));
+        let v = b.build().unwrap();
 
         let v_size = v.size();
 
         assert_eq!(size, v_size);
 
         for i in 0..v_size {
-            let v_value = v.get_value(i);
+            let v_value = v.get_value(i).unwrap();
 
-            if let ArrowValue::Int64Type(int_value) = v_value {
+            if let Some(ArrowValue::Int64Type(int_value)) = v_value {
                 assert_eq!(i as i64, int_value);
             }
         }
     }
+
+    #[test]
+    fn promote_numeric_types() {
+        let cases = [
+            (DataType::Int32, DataType::Int32, DataType::Int32),
+            (DataType::Int8, DataType::Int64, DataType::Int64),
+            (DataType::UInt8, DataType::UInt32, DataType::UInt32),
+            (DataType::Int8, DataType::UInt8, DataType::Int16),
+            (DataType::Int64, DataType::UInt32, DataType::Int64),
+            (DataType::UInt64, DataType::Int8, DataType::Int64),
+            (DataType::Float32, DataType::Int16, DataType::Float32),
+            (DataType::Float32, DataType::Int32, DataType::Float64),
+            (DataType::UInt64, DataType::Float64, DataType::Float64),
+        ];
+        for (l, r, expected) in cases {
+            assert_eq!(numeric_promotion(&l, &r).unwrap(), expected);
+            assert_eq!(numeric_promotion(&r, &l).unwrap(), expected);
+        }
+
+        assert!(numeric_promotion(&DataType::Utf8, &DataType::Int64).is_err());
+        assert!(numeric_promotion(&DataType::Boolean, &DataType::Boolean).is_err());
+    }
+
+    #[test]
+    fn unsupported_values_are_errors() {
+        assert!(matches!(
+            ArrowVectorBuilder::new(&DataType::Date32),
+            Err(UnakiteError::NotImplemented(_))
+        ));
+
+        let schema = ArrowSchema::new(vec![ArrowField::new("day", DataType::Date32, false)]);
+        let batch: RecordBatch =
+            ArrowRecordBatch::try_new(Arc::new(schema), vec![Arc::new(Date32Array::from(vec![1]))])
+                .unwrap()
+                .into();
+
+        let day = batch.field(0).unwrap();
+        assert!(matches!(
+            day.get_value(0),
+            Err(UnakiteError::NotImplemented(_))
+        ));
+        assert!(matches!(batch.field(1), Err(UnakiteError::Schema(_))));
+
+        let value = ArrowValue::Int64Type(1);
+        assert!(matches!(
+            String::try_from(value),
+            Err(UnakiteError::Type(_))
+        ));
+    }
 }
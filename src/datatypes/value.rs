@@ -1,4 +1,12 @@
 use core::fmt;
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, BooleanArray, Float32Array, Float64Array, Int8Array, Int16Array, Int32Array,
+    Int64Array, StringArray, UInt8Array, UInt16Array, UInt32Array, UInt64Array,
+};
+
+use crate::error::UnakiteError;
 
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
 pub enum ArrowValue {
@@ -34,6 +42,31 @@ impl fmt::Display for ArrowValue {
         }
     }
 }
+impl ArrowValue {
+    /** Single element Arrow array holding this value */
+    pub fn to_array(&self) -> ArrayRef {
+        self.to_array_of_size(1)
+    }
+
+    /** Arrow array repeating this value `size` times */
+    pub fn to_array_of_size(&self, size: usize) -> ArrayRef {
+        match self {
+            ArrowValue::BooleanType(val) => Arc::new(BooleanArray::from(vec![*val; size])),
+            ArrowValue::Int8Type(val) => Arc::new(Int8Array::from(vec![*val; size])),
+            ArrowValue::Int16Type(val) => Arc::new(Int16Array::from(vec![*val; size])),
+            ArrowValue::Int32Type(val) => Arc::new(Int32Array::from(vec![*val; size])),
+            ArrowValue::Int64Type(val) => Arc::new(Int64Array::from(vec![*val; size])),
+            ArrowValue::UInt8Type(val) => Arc::new(UInt8Array::from(vec![*val; size])),
+            ArrowValue::UInt16Type(val) => Arc::new(UInt16Array::from(vec![*val; size])),
+            ArrowValue::UInt32Type(val) => Arc::new(UInt32Array::from(vec![*val; size])),
+            ArrowValue::UInt64Type(val) => Arc::new(UInt64Array::from(vec![*val; size])),
+            ArrowValue::FloatType(val) => Arc::new(Float32Array::from(vec![*val; size])),
+            ArrowValue::DoubleType(val) => Arc::new(Float64Array::from(vec![*val; size])),
+            ArrowValue::StringType(val) => Arc::new(StringArray::from(vec![val.as_str(); size])),
+        }
+    }
+}
+
 // Used for Arrow Value to rust native type conversions
 macro_rules! impl_from {
     ($(($data_type: ty, $variant:ident)),* $(,)?) => {
@@ -45,12 +78,18 @@ macro_rules! impl_from {
                 }
             }
 
-            impl From<ArrowValue> for $data_type {
-                fn from(value: ArrowValue) -> Self {
+            impl TryFrom<ArrowValue> for $data_type {
+                type Error = UnakiteError;
+
+                fn try_from(value: ArrowValue) -> Result<Self, Self::Error> {
                     if let ArrowValue::$variant(value) = value {
-                        return value;
+                        return Ok(value);
                     } else {
-                        panic!("Can not cast to target type")
+                        return Err(UnakiteError::Type(format!(
+                            "Can not cast {} to {}",
+                            value,
+                            stringify!($data_type)
+                        )));
                     }
                 }
             }
@@ -84,9 +123,9 @@ pub mod test {
         let bool = ArrowValue::from(false);
 
         assert_eq!(b, bool);
-        let lit_bool: bool = b.into();
+        let lit_bool: bool = b.try_into().unwrap();
 
-        assert_eq!(false, lit_bool);
+        assert!(!lit_bool);
 
         let int: ArrowValue = 12u8.into();
         let integer: ArrowValue = ArrowValue::from(12u8);
@@ -99,11 +138,11 @@ pub mod test {
 
         assert_eq!(int, integer);
 
-        let lit_int: u8 = int.into();
+        let lit_int: u8 = int.try_into().unwrap();
 
         assert_eq!(lit_int, 12u8);
 
-        let from_u8 = u8::from(integer);
+        let from_u8 = u8::try_from(integer).unwrap();
 
         assert_eq!(from_u8, lit_int);
 
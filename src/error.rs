@@ -0,0 +1,73 @@
+use std::fmt;
+
+use arrow::error::ArrowError;
+use parquet::errors::ParquetError;
+
+/** Result type returned by every fallible operation of the engine */
+pub type Result<T, E = UnakiteError> = std::result::Result<T, E>;
+
+/**
+ * Errors raised while planning or executing a query. A bad query surfaces as one of these
+ * instead of a panic, so callers can report it and keep serving other queries.
+ */
+#[derive(Debug)]
+pub enum UnakiteError {
+    /** Reading or writing a file failed */
+    Io(std::io::Error),
+    /** A column or table could not be resolved, or schemas do not line up */
+    Schema(String),
+    /** The query is malformed or can not be turned into a plan */
+    Plan(String),
+    /** A failure while running a physical plan */
+    Execution(String),
+    /** Operands or values of an unexpected data type */
+    Type(String),
+    /** A valid request the engine does not support yet */
+    NotImplemented(String),
+    Arrow(ArrowError),
+    Parquet(ParquetError),
+}
+
+impl fmt::Display for UnakiteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnakiteError::Io(e) => write!(f, "IO error: {}", e),
+            UnakiteError::Schema(msg) => write!(f, "Schema error: {}", msg),
+            UnakiteError::Plan(msg) => write!(f, "Plan error: {}", msg),
+            UnakiteError::Execution(msg) => write!(f, "Execution error: {}", msg),
+            UnakiteError::Type(msg) => write!(f, "Type error: {}", msg),
+            UnakiteError::NotImplemented(msg) => write!(f, "Not implemented: {}", msg),
+            UnakiteError::Arrow(e) => write!(f, "Arrow error: {}", e),
+            UnakiteError::Parquet(e) => write!(f, "Parquet error: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for UnakiteError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            UnakiteError::Io(e) => Some(e),
+            UnakiteError::Arrow(e) => Some(e),
+            UnakiteError::Parquet(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for UnakiteError {
+    fn from(e: std::io::Error) -> Self {
+        UnakiteError::Io(e)
+    }
+}
+
+impl From<ArrowError> for UnakiteError {
+    fn from(e: ArrowError) -> Self {
+        UnakiteError::Arrow(e)
+    }
+}
+
+impl From<ParquetError> for UnakiteError {
+    fn from(e: ParquetError) -> Self {
+        UnakiteError::Parquet(e)
+    }
+}
@@ -0,0 +1,164 @@
+pub mod test;
+
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, ErrorKind},
+    path::Path,
+    sync::Arc,
+};
+
+use crate::{
+    datasource::{
+        DataSource,
+        csv::{CsvDataSource, CsvOptions},
+        ipc::ArrowIpcDataSource,
+        json::{JsonDataSource, JsonOptions},
+        listing::{ListingDataSource, ListingOptions},
+        parquet::ParquetDataSource,
+    },
+    datatypes::record_batch::RecordBatch,
+    error::{Result, UnakiteError},
+    logical_plan::{LogicalPlan, data_frame::Frame, scan::Scan},
+    optimizer::Optimizer,
+    physical_plan::RecordBatchStream,
+    query_planner::QueryPlanner,
+    sql::planner::SqlPlanner,
+};
+
+/**
+ * Entry point for running queries. The context holds a catalog of named tables that can be
+ * queried with the DataFrame API or with SQL.
+ */
+#[derive(Default)]
+pub struct ExecutionContext {
+    tables: HashMap<String, Frame>,
+}
+
+impl ExecutionContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /** Register a CSV file as a table, inferring its schema unless the options hold one */
+    pub fn register_csv(&mut self, name: &str, path: &str, options: CsvOptions) -> Result<()> {
+        check_file(path)?;
+
+        let source = CsvDataSource::try_new(path.to_string(), options)?;
+        self.register_data_source(name, DataSource::CSV(source))
+    }
+
+    /** Register a newline delimited JSON file as a table, inferring its schema unless given */
+    pub fn register_json(&mut self, name: &str, path: &str, options: JsonOptions) -> Result<()> {
+        check_file(path)?;
+
+        let source = JsonDataSource::try_new(path.to_string(), options)?;
+        self.register_data_source(name, DataSource::Json(source))
+    }
+
+    /** Register a Parquet file as a table, using the schema stored in the file */
+    pub fn register_parquet(&mut self, name: &str, path: &str) -> Result<()> {
+        check_file(path)?;
+
+        let source = ParquetDataSource::new(path.to_string())?;
+        self.register_data_source(name, DataSource::Parquet(source))
+    }
+
+    /** Register an Arrow IPC file, in either the file or the stream format, as a table */
+    pub fn register_ipc(&mut self, name: &str, path: &str) -> Result<()> {
+        check_file(path)?;
+
+        let source = ArrowIpcDataSource::try_new(path.to_string())?;
+        self.register_data_source(name, DataSource::ArrowIpc(source))
+    }
+
+    /** Register every file in a directory, or matching a glob pattern, as a single table */
+    pub fn register_listing(
+        &mut self,
+        name: &str,
+        path: &str,
+        options: ListingOptions,
+    ) -> Result<()> {
+        let source = ListingDataSource::try_new(path, options)?;
+        self.register_data_source(name, DataSource::Listing(source))
+    }
+
+    /** Register any data source as a table */
+    pub fn register_data_source(&mut self, name: &str, source: DataSource) -> Result<()> {
+        let scan = Scan::new(name.to_string(), source, Arc::new(vec![]));
+        self.register_frame(
+            name,
+            Frame {
+                plan: Arc::new(LogicalPlan::ScanPlan(scan)),
+            },
+        )
+    }
+
+    /** Register the result of a data frame as a table, e.g. to reuse a query as a view */
+    pub fn register_frame(&mut self, name: &str, frame: Frame) -> Result<()> {
+        if self.tables.contains_key(name) {
+            return Err(UnakiteError::Plan(format!(
+                "Table '{}' is already registered",
+                name
+            )));
+        }
+
+        self.tables.insert(name.to_string(), frame);
+        Ok(())
+    }
+
+    /** Remove a table from the catalog, returning it if it was registered */
+    pub fn deregister_table(&mut self, name: &str) -> Option<Frame> {
+        self.tables.remove(name)
+    }
+
+    /** Names of all registered tables in alphabetical order */
+    pub fn table_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.tables.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /** A data frame reading the whole of a registered table */
+    pub fn table(&self, name: &str) -> Result<Frame> {
+        self.tables
+            .get(name)
+            .cloned()
+            .ok_or_else(|| UnakiteError::Plan(format!("No table named '{}'", name)))
+    }
+
+    /** Plan a SQL query against the registered tables */
+    pub fn sql(&self, query: &str) -> Result<Frame> {
+        SqlPlanner::plan(query, &self.tables)
+    }
+
+    /** Rewrite the plan of a data frame into a cheaper equivalent plan */
+    pub fn optimize(&self, df: &Frame) -> Result<Frame> {
+        Ok(Frame {
+            plan: Optimizer::new().optimize(df.plan.clone())?,
+        })
+    }
+
+    /** Optimize the data frame, then create a physical plan for it and execute it */
+    pub fn execute(&self, df: &Frame) -> Result<RecordBatchStream> {
+        let df = self.optimize(df)?;
+        let plan = QueryPlanner::create_physical_plan(&df.plan)?;
+        plan.execute()
+    }
+
+    /** Execute the data frame and collect every result batch */
+    pub fn collect(&self, df: &Frame) -> Result<Vec<RecordBatch>> {
+        self.execute(df)?.collect()
+    }
+}
+
+fn check_file(path: &str) -> Result<()> {
+    if !Path::new(path).is_file() {
+        return Err(UnakiteError::Io(io::Error::new(
+            ErrorKind::NotFound,
+            format!("File not found: {}", path),
+        )));
+    }
+    File::open(path)?;
+    Ok(())
+}
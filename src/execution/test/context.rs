@@ -0,0 +1,170 @@
+#[cfg(test)]
+pub mod test {
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    use crate::{
+        datasource::csv::CsvOptions,
+        execution::ExecutionContext,
+        logical_plan::{
+            data_frame::{DataFrame, Frame},
+            helper::column,
+            macro_utils::literal_string,
+        },
+    };
+
+    const SALES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/test_data/sales.csv");
+    const MTCARS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/test_data/mtcars.parquet");
+
+    #[test]
+    fn register_and_list_tables() {
+        let mut ctx = context();
+        ctx.register_parquet("mtcars", MTCARS).unwrap();
+
+        assert_eq!(ctx.table_names(), vec!["mtcars", "sales"]);
+        assert_eq!(
+            ctx.table("sales").unwrap().schema().unwrap().fields.len(),
+            4
+        );
+        assert!(
+            !ctx.table("mtcars")
+                .unwrap()
+                .schema()
+                .unwrap()
+                .fields
+                .is_empty()
+        );
+
+        assert!(ctx.deregister_table("mtcars").is_some());
+        assert!(ctx.deregister_table("mtcars").is_none());
+        assert_eq!(ctx.table_names(), vec!["sales"]);
+        assert!(ctx.table("mtcars").is_err());
+    }
+
+    #[test]
+    fn query_with_data_frame() {
+        let ctx = context();
+
+        let df = ctx
+            .table("sales")
+            .unwrap()
+            .filter(column("region").eq(literal_string("North")))
+            .project(vec![column("product")]);
+
+        let rows: usize = ctx
+            .collect(&df)
+            .unwrap()
+            .iter()
+            .map(|it| it.row_count())
+            .sum();
+        assert_eq!(rows, 4);
+    }
+
+    #[test]
+    fn query_with_sql() {
+        let mut ctx = context();
+        ctx.register_parquet("mtcars", MTCARS).unwrap();
+
+        let df = ctx
+            .sql("SELECT product, COUNT(*) AS n FROM sales GROUP BY product ORDER BY n DESC")
+            .unwrap();
+        let batches = ctx.collect(&df).unwrap();
+
+        let product: String = batches[0]
+            .field(0)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let n: i32 = batches[0]
+            .field(1)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!((product.as_str(), n), ("Widget", 6));
+
+        let df = ctx.sql("SELECT COUNT(*) FROM mtcars").unwrap();
+        let count: i32 = ctx.collect(&df).unwrap()[0]
+            .field(0)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(count, 32);
+    }
+
+    #[test]
+    fn optimize_keeps_the_output_schema() {
+        let ctx = context();
+
+        let df = ctx
+            .sql("SELECT product FROM sales WHERE quantity > 4 ORDER BY price")
+            .unwrap();
+        let optimized = ctx.optimize(&df).unwrap();
+
+        let names = |df: &Frame| -> Vec<String> {
+            df.schema()
+                .unwrap()
+                .fields
+                .iter()
+                .map(|it| it.name.clone())
+                .collect()
+        };
+        assert_eq!(names(&optimized), names(&df));
+    }
+
+    #[test]
+    fn register_csv_without_schema() {
+        let mut ctx = ExecutionContext::new();
+        ctx.register_csv("sales", SALES, CsvOptions::new().with_header(true))
+            .unwrap();
+
+        let df = ctx.sql("SELECT SUM(quantity) FROM sales").unwrap();
+        let total: i64 = ctx.collect(&df).unwrap()[0]
+            .field(0)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(total, 58);
+    }
+
+    #[test]
+    fn registration_errors() {
+        let mut ctx = context();
+
+        assert!(ctx.register_parquet("sales", MTCARS).is_err());
+        assert!(
+            ctx.register_parquet("missing", "/no/such/file.parquet")
+                .is_err()
+        );
+        assert!(
+            ctx.register_csv("sales", SALES, CsvOptions::new().with_header(true))
+                .is_err()
+        );
+        assert!(ctx.sql("SELECT * FROM missing").is_err());
+    }
+
+    fn context() -> ExecutionContext {
+        let mut ctx = ExecutionContext::new();
+        let options = CsvOptions::new()
+            .with_header(true)
+            .with_schema(Schema::new(vec![
+                Field::new("region", DataType::Utf8, false),
+                Field::new("product", DataType::Utf8, false),
+                Field::new("quantity", DataType::Int64, false),
+                Field::new("price", DataType::Float64, false),
+            ]));
+
+        ctx.register_csv("sales", SALES, options).unwrap();
+        ctx
+    }
+}
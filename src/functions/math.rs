@@ -0,0 +1,145 @@
+use std::sync::Arc;
+
+use arrow::{
+    array::{Array, ArrayRef, AsArray, Int8Array, Scalar},
+    compute::{
+        CastOptions, binary, cast, cast_with_options,
+        kernels::{cmp::lt, numeric::neg, zip::zip},
+        try_binary,
+    },
+    datatypes::{DataType, Decimal128Type, Float32Type, Float64Type, Int64Type},
+    error::ArrowError,
+};
+
+use crate::error::Result;
+
+/** Absolute value in the type of the argument, the minimum of a signed integer type overflows */
+pub fn abs(array: &ArrayRef) -> Result<ArrayRef> {
+    if array.data_type().is_unsigned_integer() {
+        return Ok(array.clone());
+    }
+
+    let zero = cast(&Int8Array::from(vec![0]), array.data_type())?;
+    let negative = lt(array, &Scalar::new(zero))?;
+    Ok(zip(&negative, &neg(array)?, array)?)
+}
+
+/** Apply a rounding operation to a float column, integer columns are already whole numbers */
+pub fn float_unary(
+    array: &ArrayRef,
+    f32_op: fn(f32) -> f32,
+    f64_op: fn(f64) -> f64,
+) -> Result<ArrayRef> {
+    Ok(match array.data_type() {
+        DataType::Float32 => Arc::new(
+            array
+                .as_primitive::<Float32Type>()
+                .unary::<_, Float32Type>(f32_op),
+        ),
+        DataType::Float64 => Arc::new(
+            array
+                .as_primitive::<Float64Type>()
+                .unary::<_, Float64Type>(f64_op),
+        ),
+        _ => array.clone(),
+    })
+}
+
+/** Apply `op` to the argument converted to Float64 */
+pub fn double_unary(array: &ArrayRef, op: fn(f64) -> f64) -> Result<ArrayRef> {
+    let values = cast(array, &DataType::Float64)?;
+    Ok(Arc::new(
+        values
+            .as_primitive::<Float64Type>()
+            .unary::<_, Float64Type>(op),
+    ))
+}
+
+/** Apply `op` row by row to both arguments converted to Float64 */
+pub fn double_binary(l: &ArrayRef, r: &ArrayRef, op: fn(f64, f64) -> f64) -> Result<ArrayRef> {
+    let l = cast(l, &DataType::Float64)?;
+    let r = cast(r, &DataType::Float64)?;
+    Ok(Arc::new(binary::<_, _, _, Float64Type>(
+        l.as_primitive::<Float64Type>(),
+        r.as_primitive::<Float64Type>(),
+        op,
+    )?))
+}
+
+/**
+ * Round half away from zero, to a number of decimal places when `digits` is given. Negative
+ * places round to tens, hundreds and so on. The result keeps the type of the argument, an
+ * integer that no longer fits after rounding is an error.
+ */
+pub fn round(array: &ArrayRef, digits: Option<&ArrayRef>) -> Result<ArrayRef> {
+    let Some(digits) = digits else {
+        return float_unary(array, f32::round, f64::round);
+    };
+
+    let digits = cast(digits, &DataType::Int64)?;
+    let rounded: ArrayRef = match array.data_type().is_integer() {
+        // Every integer type fits in an i128, a detour through f64 would lose precision
+        true => {
+            let values = cast(array, &DataType::Decimal128(38, 0))?;
+            Arc::new(
+                try_binary::<_, _, _, Decimal128Type>(
+                    values.as_primitive::<Decimal128Type>(),
+                    digits.as_primitive::<Int64Type>(),
+                    round_integer,
+                )?
+                .with_precision_and_scale(38, 0)?,
+            )
+        }
+        false => {
+            let values = cast(array, &DataType::Float64)?;
+            Arc::new(binary::<_, _, _, Float64Type>(
+                values.as_primitive::<Float64Type>(),
+                digits.as_primitive::<Int64Type>(),
+                round_to,
+            )?)
+        }
+    };
+
+    let options = CastOptions {
+        safe: false,
+        ..Default::default()
+    };
+    Ok(cast_with_options(&rounded, array.data_type(), &options)?)
+}
+
+/** Integers are already whole, negative places round half away from zero to a power of ten */
+fn round_integer(value: i128, digits: i64) -> std::result::Result<i128, ArrowError> {
+    if digits >= 0 {
+        return Ok(value);
+    }
+    // A power of ten past i128 is more than twice any integer argument, which rounds to zero
+    let Some(scale) = u32::try_from(digits.unsigned_abs())
+        .ok()
+        .and_then(|it| 10i128.checked_pow(it))
+    else {
+        return Ok(0);
+    };
+
+    let mut quotient = value / scale;
+    if (value % scale).unsigned_abs() * 2 >= scale.unsigned_abs() {
+        quotient += value.signum();
+    }
+    quotient.checked_mul(scale).ok_or_else(|| {
+        ArrowError::ArithmeticOverflow(format!("Rounding {} to {} places", value, digits))
+    })
+}
+
+fn round_to(value: f64, digits: i64) -> f64 {
+    let scale = 10f64.powi(digits.unsigned_abs().min(i32::MAX as u64) as i32);
+    if digits >= 0 {
+        let scaled = value * scale;
+        return match scaled.is_finite() {
+            true => scaled.round() / scale,
+            false => value,
+        };
+    }
+    match scale.is_finite() {
+        true => (value / scale).round() * scale,
+        false => 0.0 * value,
+    }
+}
@@ -0,0 +1,178 @@
+pub mod math;
+pub mod test;
+
+use std::{fmt, ops::RangeInclusive};
+
+use arrow::{array::new_null_array, datatypes::DataType};
+
+use crate::{
+    datatypes::{
+        arrow_field_vector::ArrowFieldVector,
+        coercion::is_arithmetic,
+        column_vector::{ColumnVector, ColumnVectorTrait},
+    },
+    error::{Result, UnakiteError},
+};
+
+/** The scalar functions built into the engine, callable from the DataFrame API and from SQL */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinFunction {
+    Abs,
+    Ceil,
+    Floor,
+    Round,
+    Sqrt,
+    Exp,
+    Ln,
+    Log10,
+    Log,
+    Power,
+}
+
+/** Every name a built-in function can be called by, matched case insensitively */
+const BUILTIN_FUNCTIONS: [(&str, BuiltinFunction); 12] = [
+    ("abs", BuiltinFunction::Abs),
+    ("ceil", BuiltinFunction::Ceil),
+    ("ceiling", BuiltinFunction::Ceil),
+    ("floor", BuiltinFunction::Floor),
+    ("round", BuiltinFunction::Round),
+    ("sqrt", BuiltinFunction::Sqrt),
+    ("exp", BuiltinFunction::Exp),
+    ("ln", BuiltinFunction::Ln),
+    ("log10", BuiltinFunction::Log10),
+    ("log", BuiltinFunction::Log),
+    ("power", BuiltinFunction::Power),
+    ("pow", BuiltinFunction::Power),
+];
+
+impl BuiltinFunction {
+    /** Look up a built-in function by name */
+    pub fn from_name(name: &str) -> Option<Self> {
+        BUILTIN_FUNCTIONS
+            .iter()
+            .find(|(it, _)| it.eq_ignore_ascii_case(name))
+            .map(|(_, fun)| *fun)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BuiltinFunction::Abs => "abs",
+            BuiltinFunction::Ceil => "ceil",
+            BuiltinFunction::Floor => "floor",
+            BuiltinFunction::Round => "round",
+            BuiltinFunction::Sqrt => "sqrt",
+            BuiltinFunction::Exp => "exp",
+            BuiltinFunction::Ln => "ln",
+            BuiltinFunction::Log10 => "log10",
+            BuiltinFunction::Log => "log",
+            BuiltinFunction::Power => "power",
+        }
+    }
+
+    /**
+     * How many arguments the function takes. ROUND takes an optional number of decimal places,
+     * LOG an optional base in front of its argument.
+     */
+    pub fn arity(&self) -> RangeInclusive<usize> {
+        match self {
+            BuiltinFunction::Round | BuiltinFunction::Log => 1..=2,
+            BuiltinFunction::Power => 2..=2,
+            _ => 1..=1,
+        }
+    }
+
+    pub fn check_arity(&self, args: usize) -> Result<()> {
+        let arity = self.arity();
+        if arity.contains(&args) {
+            return Ok(());
+        }
+
+        let expected = match arity.start() == arity.end() {
+            true => format!("{}", arity.start()),
+            false => format!("{} to {}", arity.start(), arity.end()),
+        };
+        Err(UnakiteError::Plan(format!(
+            "{} expects {} argument(s), got {}",
+            self, expected, args
+        )))
+    }
+
+    /**
+     * The type of the value the function produces for arguments of the given types. ABS, CEIL,
+     * FLOOR and ROUND keep the type of their argument, the other functions compute in Float64.
+     * NULL arguments are accepted, the function then returns NULL.
+     */
+    pub fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        self.check_arity(arg_types.len())?;
+
+        let numeric = |data_type: &DataType| is_arithmetic(data_type) || data_type.is_null();
+        if let Some(data_type) = arg_types.iter().find(|it| !numeric(it)) {
+            return Err(UnakiteError::Type(format!(
+                "{} expects numeric arguments, got {}",
+                self, data_type
+            )));
+        }
+
+        match self {
+            BuiltinFunction::Round
+                if arg_types.len() == 2
+                    && !arg_types[1].is_integer()
+                    && !arg_types[1].is_null() =>
+            {
+                Err(UnakiteError::Type(format!(
+                    "{} expects an integer number of decimal places, got {}",
+                    self, arg_types[1]
+                )))
+            }
+            BuiltinFunction::Abs
+            | BuiltinFunction::Ceil
+            | BuiltinFunction::Floor
+            | BuiltinFunction::Round => Ok(arg_types[0].clone()),
+            _ => Ok(DataType::Float64),
+        }
+    }
+
+    /** Apply the function to one batch of argument values */
+    pub fn evaluate(&self, args: &[ColumnVector]) -> Result<ColumnVector> {
+        let arg_types: Vec<DataType> = args.iter().map(|it| it.get_type()).collect();
+        let return_type = self.return_type(&arg_types)?;
+
+        if arg_types.iter().any(|it| it.is_null()) {
+            let rows = args.first().map(|it| it.size()).unwrap_or(0);
+            return Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+                field: new_null_array(&return_type, rows),
+            }));
+        }
+
+        let args: Vec<_> = args.iter().map(|it| it.to_array()).collect();
+        let result = match self {
+            BuiltinFunction::Abs => math::abs(&args[0]),
+            BuiltinFunction::Ceil => math::float_unary(&args[0], f32::ceil, f64::ceil),
+            BuiltinFunction::Floor => math::float_unary(&args[0], f32::floor, f64::floor),
+            BuiltinFunction::Round => math::round(&args[0], args.get(1)),
+            BuiltinFunction::Sqrt => math::double_unary(&args[0], f64::sqrt),
+            BuiltinFunction::Exp => math::double_unary(&args[0], f64::exp),
+            BuiltinFunction::Ln => math::double_unary(&args[0], f64::ln),
+            BuiltinFunction::Log10 => math::double_unary(&args[0], f64::log10),
+            BuiltinFunction::Log => match args.len() {
+                1 => math::double_unary(&args[0], f64::log10),
+                _ => math::double_binary(&args[1], &args[0], f64::log),
+            },
+            BuiltinFunction::Power => math::double_binary(&args[0], &args[1], f64::powf),
+        }
+        .map_err(|e| match e {
+            UnakiteError::Arrow(e) => {
+                UnakiteError::Execution(format!("{} failed: {}", self.name(), e))
+            }
+            e => e,
+        })?;
+
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector { field: result }))
+    }
+}
+
+impl fmt::Display for BuiltinFunction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name().to_ascii_uppercase())
+    }
+}
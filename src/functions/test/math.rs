@@ -0,0 +1,198 @@
+#[cfg(test)]
+pub mod test {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{
+            Array, ArrayRef, Float32Array, Float64Array, Int8Array, Int64Array, NullArray,
+            UInt32Array,
+        },
+        datatypes::DataType,
+    };
+
+    use crate::{
+        datatypes::{arrow_field_vector::ArrowFieldVector, column_vector::ColumnVector},
+        error::UnakiteError,
+        functions::BuiltinFunction,
+    };
+
+    #[test]
+    fn registry_lookup_and_arity() {
+        assert_eq!(
+            BuiltinFunction::from_name("SQRT"),
+            Some(BuiltinFunction::Sqrt)
+        );
+        assert_eq!(
+            BuiltinFunction::from_name("Ceiling"),
+            Some(BuiltinFunction::Ceil)
+        );
+        assert_eq!(
+            BuiltinFunction::from_name("pow"),
+            Some(BuiltinFunction::Power)
+        );
+        assert_eq!(BuiltinFunction::from_name("upper"), None);
+
+        assert!(BuiltinFunction::Round.check_arity(2).is_ok());
+        assert!(matches!(
+            BuiltinFunction::Power.check_arity(1),
+            Err(UnakiteError::Plan(_))
+        ));
+    }
+
+    #[test]
+    fn return_types() {
+        use DataType::*;
+
+        let return_type = |fun: BuiltinFunction, args: &[DataType]| fun.return_type(args);
+        assert_eq!(return_type(BuiltinFunction::Abs, &[Int32]).unwrap(), Int32);
+        assert_eq!(
+            return_type(BuiltinFunction::Round, &[Float32, Int64]).unwrap(),
+            Float32
+        );
+        assert_eq!(
+            return_type(BuiltinFunction::Sqrt, &[Int64]).unwrap(),
+            Float64
+        );
+        assert_eq!(
+            return_type(BuiltinFunction::Power, &[Int32, Float32]).unwrap(),
+            Float64
+        );
+
+        // NULL fits any numeric argument
+        assert_eq!(return_type(BuiltinFunction::Abs, &[Null]).unwrap(), Null);
+        assert_eq!(
+            return_type(BuiltinFunction::Round, &[Int64, Null]).unwrap(),
+            Int64
+        );
+        assert_eq!(return_type(BuiltinFunction::Ln, &[Null]).unwrap(), Float64);
+
+        assert!(matches!(
+            return_type(BuiltinFunction::Ln, &[Utf8]),
+            Err(UnakiteError::Type(_))
+        ));
+        assert!(matches!(
+            return_type(BuiltinFunction::Round, &[Float64, Float64]),
+            Err(UnakiteError::Type(_))
+        ));
+    }
+
+    #[test]
+    fn evaluate_unary_functions() {
+        let doubles = vector(Arc::new(Float64Array::from(vec![
+            Some(-2.5),
+            None,
+            Some(4.0),
+        ])));
+
+        let result = evaluate(BuiltinFunction::Abs, vec![doubles.clone()]);
+        assert_eq!(doubles_of(&result), vec![Some(2.5), None, Some(4.0)]);
+        let result = evaluate(BuiltinFunction::Round, vec![doubles.clone()]);
+        assert_eq!(doubles_of(&result), vec![Some(-3.0), None, Some(4.0)]);
+        let result = evaluate(BuiltinFunction::Sqrt, vec![doubles.clone()]);
+        let values = doubles_of(&result);
+        assert!(values[0].unwrap().is_nan());
+        assert_eq!(values[1..], [None, Some(2.0)]);
+
+        // Integers keep their type, or compute in Float64
+        let longs = vector(Arc::new(Int64Array::from(vec![-7, 0, 100])));
+        let result = evaluate(BuiltinFunction::Abs, vec![longs.clone()]);
+        let result = result.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(result.values().to_vec(), vec![7, 0, 100]);
+        let result = evaluate(BuiltinFunction::Log10, vec![longs.clone()]);
+        assert_eq!(doubles_of(&result)[2], Some(2.0));
+
+        let floats = vector(Arc::new(Float32Array::from(vec![1.5, -1.5])));
+        let result = evaluate(BuiltinFunction::Floor, vec![floats]);
+        let result = result.as_any().downcast_ref::<Float32Array>().unwrap();
+        assert_eq!(result.values().to_vec(), vec![1.0, -2.0]);
+
+        let unsigned = vector(Arc::new(UInt32Array::from(vec![3, 4])));
+        let result = evaluate(BuiltinFunction::Abs, vec![unsigned]);
+        assert_eq!(result.data_type(), &DataType::UInt32);
+    }
+
+    #[test]
+    fn evaluate_binary_functions() {
+        let doubles = vector(Arc::new(Float64Array::from(vec![1234.5678, -1234.5678])));
+        let digits = vector(Arc::new(Int64Array::from(vec![2, -2])));
+        let result = evaluate(BuiltinFunction::Round, vec![doubles, digits]);
+        assert_eq!(doubles_of(&result), vec![Some(1234.57), Some(-1200.0)]);
+
+        let longs = vector(Arc::new(Int64Array::from(vec![1250, 1249])));
+        let digits = vector(Arc::new(Int64Array::from(vec![-2, -2])));
+        let result = evaluate(BuiltinFunction::Round, vec![longs, digits]);
+        let result = result.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(result.values().to_vec(), vec![1300, 1200]);
+
+        // Integers are rounded exactly, even past the precision of a double
+        let longs = vector(Arc::new(Int64Array::from(vec![
+            9_007_199_254_740_993,
+            -9_007_199_254_740_993,
+            -15,
+        ])));
+        let digits = vector(Arc::new(Int64Array::from(vec![2, -1, -40])));
+        let result = evaluate(BuiltinFunction::Round, vec![longs, digits]);
+        let result = result.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(
+            result.values().to_vec(),
+            vec![9_007_199_254_740_993, -9_007_199_254_740_990, 0]
+        );
+
+        let base = vector(Arc::new(Int64Array::from(vec![2, 10])));
+        let exponent = vector(Arc::new(Float64Array::from(vec![10.0, -1.0])));
+        let result = evaluate(BuiltinFunction::Power, vec![base.clone(), exponent]);
+        assert_eq!(doubles_of(&result), vec![Some(1024.0), Some(0.1)]);
+
+        let values = vector(Arc::new(Int64Array::from(vec![8, 1000])));
+        let result = evaluate(BuiltinFunction::Log, vec![base, values]);
+        for value in doubles_of(&result) {
+            assert!((value.unwrap() - 3.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn null_arguments_give_null() {
+        let nulls = vector(Arc::new(NullArray::new(2)));
+        let result = evaluate(BuiltinFunction::Abs, vec![nulls.clone()]);
+        assert_eq!(result.data_type(), &DataType::Null);
+        assert_eq!(result.len(), 2);
+
+        let result = evaluate(BuiltinFunction::Sqrt, vec![nulls.clone()]);
+        assert_eq!(doubles_of(&result), vec![None, None]);
+
+        let longs = vector(Arc::new(Int64Array::from(vec![1, 2])));
+        let result = evaluate(BuiltinFunction::Round, vec![longs, nulls]);
+        assert_eq!(result.data_type(), &DataType::Int64);
+        assert_eq!(result.null_count(), 2);
+    }
+
+    #[test]
+    fn integer_overflow_is_an_error() {
+        let smallest = vector(Arc::new(Int8Array::from(vec![1, i8::MIN])));
+        assert!(matches!(
+            BuiltinFunction::Abs.evaluate(&[smallest]),
+            Err(UnakiteError::Execution(_))
+        ));
+
+        // 125 rounds to 130, which does not fit in an Int8
+        let digits = vector(Arc::new(Int64Array::from(vec![-1, -1])));
+        let too_big = vector(Arc::new(Int8Array::from(vec![1, 125])));
+        assert!(matches!(
+            BuiltinFunction::Round.evaluate(&[too_big, digits]),
+            Err(UnakiteError::Execution(_))
+        ));
+    }
+
+    fn evaluate(fun: BuiltinFunction, args: Vec<ColumnVector>) -> ArrayRef {
+        fun.evaluate(&args).unwrap().to_array()
+    }
+
+    fn vector(array: ArrayRef) -> ColumnVector {
+        ColumnVector::ArrowVector(ArrowFieldVector { field: array })
+    }
+
+    fn doubles_of(array: &ArrayRef) -> Vec<Option<f64>> {
+        let values = array.as_any().downcast_ref::<Float64Array>().unwrap();
+        values.iter().collect()
+    }
+}
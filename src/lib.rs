@@ -1,4 +1,14 @@
+
 pub mod datatypes;
+pub mod error;
 pub mod logical_plan;
 pub mod datasource;
-pub mod physical_plan;
\ No newline at end of file
+pub mod execution;
+pub mod functions;
+pub mod optimizer;
+pub mod physical_plan;
+pub mod query_planner;
+pub mod sql;
+
+#[cfg(test)]
+pub mod test_util;
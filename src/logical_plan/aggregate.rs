@@ -2,7 +2,8 @@ use std::{fmt, sync::Arc};
 
 use crate::{
     datatypes::schema::{Field, Schema},
-    logical_plan::{expr::ExprRef, AggregateExpr, LogicalExpr, LogicalPlan},
+    error::Result,
+    logical_plan::{AggregateExpr, LogicalExpr, LogicalPlan, expr::ExprRef},
 };
 
 pub struct Aggregate {
@@ -12,24 +13,24 @@ pub struct Aggregate {
 }
 
 impl Aggregate {
-    pub fn schema(&self) -> Arc<Schema> {
+    pub fn schema(&self) -> Result<Arc<Schema>> {
         let mut groups: Vec<Field> = self
             .group_expr
             .iter()
             .map(|it| it.state.to_field(self.input.clone()))
-            .collect();
+            .collect::<Result<_>>()?;
 
         let mut aggregates: Vec<Field> = self
-            .group_expr
+            .aggregate_expr
             .iter()
-            .map(|it| it.state.to_field(self.input.clone()))
-            .collect();
+            .map(|it| it.to_field(self.input.clone()))
+            .collect::<Result<_>>()?;
 
         let mut fields = Vec::<Field>::new();
         fields.append(&mut groups);
         fields.append(&mut aggregates);
 
-        Arc::new(Schema { fields })
+        Ok(Arc::new(Schema { fields }))
     }
 
     pub fn children(&self) -> Vec<Arc<LogicalPlan>> {
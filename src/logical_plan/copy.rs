@@ -0,0 +1,48 @@
+use std::sync::Arc;
+
+use arrow::datatypes::DataType;
+
+use crate::{
+    datasource::sink::{WriteFormat, WriteOptions},
+    datatypes::schema::{Field, Schema},
+    error::Result,
+    logical_plan::LogicalPlan,
+};
+
+/** Write the rows of the input to files, producing the number of rows written */
+pub struct Copy {
+    pub input: Arc<LogicalPlan>,
+    pub path: String,
+    pub format: WriteFormat,
+    pub options: WriteOptions,
+}
+
+impl Copy {
+    pub fn children(&self) -> Vec<Arc<LogicalPlan>> {
+        vec![self.input.clone()]
+    }
+
+    pub fn schema(&self) -> Result<Arc<Schema>> {
+        Ok(Arc::new(Schema {
+            fields: vec![Field {
+                name: "rows".to_string(),
+                data_type: DataType::UInt64,
+                nullable: false,
+            }],
+        }))
+    }
+}
+
+impl std::fmt::Display for Copy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Copy: path={}, format={:?}", self.path, self.format)?;
+        if !self.options.partition_by.is_empty() {
+            write!(
+                f,
+                ", partition_by=[{}]",
+                self.options.partition_by.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
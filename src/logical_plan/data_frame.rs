@@ -1,17 +1,25 @@
 use std::sync::Arc;
 
+use arrow::array::{Array, UInt64Array};
+
 use crate::{
+    datasource::sink::{WriteFormat, WriteOptions},
     datatypes::schema::Schema,
+    error::{Result, UnakiteError},
     logical_plan::{
         AggregateExpr, LogicalPlan,
         aggregate::Aggregate,
+        copy::Copy,
         expr::ExprRef,
         helper::numeric_lit_expr_to_usize,
         join::{Join, JoinType},
         limit::Limit,
         projection::Projection,
         selection::Selection,
+        sort::{Sort, SortExpr},
     },
+    optimizer::Optimizer,
+    query_planner::QueryPlanner,
 };
 
 pub trait DataFrame {
@@ -25,8 +33,13 @@ pub trait DataFrame {
     where
         Self: Sized;
 
-    /** Apply a limit */
-    fn limit(&self, expr: ExprRef) -> Frame
+    /** Apply a limit, the expression must be a numeric literal */
+    fn limit(&self, expr: ExprRef) -> Result<Frame>
+    where
+        Self: Sized;
+
+    /** Sort rows by one or more keys */
+    fn sort(&self, order_by: Vec<SortExpr>) -> Frame
     where
         Self: Sized;
 
@@ -40,8 +53,13 @@ pub trait DataFrame {
     where
         Self: Sized;
 
+    /** Write the rows to files, the resulting frame holds the number of rows written */
+    fn copy_to(&self, path: &str, format: WriteFormat, options: WriteOptions) -> Frame
+    where
+        Self: Sized;
+
     /** Returns the schema of the data that will be produced by this DataFrame. */
-    fn schema(&self) -> Arc<Schema>
+    fn schema(&self) -> Result<Arc<Schema>>
     where
         Self: Sized;
 
@@ -51,6 +69,7 @@ pub trait DataFrame {
         Self: Sized;
 }
 
+#[derive(Clone)]
 pub struct Frame {
     pub plan: Arc<LogicalPlan>,
 }
@@ -80,29 +99,24 @@ impl DataFrame for Frame {
         }
     }
 
-    fn limit(&self, expr: ExprRef) -> Frame
+    fn limit(&self, expr: ExprRef) -> Result<Frame>
     where
         Self: Sized,
     {
         let state = expr.state.as_ref();
-        let limit = numeric_lit_expr_to_usize(state);
-        Frame {
+        let limit = numeric_lit_expr_to_usize(state)?;
+        Ok(Frame {
             plan: Arc::new(LogicalPlan::LimitPlan(Limit {
                 input: self.plan.clone(),
                 limit,
             })),
-        }
+        })
     }
 
     fn join(&self, plan: Frame, join_type: JoinType, on: Vec<(String, String)>) -> Frame
     where
         Self: Sized,
     {
-        let join_type = match join_type {
-            JoinType::Inner => JoinType::Inner,
-            JoinType::Left => JoinType::Left,
-            JoinType::Right => JoinType::Right,
-        };
         Frame {
             plan: Arc::new(LogicalPlan::JoinPlan(Join {
                 left: self.plan.clone(),
@@ -113,6 +127,18 @@ impl DataFrame for Frame {
         }
     }
 
+    fn sort(&self, order_by: Vec<SortExpr>) -> Frame
+    where
+        Self: Sized,
+    {
+        Frame {
+            plan: Arc::new(LogicalPlan::SortPlan(Sort {
+                input: self.plan.clone(),
+                order_by,
+            })),
+        }
+    }
+
     fn aggregate(&self, group_by: Vec<ExprRef>, aggregate_expr: Vec<AggregateExpr>) -> Frame
     where
         Self: Sized,
@@ -121,12 +147,26 @@ impl DataFrame for Frame {
             plan: Arc::new(LogicalPlan::AggregatePlan(Aggregate {
                 input: self.plan.clone(),
                 group_expr: group_by,
-                aggregate_expr: aggregate_expr,
+                aggregate_expr,
             })),
         }
     }
 
-    fn schema(&self) -> Arc<Schema>
+    fn copy_to(&self, path: &str, format: WriteFormat, options: WriteOptions) -> Frame
+    where
+        Self: Sized,
+    {
+        Frame {
+            plan: Arc::new(LogicalPlan::CopyPlan(Copy {
+                input: self.plan.clone(),
+                path: path.to_string(),
+                format,
+                options,
+            })),
+        }
+    }
+
+    fn schema(&self) -> Result<Arc<Schema>>
     where
         Self: Sized,
     {
@@ -140,3 +180,41 @@ impl DataFrame for Frame {
         self.plan.clone()
     }
 }
+
+impl Frame {
+    /** Execute the frame and write the rows to CSV files, returning the number of rows written */
+    pub fn write_csv(&self, path: &str, options: WriteOptions) -> Result<usize> {
+        self.write(path, WriteFormat::Csv, options)
+    }
+
+    /** Execute the frame and write the rows to Parquet files */
+    pub fn write_parquet(&self, path: &str, options: WriteOptions) -> Result<usize> {
+        self.write(path, WriteFormat::Parquet, options)
+    }
+
+    /** Execute the frame and write the rows to newline delimited JSON files */
+    pub fn write_json(&self, path: &str, options: WriteOptions) -> Result<usize> {
+        self.write(path, WriteFormat::Json, options)
+    }
+
+    /** Execute the frame and write the rows to Arrow IPC files */
+    pub fn write_ipc(&self, path: &str, options: WriteOptions) -> Result<usize> {
+        self.write(path, WriteFormat::ArrowIpc, options)
+    }
+
+    fn write(&self, path: &str, format: WriteFormat, options: WriteOptions) -> Result<usize> {
+        let copy = self.copy_to(path, format, options);
+        let plan = Optimizer::new().optimize(copy.plan)?;
+
+        let mut rows = 0;
+        for batch in QueryPlanner::create_physical_plan(&plan)?.execute()? {
+            let array = batch?.field(0)?.to_array();
+            let counts = array
+                .as_any()
+                .downcast_ref::<UInt64Array>()
+                .ok_or_else(|| UnakiteError::Execution("Copy produced no row count".to_string()))?;
+            rows += counts.values().iter().sum::<u64>() as usize;
+        }
+        Ok(rows)
+    }
+}
@@ -1,16 +1,22 @@
 use std::{fmt, sync::Arc};
 
+use arrow::datatypes::DataType;
+
 use crate::{
     datatypes::schema::Field,
+    error::Result,
     logical_plan::{
         LogicalExpr, LogicalPlan,
-        expression::{Alias, Column},
+        expression::{
+            Alias, Between, Case, CastExpr, Coalesce, Column, InList, IsNull, Negative, Not,
+            NullIf, ScalarFunction,
+        },
         macro_utils::{
             AggregateAvg, AggregateCount, AggregateCountDistinct, AggregateMax, AggregateMin,
-            AggregateSum, And, EqOp, Gt, Gteq, LiteralDouble, LiteralFloat, LiteralInt8,
-            LiteralInt16, LiteralInt32, LiteralInt64, LiteralString, LiteralUInt8, LiteralUInt16,
-            LiteralUInt32, LiteralUInt64, Lt, Lteq, MathAdd, MathDivide, MathMod, MathMultiply,
-            MathSubtract, Neq, Or,
+            AggregateSum, And, EqOp, Gt, Gteq, IsDistinctFrom, IsNotDistinctFrom, LiteralDouble,
+            LiteralFloat, LiteralInt8, LiteralInt16, LiteralInt32, LiteralInt64, LiteralNull,
+            LiteralString, LiteralUInt8, LiteralUInt16, LiteralUInt32, LiteralUInt64, Lt, Lteq,
+            MathAdd, MathDivide, MathMod, MathMultiply, MathSubtract, Neq, Or,
         },
     },
 };
@@ -42,10 +48,11 @@ pub enum NumericExpression {
 pub enum LiteralExpression {
     StringExpr(LiteralString),
     Numeric(NumericExpression),
+    NullExpr(LiteralNull),
 }
 
 impl NumericExpression {
-    pub fn to_field(&self, input: Arc<LogicalPlan>) -> Field {
+    pub fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
         match self {
             NumericExpression::Integer8Expr(literal_int8) => literal_int8.to_field(input),
             NumericExpression::Integer16Expr(literal_int16) => literal_int16.to_field(input),
@@ -62,16 +69,17 @@ impl NumericExpression {
 }
 
 impl LiteralExpression {
-    pub fn to_field(&self, input: Arc<LogicalPlan>) -> Field {
+    pub fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
         match self {
             LiteralExpression::StringExpr(literal_string) => literal_string.to_field(input),
             LiteralExpression::Numeric(numeric_expression) => numeric_expression.to_field(input),
+            LiteralExpression::NullExpr(literal_null) => literal_null.to_field(input),
         }
     }
 }
 
 impl MathExpression {
-    pub fn to_field(&self, input: Arc<LogicalPlan>) -> Field {
+    pub fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
         match self {
             MathExpression::AddExpr(math_add) => math_add.to_field(input),
             MathExpression::SubExpr(math_subtract) => math_subtract.to_field(input),
@@ -93,6 +101,22 @@ pub enum Expr {
     LtEqExpr(Lteq),
     AndExpr(And),
     OrExpr(Or),
+    IsDistinctFromExpr(IsDistinctFrom),
+    IsNotDistinctFromExpr(IsNotDistinctFrom),
+
+    // Null handling
+    IsNullExpr(IsNull),
+    CoalesceExpr(Coalesce),
+    NullIfExpr(NullIf),
+
+    // Unary
+    NotExpr(Not),
+    NegativeExpr(Negative),
+
+    // Conditional
+    CaseExpr(Case),
+    InListExpr(InList),
+    BetweenExpr(Between),
 
     // Column
     ColumnExpr(Column),
@@ -101,6 +125,10 @@ pub enum Expr {
     LiteralExpr(LiteralExpression),
     // Math Expression
     MathExpr(MathExpression),
+    // Built-in scalar function
+    ScalarFunctionExpr(ScalarFunction),
+    // CAST and TRY_CAST
+    CastExpr(CastExpr),
 
     // aggregations
     MaxExpr(AggregateMax),
@@ -114,12 +142,67 @@ pub enum Expr {
     AliasExpr(Alias),
 }
 
+impl Expr {
+    /** Direct sub-expressions of this expression, used to walk expression trees */
+    pub fn children(&self) -> Vec<Arc<Expr>> {
+        match self {
+            Expr::EqOpExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+            Expr::NeqExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+            Expr::GtExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+            Expr::GtEqExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+            Expr::LtExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+            Expr::LtEqExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+            Expr::AndExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+            Expr::OrExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+            Expr::IsDistinctFromExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+            Expr::IsNotDistinctFromExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+            Expr::IsNullExpr(expr) => vec![expr.expr.clone()],
+            Expr::CoalesceExpr(expr) => expr.args.clone(),
+            Expr::NullIfExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+            Expr::NotExpr(expr) => vec![expr.expr.clone()],
+            Expr::NegativeExpr(expr) => vec![expr.expr.clone()],
+            Expr::CaseExpr(expr) => {
+                let mut children: Vec<Arc<Expr>> = expr.expr.iter().cloned().collect();
+                for (when, then) in &expr.when_then {
+                    children.push(when.clone());
+                    children.push(then.clone());
+                }
+                children.extend(expr.else_expr.iter().cloned());
+                children
+            }
+            Expr::InListExpr(expr) => {
+                let mut children = vec![expr.expr.clone()];
+                children.extend(expr.list.iter().cloned());
+                children
+            }
+            Expr::BetweenExpr(expr) => vec![expr.expr.clone(), expr.low.clone(), expr.high.clone()],
+            Expr::MathExpr(math) => match math {
+                MathExpression::AddExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+                MathExpression::SubExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+                MathExpression::MulExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+                MathExpression::DivExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+                MathExpression::ModExpr(expr) => vec![expr.l.clone(), expr.r.clone()],
+            },
+            Expr::ScalarFunctionExpr(function) => function.args.clone(),
+            Expr::CastExpr(cast) => vec![cast.expr.clone()],
+            Expr::MaxExpr(aggregate) => vec![aggregate.expr.state.clone()],
+            Expr::MinExpr(aggregate) => vec![aggregate.expr.state.clone()],
+            Expr::SumExpr(aggregate) => vec![aggregate.expr.state.clone()],
+            Expr::AvgExpr(aggregate) => vec![aggregate.expr.state.clone()],
+            Expr::CountExpr(aggregate) => vec![aggregate.expr.state.clone()],
+            Expr::CountDistinctExpr(aggregate) => vec![aggregate.expr.state.clone()],
+            Expr::AliasExpr(alias) => vec![alias.expr.state.clone()],
+            Expr::ColumnExpr(_) | Expr::LiteralExpr(_) => vec![],
+        }
+    }
+}
+
 impl LogicalExpr for Expr {
     /**
      * Return meta-data about the value that will be produced by this expression when evaluated
      * against a particular input.
      */
-    fn to_field(&self, input: Arc<LogicalPlan>) -> Field {
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
         match self {
             Expr::MaxExpr(aggregate_max) => aggregate_max.to_field(input),
             Expr::MinExpr(aggregate_min) => aggregate_min.to_field(input),
@@ -130,6 +213,8 @@ impl LogicalExpr for Expr {
                 aggregate_count_distinct.to_field(input)
             }
             Expr::MathExpr(math_expression) => math_expression.to_field(input),
+            Expr::ScalarFunctionExpr(function) => function.to_field(input),
+            Expr::CastExpr(cast) => cast.to_field(input),
             Expr::ColumnExpr(column) => column.to_field(input),
             Expr::LiteralExpr(literal_expression) => literal_expression.to_field(input),
             Expr::AliasExpr(alias) => alias.to_field(input),
@@ -142,6 +227,16 @@ impl LogicalExpr for Expr {
             Expr::LtEqExpr(lteq) => lteq.to_field(input),
             Expr::AndExpr(and) => and.to_field(input),
             Expr::OrExpr(or) => or.to_field(input),
+            Expr::IsDistinctFromExpr(distinct) => distinct.to_field(input),
+            Expr::IsNotDistinctFromExpr(not_distinct) => not_distinct.to_field(input),
+            Expr::IsNullExpr(is_null) => is_null.to_field(input),
+            Expr::CoalesceExpr(coalesce) => coalesce.to_field(input),
+            Expr::NullIfExpr(null_if) => null_if.to_field(input),
+            Expr::NotExpr(not) => not.to_field(input),
+            Expr::NegativeExpr(negative) => negative.to_field(input),
+            Expr::CaseExpr(case) => case.to_field(input),
+            Expr::InListExpr(in_list) => in_list.to_field(input),
+            Expr::BetweenExpr(between) => between.to_field(input),
         }
     }
 }
@@ -151,6 +246,8 @@ impl fmt::Display for Expr {
         match self {
             Expr::ColumnExpr(column) => write!(f, "{}", column),
             Expr::MathExpr(math_expression) => write!(f, "{:?}", math_expression),
+            Expr::ScalarFunctionExpr(function) => write!(f, "{}", function),
+            Expr::CastExpr(cast) => write!(f, "{}", cast),
             Expr::MaxExpr(aggregate_max) => write!(f, "{}", aggregate_max),
             Expr::MinExpr(aggregate_min) => write!(f, "{}", aggregate_min),
             Expr::SumExpr(aggregate_sum) => write!(f, "{}", aggregate_sum),
@@ -170,11 +267,21 @@ impl fmt::Display for Expr {
             Expr::LtEqExpr(lteq) => write!(f, "{}", lteq),
             Expr::AndExpr(and) => write!(f, "{}", and),
             Expr::OrExpr(or) => write!(f, "{}", or),
+            Expr::IsDistinctFromExpr(distinct) => write!(f, "{}", distinct),
+            Expr::IsNotDistinctFromExpr(not_distinct) => write!(f, "{}", not_distinct),
+            Expr::IsNullExpr(is_null) => write!(f, "{}", is_null),
+            Expr::CoalesceExpr(coalesce) => write!(f, "{}", coalesce),
+            Expr::NullIfExpr(null_if) => write!(f, "{}", null_if),
+            Expr::NotExpr(not) => write!(f, "{}", not),
+            Expr::NegativeExpr(negative) => write!(f, "{}", negative),
+            Expr::CaseExpr(case) => write!(f, "{}", case),
+            Expr::InListExpr(in_list) => write!(f, "{}", in_list),
+            Expr::BetweenExpr(between) => write!(f, "{}", between),
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ExprRef {
     pub state: Arc<Expr>,
 }
@@ -183,9 +290,69 @@ impl ExprRef {
     pub fn new(state: Arc<Expr>) -> Self {
         ExprRef { state }
     }
-    pub fn to_field(&self, input: Arc<LogicalPlan>) -> Field {
+    pub fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
         self.state.to_field(input)
     }
+
+    pub fn is_null(self) -> ExprRef {
+        ExprRef::new(Arc::new(Expr::IsNullExpr(IsNull {
+            expr: self.state,
+            negated: false,
+        })))
+    }
+
+    pub fn is_not_null(self) -> ExprRef {
+        ExprRef::new(Arc::new(Expr::IsNullExpr(IsNull {
+            expr: self.state,
+            negated: true,
+        })))
+    }
+
+    /** Whether the value equals one of `list`, or none of them when negated */
+    pub fn in_list(self, list: Vec<ExprRef>, negated: bool) -> ExprRef {
+        ExprRef::new(Arc::new(Expr::InListExpr(InList {
+            expr: self.state,
+            list: list.into_iter().map(|it| it.state).collect(),
+            negated,
+        })))
+    }
+
+    /** Whether the value lies between `low` and `high`, both included */
+    pub fn between(self, low: ExprRef, high: ExprRef) -> ExprRef {
+        ExprRef::new(Arc::new(Expr::BetweenExpr(Between {
+            expr: self.state,
+            low: low.state,
+            high: high.state,
+            negated: false,
+        })))
+    }
+
+    pub fn not_between(self, low: ExprRef, high: ExprRef) -> ExprRef {
+        ExprRef::new(Arc::new(Expr::BetweenExpr(Between {
+            expr: self.state,
+            low: low.state,
+            high: high.state,
+            negated: true,
+        })))
+    }
+
+    /** Convert the value to `data_type`, failing on values that cannot be converted */
+    pub fn cast(self, data_type: DataType) -> ExprRef {
+        ExprRef::new(Arc::new(Expr::CastExpr(CastExpr {
+            expr: self.state,
+            data_type,
+            try_cast: false,
+        })))
+    }
+
+    /** Convert the value to `data_type`, values that cannot be converted become null */
+    pub fn try_cast(self, data_type: DataType) -> ExprRef {
+        ExprRef::new(Arc::new(Expr::CastExpr(CastExpr {
+            expr: self.state,
+            data_type,
+            try_cast: true,
+        })))
+    }
 }
 
 pub trait AsAlias {
@@ -236,6 +403,23 @@ impl_exprref_math_op!(Add, add, AddExpr, MathAdd);
 impl_exprref_math_op!(Sub, sub, SubExpr, MathSubtract);
 impl_exprref_math_op!(Mul, mul, MulExpr, MathMultiply);
 impl_exprref_math_op!(Div, div, DivExpr, MathDivide);
+impl_exprref_math_op!(Rem, rem, ModExpr, MathMod);
+
+impl std::ops::Not for ExprRef {
+    type Output = ExprRef;
+
+    fn not(self) -> Self::Output {
+        ExprRef::new(Arc::new(Expr::NotExpr(Not { expr: self.state })))
+    }
+}
+
+impl std::ops::Neg for ExprRef {
+    type Output = ExprRef;
+
+    fn neg(self) -> Self::Output {
+        ExprRef::new(Arc::new(Expr::NegativeExpr(Negative { expr: self.state })))
+    }
+}
 
 impl ExprRef {
     impl_exprref_binop! {
@@ -246,5 +430,8 @@ impl ExprRef {
         lt => LtExpr, Lt,
         lteq => LtEqExpr, Lteq,
         and => AndExpr, And,
+        or => OrExpr, Or,
+        is_distinct_from => IsDistinctFromExpr, IsDistinctFrom,
+        is_not_distinct_from => IsNotDistinctFromExpr, IsNotDistinctFrom,
     }
 }
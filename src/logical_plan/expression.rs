@@ -3,8 +3,16 @@ use std::sync::Arc;
 use arrow::datatypes::DataType;
 
 use crate::{
-    datatypes::schema::Field,
-    logical_plan::{LogicalExpr, LogicalPlan, expr::ExprRef},
+    datatypes::{
+        coercion::{check_cast, common_type, is_arithmetic},
+        schema::Field,
+    },
+    error::{Result, UnakiteError},
+    functions::BuiltinFunction,
+    logical_plan::{
+        LogicalExpr, LogicalPlan,
+        expr::{Expr, ExprRef},
+    },
 };
 
 /*Logical expression representing a reference to a column by name. */
@@ -13,14 +21,14 @@ pub struct Column {
 }
 
 impl LogicalExpr for Column {
-    fn to_field(&self, input: Arc<LogicalPlan>) -> Field {
-        let schema = input.schema();
-        let found_field = schema
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        let schema = input.schema()?;
+        schema
             .fields
             .iter()
             .find(|&it| it.name == self.name)
-            .unwrap();
-        found_field.clone()
+            .cloned()
+            .ok_or_else(|| UnakiteError::Schema(format!("No column named {}", self.name)))
     }
 }
 
@@ -30,11 +38,15 @@ pub struct ColumnIndex {
 }
 
 impl LogicalExpr for ColumnIndex {
-    fn to_field(&self, input: Arc<LogicalPlan>) -> Field {
-        let schema = input.schema();
-        let found_field = &schema.fields[self.i];
-
-        found_field.clone()
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        let schema = input.schema()?;
+        schema.fields.get(self.i).cloned().ok_or_else(|| {
+            UnakiteError::Schema(format!(
+                "Column index {} is out of bounds for {} columns",
+                self.i,
+                schema.fields.len()
+            ))
+        })
     }
 }
 
@@ -44,56 +56,310 @@ pub struct Alias {
 }
 
 impl LogicalExpr for Alias {
-    fn to_field(&self, input: Arc<LogicalPlan>) -> Field {
-        Field {
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        let field = self.expr.state.to_field(input)?;
+        Ok(Field {
             name: self.alias.clone(),
-            data_type: self.expr.state.to_field(input).data_type,
-        }
+            ..field
+        })
     }
 }
 
-/* Scalar function */
+/* Call of a built-in scalar function */
 pub struct ScalarFunction {
-    name: String,
-    _args: Vec<Arc<dyn LogicalExpr>>,
-    return_type: DataType,
+    pub fun: BuiltinFunction,
+    pub args: Vec<Arc<Expr>>,
+}
+
+impl ScalarFunction {
+    pub fn new(fun: BuiltinFunction, args: Vec<ExprRef>) -> Result<Self> {
+        fun.check_arity(args.len())?;
+        Ok(ScalarFunction {
+            fun,
+            args: args.into_iter().map(|it| it.state).collect(),
+        })
+    }
 }
 
 impl LogicalExpr for ScalarFunction {
-    fn to_field(&self, _input: Arc<LogicalPlan>) -> Field {
-        Field {
-            name: self.name.clone(),
-            data_type: self.return_type.clone(),
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        let args = self
+            .args
+            .iter()
+            .map(|it| it.to_field(input.clone()))
+            .collect::<Result<Vec<Field>>>()?;
+        let arg_types: Vec<DataType> = args.iter().map(|it| it.data_type.clone()).collect();
+
+        Ok(Field {
+            name: format!("{}", self),
+            data_type: self.fun.return_type(&arg_types)?,
+            nullable: args.iter().any(|it| it.nullable),
+        })
+    }
+}
+
+/* Logical NOT of a boolean */
+pub struct Not {
+    pub expr: Arc<Expr>,
+}
+
+impl LogicalExpr for Not {
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        let field = self.expr.to_field(input)?;
+        if !matches!(field.data_type, DataType::Boolean | DataType::Null) {
+            return Err(UnakiteError::Type(format!(
+                "NOT expects a boolean operand, got {}",
+                field.data_type
+            )));
+        }
+
+        Ok(Field {
+            name: format!("{}", self),
+            data_type: DataType::Boolean,
+            nullable: field.nullable,
+        })
+    }
+}
+
+/* Numeric negation, the minimum of a signed integer type overflows */
+pub struct Negative {
+    pub expr: Arc<Expr>,
+}
+
+impl LogicalExpr for Negative {
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        let field = self.expr.to_field(input)?;
+        let data_type = &field.data_type;
+        if !(is_arithmetic(data_type) || *data_type == DataType::Null)
+            || data_type.is_unsigned_integer()
+        {
+            return Err(UnakiteError::Type(format!(
+                "Negation expects a signed numeric operand, got {}",
+                data_type
+            )));
         }
+
+        Ok(Field {
+            name: format!("{}", self),
+            ..field
+        })
     }
 }
 
-pub struct UnaryExpr {
-    name: String,
-    op: String,
-    expr: Arc<dyn LogicalExpr>,
+/*
+ * CASE with an operand compares it to every WHEN value in turn, CASE without one takes the first
+ * WHEN condition that is true. Rows no branch applies to get the ELSE value, or null.
+ */
+pub struct Case {
+    pub expr: Option<Arc<Expr>>,
+    pub when_then: Vec<(Arc<Expr>, Arc<Expr>)>,
+    pub else_expr: Option<Arc<Expr>>,
+}
+
+impl Case {
+    pub fn new(
+        expr: Option<ExprRef>,
+        when_then: Vec<(ExprRef, ExprRef)>,
+        else_expr: Option<ExprRef>,
+    ) -> Result<Self> {
+        if when_then.is_empty() {
+            return Err(UnakiteError::Plan(
+                "CASE expects at least one WHEN branch".to_string(),
+            ));
+        }
+        Ok(Case {
+            expr: expr.map(|it| it.state),
+            when_then: when_then
+                .into_iter()
+                .map(|(when, then)| (when.state, then.state))
+                .collect(),
+            else_expr: else_expr.map(|it| it.state),
+        })
+    }
 }
 
-impl LogicalExpr for UnaryExpr {
-    fn to_field(&self, _input: Arc<LogicalPlan>) -> Field {
-        Field {
-            name: self.name.clone(),
-            data_type: DataType::Binary,
+impl LogicalExpr for Case {
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        let whens = self
+            .when_then
+            .iter()
+            .map(|(when, _)| Ok(when.to_field(input.clone())?.data_type))
+            .collect::<Result<Vec<DataType>>>()?;
+
+        match &self.expr {
+            Some(expr) => {
+                let mut compared = vec![expr.to_field(input.clone())?.data_type];
+                compared.extend(whens);
+                common_type(&compared)?;
+            }
+            None => {
+                if let Some(when) = whens
+                    .iter()
+                    .find(|it| !matches!(it, DataType::Boolean | DataType::Null))
+                {
+                    return Err(UnakiteError::Type(format!(
+                        "CASE WHEN expects a boolean condition, got {}",
+                        when
+                    )));
+                }
+            }
         }
+
+        let results = self
+            .when_then
+            .iter()
+            .map(|(_, then)| then)
+            .chain(&self.else_expr)
+            .map(|it| it.to_field(input.clone()))
+            .collect::<Result<Vec<Field>>>()?;
+        let result_types: Vec<DataType> = results.iter().map(|it| it.data_type.clone()).collect();
+
+        Ok(Field {
+            name: format!("{}", self),
+            data_type: common_type(&result_types)?,
+            nullable: self.else_expr.is_none() || results.iter().any(|it| it.nullable),
+        })
+    }
+}
+
+/* `expr IN (list)`, or `expr NOT IN (list)` when negated */
+pub struct InList {
+    pub expr: Arc<Expr>,
+    pub list: Vec<Arc<Expr>>,
+    pub negated: bool,
+}
+
+impl LogicalExpr for InList {
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        let fields = std::iter::once(&self.expr)
+            .chain(&self.list)
+            .map(|it| it.to_field(input.clone()))
+            .collect::<Result<Vec<Field>>>()?;
+        let types: Vec<DataType> = fields.iter().map(|it| it.data_type.clone()).collect();
+        common_type(&types)?;
+
+        Ok(Field {
+            name: format!("{}", self),
+            data_type: DataType::Boolean,
+            nullable: fields.iter().any(|it| it.nullable),
+        })
+    }
+}
+
+/* `expr BETWEEN low AND high` including both bounds, or `NOT BETWEEN` when negated */
+pub struct Between {
+    pub expr: Arc<Expr>,
+    pub low: Arc<Expr>,
+    pub high: Arc<Expr>,
+    pub negated: bool,
+}
+
+impl LogicalExpr for Between {
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        let fields = [&self.expr, &self.low, &self.high]
+            .iter()
+            .map(|it| it.to_field(input.clone()))
+            .collect::<Result<Vec<Field>>>()?;
+        let types: Vec<DataType> = fields.iter().map(|it| it.data_type.clone()).collect();
+        common_type(&types)?;
+
+        Ok(Field {
+            name: format!("{}", self),
+            data_type: DataType::Boolean,
+            nullable: fields.iter().any(|it| it.nullable),
+        })
     }
 }
 
+/* Conversion of a value to another type, TRY_CAST yields null where CAST would fail */
 pub struct CastExpr {
-    pub expr: Arc<dyn LogicalExpr>,
+    pub expr: Arc<Expr>,
     pub data_type: DataType,
+    pub try_cast: bool,
 }
 
 impl LogicalExpr for CastExpr {
-    fn to_field(&self, input: Arc<LogicalPlan>) -> Field {
-        Field {
-            name: self.expr.to_field(input).name,
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        let field = self.expr.to_field(input)?;
+        check_cast(&field.data_type, &self.data_type)?;
+
+        Ok(Field {
             data_type: self.data_type.clone(),
+            nullable: field.nullable || self.try_cast,
+            ..field
+        })
+    }
+}
+
+/* IS NULL, or IS NOT NULL when negated. Never null itself */
+pub struct IsNull {
+    pub expr: Arc<Expr>,
+    pub negated: bool,
+}
+
+impl LogicalExpr for IsNull {
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        self.expr.to_field(input)?;
+        Ok(Field {
+            name: format!("{}", self),
+            data_type: DataType::Boolean,
+            nullable: false,
+        })
+    }
+}
+
+/* The first of its arguments that is not null */
+pub struct Coalesce {
+    pub args: Vec<Arc<Expr>>,
+}
+
+impl Coalesce {
+    pub fn new(args: Vec<ExprRef>) -> Result<Self> {
+        if args.is_empty() {
+            return Err(UnakiteError::Plan(
+                "COALESCE expects at least one argument".to_string(),
+            ));
         }
+        Ok(Coalesce {
+            args: args.into_iter().map(|it| it.state).collect(),
+        })
+    }
+}
+
+impl LogicalExpr for Coalesce {
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        let args = self
+            .args
+            .iter()
+            .map(|it| it.to_field(input.clone()))
+            .collect::<Result<Vec<Field>>>()?;
+        let arg_types: Vec<DataType> = args.iter().map(|it| it.data_type.clone()).collect();
+
+        Ok(Field {
+            name: format!("{}", self),
+            data_type: common_type(&arg_types)?,
+            nullable: args.iter().all(|it| it.nullable),
+        })
+    }
+}
+
+/* Null where `l` equals `r`, `l` otherwise */
+pub struct NullIf {
+    pub l: Arc<Expr>,
+    pub r: Arc<Expr>,
+}
+
+impl LogicalExpr for NullIf {
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        let l = self.l.to_field(input.clone())?;
+        let r = self.r.to_field(input)?;
+        common_type(&[l.data_type.clone(), r.data_type])?;
+
+        Ok(Field {
+            name: format!("{}", self),
+            data_type: l.data_type,
+            nullable: true,
+        })
     }
 }
 
@@ -128,28 +394,94 @@ impl_fmt!(Alias, |s: &Alias, f: &mut std::fmt::Formatter<'_>| write!(
     "{} as {}",
     s.expr.state, s.alias
 ));
+impl_fmt!(Not, |s: &Not, f: &mut std::fmt::Formatter<'_>| write!(
+    f,
+    "NOT {}",
+    s.expr
+));
 impl_fmt!(
-    UnaryExpr,
-    |s: &UnaryExpr, f: &mut std::fmt::Formatter<'_>| write!(f, "{} {}", s.op, s.expr)
+    Negative,
+    |s: &Negative, f: &mut std::fmt::Formatter<'_>| write!(f, "-{}", s.expr)
+);
+impl_fmt!(Case, |s: &Case, f: &mut std::fmt::Formatter<'_>| {
+    write!(f, "CASE")?;
+    if let Some(expr) = &s.expr {
+        write!(f, " {}", expr)?;
+    }
+    for (when, then) in &s.when_then {
+        write!(f, " WHEN {} THEN {}", when, then)?;
+    }
+    if let Some(else_expr) = &s.else_expr {
+        write!(f, " ELSE {}", else_expr)?;
+    }
+    write!(f, " END")
+});
+impl_fmt!(InList, |s: &InList, f: &mut std::fmt::Formatter<'_>| {
+    let list = s
+        .list
+        .iter()
+        .map(|it| format!("{}", it))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let not = if s.negated { "NOT " } else { "" };
+    write!(f, "{} {}IN ({})", s.expr, not, list)
+});
+impl_fmt!(
+    Between,
+    |s: &Between, f: &mut std::fmt::Formatter<'_>| write!(
+        f,
+        "{} {}BETWEEN {} AND {}",
+        s.expr,
+        if s.negated { "NOT " } else { "" },
+        s.low,
+        s.high
+    )
 );
 impl_fmt!(
     CastExpr,
     |s: &CastExpr, f: &mut std::fmt::Formatter<'_>| write!(
         f,
-        "CAST(#{} AS {})",
-        s.expr, s.data_type
+        "{}(#{} AS {})",
+        if s.try_cast { "TRY_CAST" } else { "CAST" },
+        s.expr,
+        s.data_type
+    )
+);
+
+impl_fmt!(
+    IsNull,
+    |s: &IsNull, f: &mut std::fmt::Formatter<'_>| write!(
+        f,
+        "{} IS {}NULL",
+        s.expr,
+        if s.negated { "NOT " } else { "" }
     )
 );
 
+impl_fmt!(Coalesce, |s: &Coalesce, f: &mut std::fmt::Formatter<'_>| {
+    let args_str = s
+        .args
+        .iter()
+        .map(|arg| format!("{}", arg))
+        .collect::<Vec<_>>()
+        .join(", ");
+    write!(f, "COALESCE({})", args_str)
+});
+
+impl_fmt!(
+    NullIf,
+    |s: &NullIf, f: &mut std::fmt::Formatter<'_>| write!(f, "NULLIF({}, {})", s.l, s.r)
+);
+
 impl_fmt!(
     ScalarFunction,
     |s: &ScalarFunction, f: &mut std::fmt::Formatter<'_>| {
         let args_str = s
-            ._args
+            .args
             .iter()
             .map(|arg| format!("{}", arg))
             .collect::<Vec<_>>()
             .join(", ");
-        write!(f, "{}({})", s.name, args_str)
+        write!(f, "{}({})", s.fun.name(), args_str)
     }
 );
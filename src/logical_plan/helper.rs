@@ -1,10 +1,17 @@
 use std::sync::Arc;
 
-use crate::logical_plan::{
-    AggregateExpr,
-    expr::{Expr, ExprRef, LiteralExpression, NumericExpression},
-    expression::Column,
-    macro_utils::{AggregateCount, AggregateMax, AggregateMin},
+use crate::{
+    error::{Result, UnakiteError},
+    functions::BuiltinFunction,
+    logical_plan::{
+        AggregateExpr,
+        expr::{Expr, ExprRef, LiteralExpression, NumericExpression},
+        expression::{Case, Coalesce, Column, NullIf, ScalarFunction},
+        macro_utils::{
+            AggregateAvg, AggregateCount, AggregateCountDistinct, AggregateMax, AggregateMin,
+            AggregateSum,
+        },
+    },
 };
 
 /*Conveniece method for Aggregates */
@@ -18,10 +25,22 @@ pub fn max(name: &str) -> AggregateExpr {
     AggregateExpr::Max(AggregateMax::new(column(name)))
 }
 
+pub fn sum(name: &str) -> AggregateExpr {
+    AggregateExpr::Sum(AggregateSum::new(column(name)))
+}
+
+pub fn avg(name: &str) -> AggregateExpr {
+    AggregateExpr::Avg(AggregateAvg::new(column(name)))
+}
+
 pub fn count(name: &str) -> AggregateExpr {
     AggregateExpr::Count(AggregateCount::new(column(name)))
 }
 
+pub fn count_distinct(name: &str) -> AggregateExpr {
+    AggregateExpr::CountDistinct(AggregateCountDistinct::new(column(name)))
+}
+
 // Convenience method for creating a column Expr Enum struct
 pub fn column(name: &str) -> ExprRef {
     ExprRef {
@@ -31,11 +50,86 @@ pub fn column(name: &str) -> ExprRef {
     }
 }
 
+/*
+ * Call a built-in scalar function by name, e.g. `call_function("power", vec![column("x"), literal_i64(2)])`.
+ * Unknown names and the wrong number of arguments are plan errors.
+ */
+pub fn call_function(name: &str, args: Vec<ExprRef>) -> Result<ExprRef> {
+    let fun = BuiltinFunction::from_name(name)
+        .ok_or_else(|| UnakiteError::Plan(format!("Unknown function {}", name)))?;
+    scalar_function(fun, args)
+}
+
+pub fn scalar_function(fun: BuiltinFunction, args: Vec<ExprRef>) -> Result<ExprRef> {
+    Ok(ExprRef::new(Arc::new(Expr::ScalarFunctionExpr(
+        ScalarFunction::new(fun, args)?,
+    ))))
+}
+
+/*Convenience methods for the single argument functions */
+macro_rules! impl_unary_function_helper {
+    ($($fn_name:ident => $fun:ident),* $(,)?) => {
+        $(
+            pub fn $fn_name(expr: ExprRef) -> ExprRef {
+                ExprRef::new(Arc::new(Expr::ScalarFunctionExpr(ScalarFunction {
+                    fun: BuiltinFunction::$fun,
+                    args: vec![expr.state],
+                })))
+            }
+        )*
+    };
+}
+
+impl_unary_function_helper!(
+    abs => Abs,
+    ceil => Ceil,
+    floor => Floor,
+    round => Round,
+    sqrt => Sqrt,
+    exp => Exp,
+    ln => Ln,
+    log10 => Log10,
+);
+
+pub fn power(base: ExprRef, exponent: ExprRef) -> ExprRef {
+    ExprRef::new(Arc::new(Expr::ScalarFunctionExpr(ScalarFunction {
+        fun: BuiltinFunction::Power,
+        args: vec![base.state, exponent.state],
+    })))
+}
+
+/* COALESCE of the given expressions, at least one is required */
+pub fn coalesce(args: Vec<ExprRef>) -> Result<ExprRef> {
+    let coalesce = Coalesce::new(args)?;
+    Ok(ExprRef::new(Arc::new(Expr::CoalesceExpr(coalesce))))
+}
+
+/*
+ * CASE expression, with an operand compared to each WHEN value or with boolean WHEN conditions
+ * when there is none
+ */
+pub fn case(
+    expr: Option<ExprRef>,
+    when_then: Vec<(ExprRef, ExprRef)>,
+    else_expr: Option<ExprRef>,
+) -> Result<ExprRef> {
+    let case = Case::new(expr, when_then, else_expr)?;
+    Ok(ExprRef::new(Arc::new(Expr::CaseExpr(case))))
+}
+
+/* NULLIF(l, r), null where `l` equals `r` */
+pub fn nullif(l: ExprRef, r: ExprRef) -> ExprRef {
+    ExprRef::new(Arc::new(Expr::NullIfExpr(NullIf {
+        l: l.state,
+        r: r.state,
+    })))
+}
+
 // Convenience method for matching a literal numeric expression to a usize
-pub fn numeric_lit_expr_to_usize(state: &Expr) -> usize {
+pub fn numeric_lit_expr_to_usize(state: &Expr) -> Result<usize> {
     if let Expr::LiteralExpr(lit) = state {
         match lit {
-            LiteralExpression::Numeric(numeric_expression) => match numeric_expression {
+            LiteralExpression::Numeric(numeric_expression) => Ok(match numeric_expression {
                 NumericExpression::Integer64Expr(value) => value.value as usize,
                 NumericExpression::Integer32Expr(value) => value.value as usize,
                 NumericExpression::Integer16Expr(value) => value.value as usize,
@@ -46,10 +140,14 @@ pub fn numeric_lit_expr_to_usize(state: &Expr) -> usize {
                 NumericExpression::UInteger32Expr(value) => value.value as usize,
                 NumericExpression::UInteger16Expr(value) => value.value as usize,
                 NumericExpression::UInteger8Expr(value) => value.value as usize,
-            },
-            _ => panic!("Limit expression must be a literal numeric expression"),
+            }),
+            _ => Err(UnakiteError::Plan(
+                "Limit expression must be a literal numeric expression".to_string(),
+            )),
         }
     } else {
-        panic!("Limit expression must be a literal expression")
+        Err(UnakiteError::Plan(
+            "Limit expression must be a literal expression".to_string(),
+        ))
     }
 }
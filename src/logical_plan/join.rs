@@ -2,14 +2,22 @@ use std::sync::Arc;
 
 use crate::{
     datatypes::schema::{Field, Schema},
+    error::Result,
     logical_plan::LogicalPlan,
 };
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum JoinType {
     Inner,
     Left,
     Right,
+    Full,
+    /** Left rows with at least one match, like `EXISTS` */
+    LeftSemi,
+    /** Left rows without any match, like `NOT EXISTS` */
+    LeftAnti,
+    /** Every pairing of left and right rows. Takes no join keys */
+    Cross,
 }
 
 pub struct Join {
@@ -28,51 +36,63 @@ impl Join {
         let children: Vec<Arc<LogicalPlan>> = vec![l, r];
         children
     }
-    pub fn schema(&self) -> Arc<Schema> {
-        let duplicate_keys: Vec<String> = self
-            .on
+    /**
+     * Left columns followed by right columns, matching the layout produced by the hash join.
+     * Right columns whose names clash with a left column are qualified with the name of the
+     * right relation, e.g. `regions.region`. Columns of a side that can be null padded are
+     * nullable. Semi and anti joins only produce the left columns.
+     */
+    pub fn schema(&self) -> Result<Arc<Schema>> {
+        let (left_nullable, right_nullable) = match self.join_type {
+            JoinType::Inner | JoinType::Cross => (false, false),
+            JoinType::Left => (false, true),
+            JoinType::Right => (true, false),
+            JoinType::Full => (true, true),
+            JoinType::LeftSemi | JoinType::LeftAnti => {
+                return self.left.schema();
+            }
+        };
+
+        let mut fields: Vec<Field> = self
+            .left
+            .schema()?
+            .fields
             .iter()
-            .filter(|&it| it.0 == it.1)
-            .map(|it| it.0.clone())
+            .map(|it| nullable_if(it, left_nullable))
             .collect();
 
-        let fields = match self.join_type {
-            JoinType::Inner | JoinType::Right => {
-                let mut left_fields = self.left.schema().fields.clone();
-                let mut right_fields: Vec<Field> = self
-                    .right
-                    .schema()
-                    .fields
-                    .iter()
-                    .filter(|it| duplicate_keys.contains(&it.name))
-                    .cloned()
-                    .collect();
-
-                left_fields.append(&mut right_fields);
-                left_fields
+        let qualifier = relation_name(&self.right).unwrap_or_else(|| "right".to_string());
+        for field in self.right.schema()?.fields.iter() {
+            let mut field = nullable_if(field, right_nullable);
+            if fields.iter().any(|it| it.name == field.name) {
+                field.name = format!("{}.{}", qualifier, field.name);
             }
-            JoinType::Left => {
-                let mut left_fields: Vec<Field> = self
-                    .left
-                    .schema()
-                    .fields
-                    .iter()
-                    .filter(|it| duplicate_keys.contains(&it.name))
-                    .cloned()
-                    .collect();
-                let mut right_fields = self.right.schema().fields.clone();
+            fields.push(field);
+        }
 
-                right_fields.append(&mut left_fields);
-                right_fields
-            }
-        };
+        Ok(Arc::new(Schema { fields }))
+    }
+}
+
+fn nullable_if(field: &Field, nullable: bool) -> Field {
+    field.clone().with_nullable(field.nullable || nullable)
+}
 
-        Arc::new(Schema { fields })
+/** Name of the relation a plan reads from, if it reads from a single scan */
+fn relation_name(plan: &LogicalPlan) -> Option<String> {
+    match plan {
+        LogicalPlan::ScanPlan(scan) => Some(scan.path.clone()),
+        LogicalPlan::JoinPlan(_) => None,
+        other => other.children().first().and_then(|it| relation_name(it)),
     }
 }
 
 impl std::fmt::Display for Join {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f,"Join: Left = {} Right= {}\n  On: {:?}", self.left,self.right,self.on)
+        write!(
+            f,
+            "Join: {:?} Left = {} Right= {}\n  On: {:?}",
+            self.join_type, self.left, self.right, self.on
+        )
     }
 }
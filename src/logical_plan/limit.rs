@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::{datatypes::schema::Schema, logical_plan::LogicalPlan};
+use crate::{datatypes::schema::Schema, error::Result, logical_plan::LogicalPlan};
 
 pub struct Limit {
     pub input: Arc<LogicalPlan>,
@@ -12,7 +12,7 @@ impl Limit {
         vec![self.input.clone()]
     }
 
-    pub fn schema(&self) -> Arc<Schema> {
+    pub fn schema(&self) -> Result<Arc<Schema>> {
         self.input.schema()
     }
 }
@@ -1,5 +1,13 @@
 macro_rules! impl_literal_helper {
     ($func_name:ident, &str, $variant:ident, $struct:ident) => {
+        impl From<$struct> for crate::logical_plan::expr::Expr {
+            fn from(value: $struct) -> Self {
+                crate::logical_plan::expr::Expr::LiteralExpr(
+                    crate::logical_plan::expr::LiteralExpression::$variant(value),
+                )
+            }
+        }
+
         pub fn $func_name(value: &str) -> crate::logical_plan::expr::ExprRef {
             crate::logical_plan::expr::ExprRef {
                 state: crate::logical_plan::Arc::new(crate::logical_plan::expr::Expr::LiteralExpr(
@@ -12,6 +20,16 @@ macro_rules! impl_literal_helper {
     };
 
     ($func_name:ident, $ty:ty, $variant:ident, $struct:ident) => {
+        impl From<$struct> for crate::logical_plan::expr::Expr {
+            fn from(value: $struct) -> Self {
+                crate::logical_plan::expr::Expr::LiteralExpr(
+                    crate::logical_plan::expr::LiteralExpression::Numeric(
+                        crate::logical_plan::expr::NumericExpression::$variant(value),
+                    ),
+                )
+            }
+        }
+
         pub fn $func_name(value: $ty) -> crate::logical_plan::expr::ExprRef {
             crate::logical_plan::expr::ExprRef {
                 state: crate::logical_plan::Arc::new(crate::logical_plan::expr::Expr::LiteralExpr(
@@ -34,11 +52,12 @@ macro_rules! impl_literal {
             fn to_field(
                 &self,
                 _input: crate::logical_plan::Arc<crate::logical_plan::LogicalPlan>,
-            ) -> crate::datatypes::schema::Field {
-                crate::datatypes::schema::Field {
+            ) -> crate::error::Result<crate::datatypes::schema::Field> {
+                Ok(crate::datatypes::schema::Field {
                     name: format!("{}", self),
                     data_type: arrow::datatypes::DataType::$dt,
-                }
+                    nullable: false,
+                })
             }
         }
 
@@ -64,8 +83,8 @@ macro_rules! impl_math_expr_op {
             fn $method(self, rhs: Self) -> Self::Output {
                 return crate::logical_plan::Arc::new(
                     crate::logical_plan::expr::MathExpression::$variant($constructor::new(
-                        crate::logical_plan::Arc::new(self),
-                        crate::logical_plan::Arc::new(rhs),
+                        crate::logical_plan::Arc::new(crate::logical_plan::expr::Expr::from(self)),
+                        crate::logical_plan::Arc::new(crate::logical_plan::expr::Expr::from(rhs)),
                     )),
                 );
             }
@@ -125,19 +144,61 @@ impl_literal!(LiteralString, String, Utf8);
 impl_math_expr!(LiteralString);
 impl_literal_helper!(literal_string, &str, StringExpr, LiteralString);
 
+/* Logical expression representing the NULL literal, it takes the type of what it meets */
+pub struct LiteralNull;
+
+impl super::LogicalExpr for LiteralNull {
+    fn to_field(
+        &self,
+        _input: crate::logical_plan::Arc<crate::logical_plan::LogicalPlan>,
+    ) -> crate::error::Result<crate::datatypes::schema::Field> {
+        Ok(crate::datatypes::schema::Field {
+            name: format!("{}", self),
+            data_type: arrow::datatypes::DataType::Null,
+            nullable: true,
+        })
+    }
+}
+
+impl std::fmt::Display for LiteralNull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NULL")
+    }
+}
+
+impl std::fmt::Debug for LiteralNull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NULL")
+    }
+}
+
+impl From<LiteralNull> for crate::logical_plan::expr::Expr {
+    fn from(value: LiteralNull) -> Self {
+        crate::logical_plan::expr::Expr::LiteralExpr(
+            crate::logical_plan::expr::LiteralExpression::NullExpr(value),
+        )
+    }
+}
+
+pub fn literal_null() -> crate::logical_plan::expr::ExprRef {
+    crate::logical_plan::expr::ExprRef {
+        state: crate::logical_plan::Arc::new(LiteralNull.into()),
+    }
+}
+
 macro_rules! impl_binary_expr {
     ($name:ident, $op:expr) => {
         /// Documentation
         pub struct $name {
             pub name: String,
             op: String,
-            l: crate::logical_plan::Arc<dyn crate::logical_plan::LogicalExpr>,
-            r: crate::logical_plan::Arc<dyn crate::logical_plan::LogicalExpr>,
+            pub l: crate::logical_plan::Arc<crate::logical_plan::expr::Expr>,
+            pub r: crate::logical_plan::Arc<crate::logical_plan::expr::Expr>,
         }
         impl $name {
             pub fn new(
-                l: crate::logical_plan::Arc<dyn crate::logical_plan::LogicalExpr>,
-                r: crate::logical_plan::Arc<dyn crate::logical_plan::LogicalExpr>,
+                l: crate::logical_plan::Arc<crate::logical_plan::expr::Expr>,
+                r: crate::logical_plan::Arc<crate::logical_plan::expr::Expr>,
             ) -> $name {
                 $name {
                     name: $op.to_lowercase(),
@@ -148,18 +209,6 @@ macro_rules! impl_binary_expr {
             }
         }
 
-        impl crate::logical_plan::LogicalExpr for $name {
-            fn to_field(
-                &self,
-                _input: crate::logical_plan::Arc<crate::logical_plan::LogicalPlan>,
-            ) -> crate::datatypes::schema::Field {
-                crate::datatypes::schema::Field {
-                    name: format!("{}", self),
-                    data_type: arrow::datatypes::DataType::Boolean,
-                }
-            }
-        }
-
         impl std::fmt::Display for $name {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 write!(f, "{} {:?} {}", self.l, self.op, self.r)
@@ -174,6 +223,63 @@ macro_rules! impl_binary_expr {
     };
 }
 
+/*
+ * Comparisons and logical operators produce a boolean, null when an operand is null. The operands
+ * have to meet in a common type, numbers of different types are compared in their promoted type.
+ */
+macro_rules! impl_boolean_to_field {
+    ($($name:ident),* $(,)?) => {
+        impl_boolean_to_field!(@nullable true, $($name),*);
+    };
+
+    (@nullable $nullable:expr, $($name:ident),* $(,)?) => {
+        $(
+            impl crate::logical_plan::LogicalExpr for $name {
+                fn to_field(
+                    &self,
+                    input: crate::logical_plan::Arc<crate::logical_plan::LogicalPlan>,
+                ) -> crate::error::Result<crate::datatypes::schema::Field> {
+                    let l = self.l.to_field(input.clone())?;
+                    let r = self.r.to_field(input)?;
+                    crate::datatypes::coercion::comparison_type(&[l.data_type, r.data_type])?;
+
+                    Ok(crate::datatypes::schema::Field {
+                        name: format!("{}", self),
+                        data_type: arrow::datatypes::DataType::Boolean,
+                        nullable: $nullable,
+                    })
+                }
+            }
+        )*
+    };
+}
+
+/* Arithmetic produces the promoted type of its operands, null when either operand is null */
+macro_rules! impl_math_to_field {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl crate::logical_plan::LogicalExpr for $name {
+                fn to_field(
+                    &self,
+                    input: crate::logical_plan::Arc<crate::logical_plan::LogicalPlan>,
+                ) -> crate::error::Result<crate::datatypes::schema::Field> {
+                    let l = self.l.to_field(input.clone())?;
+                    let r = self.r.to_field(input)?;
+
+                    Ok(crate::datatypes::schema::Field {
+                        name: format!("{}", self),
+                        data_type: crate::datatypes::coercion::numeric_promotion(
+                            &l.data_type,
+                            &r.data_type,
+                        )?,
+                        nullable: l.nullable || r.nullable,
+                    })
+                }
+            }
+        )*
+    };
+}
+
 macro_rules! impl_comparison_expr_helper {
     ($fn_name:ident, $variant:ident, $struct:ident) => {
         pub fn $fn_name(
@@ -219,6 +325,18 @@ impl_comparison_expr_helper!(lt, LtExpr, Lt);
 impl_binary_expr!(Lteq, "<=".to_string());
 impl_comparison_expr_helper!(lteq, LtEqExpr, Lteq);
 
+/* Logical expression representing `IS DISTINCT FROM`, an inequality that treats NULL as a value */
+impl_binary_expr!(IsDistinctFrom, "IS DISTINCT FROM".to_string());
+impl_comparison_expr_helper!(is_distinct_from, IsDistinctFromExpr, IsDistinctFrom);
+
+/* Logical expression representing `IS NOT DISTINCT FROM`, the null safe equality */
+impl_binary_expr!(IsNotDistinctFrom, "IS NOT DISTINCT FROM".to_string());
+impl_comparison_expr_helper!(
+    is_not_distinct_from,
+    IsNotDistinctFromExpr,
+    IsNotDistinctFrom
+);
+
 /* Logical expression representing binary math exspression*/
 impl_binary_expr!(MathAdd, "+".to_string());
 impl_binary_expr!(MathSubtract, "-".to_string());
@@ -226,6 +344,10 @@ impl_binary_expr!(MathMultiply, "*".to_string());
 impl_binary_expr!(MathDivide, "/".to_string());
 impl_binary_expr!(MathMod, "%".to_string());
 
+impl_boolean_to_field!(And, Or, EqOp, Neq, Gt, Gteq, Lt, Lteq);
+impl_boolean_to_field!(@nullable false, IsDistinctFrom, IsNotDistinctFrom);
+impl_math_to_field!(MathAdd, MathSubtract, MathMultiply, MathDivide, MathMod);
+
 // Helper macro to implement both Display and Debug using the same formatting logic.
 // Helper macro: handles both one-field and two-field display formatting
 macro_rules! impl_fmt {
@@ -258,24 +380,44 @@ macro_rules! impl_fmt {
     };
 }
 
+/** SUM and AVG only add up numbers, NULL stands in for any of them */
+fn check_numeric_input(
+    name: &str,
+    field: &crate::datatypes::schema::Field,
+) -> crate::error::Result<()> {
+    let data_type = &field.data_type;
+    if crate::datatypes::coercion::is_arithmetic(data_type)
+        || data_type == &arrow::datatypes::DataType::Null
+    {
+        return Ok(());
+    }
+    Err(crate::error::UnakiteError::Type(format!(
+        "{} expects a numeric argument, got {} of type {}",
+        name, field.name, data_type
+    )))
+}
+
 // Main macro to generate aggregate logical expressions.
 // Handles:
+// Output fields are named after the aggregate and its input, e.g. SUM(quantity)
 // 1. COUNT DISTINCT — special logic and fixed return type
 // 2. COUNT — fixed return type
-// 3. Generic aggregates — use inner expression's type
+// 3. AVG — always a double, of numeric input only
+// 4. Generic aggregates — use inner expression's type, SUM also takes numeric input only
 macro_rules! impl_aggregate_expr {
-    // COUNT DISTINCT is special: uses Boxed expr and always returns Int32
+    // COUNT DISTINCT is special: always returns Int32
     (AggregateCountDistinct, $op_name:expr) => {
+        #[derive(Clone)]
         pub struct AggregateCountDistinct {
             _name: String,
-            expr: Box<dyn crate::logical_plan::LogicalExpr>,
+            pub expr: crate::logical_plan::expr::ExprRef,
         }
 
         impl AggregateCountDistinct {
-            pub fn new(input: Box<dyn crate::logical_plan::LogicalExpr>) -> Self {
+            pub fn new(expr: crate::logical_plan::expr::ExprRef) -> Self {
                 Self {
                     _name: $op_name,
-                    expr: input,
+                    expr,
                 }
             }
         }
@@ -283,24 +425,26 @@ macro_rules! impl_aggregate_expr {
         impl crate::logical_plan::LogicalExpr for AggregateCountDistinct {
             fn to_field(
                 &self,
-                _: crate::logical_plan::Arc<crate::logical_plan::LogicalPlan>,
-            ) -> crate::datatypes::schema::Field {
-                crate::datatypes::schema::Field {
-                    name: "COUNT DISTINCT".into(),
+                input: crate::logical_plan::Arc<crate::logical_plan::LogicalPlan>,
+            ) -> crate::error::Result<crate::datatypes::schema::Field> {
+                Ok(crate::datatypes::schema::Field {
+                    name: format!("COUNT(DISTINCT {})", self.expr.to_field(input)?.name),
                     data_type: arrow::datatypes::DataType::Int32,
-                }
+                    nullable: false,
+                })
             }
         }
 
         // Reuse formatting logic
-        impl_fmt!(AggregateCountDistinct, "COUNT DISTINCT({})", expr);
+        impl_fmt!(AggregateCountDistinct, "COUNT DISTINCT({:?})", expr);
     };
 
     // COUNT is also special: returns Int32 but uses Arc expr
     (AggregateCount, $op_name:expr) => {
+        #[derive(Clone)]
         pub struct AggregateCount {
             _name: String,
-            expr: crate::logical_plan::expr::ExprRef,
+            pub expr: crate::logical_plan::expr::ExprRef,
         }
 
         impl AggregateCount {
@@ -315,23 +459,63 @@ macro_rules! impl_aggregate_expr {
         impl crate::logical_plan::LogicalExpr for AggregateCount {
             fn to_field(
                 &self,
-                _: crate::logical_plan::Arc<crate::logical_plan::LogicalPlan>,
-            ) -> crate::datatypes::schema::Field {
-                crate::datatypes::schema::Field {
-                    name: "COUNT".into(),
+                input: crate::logical_plan::Arc<crate::logical_plan::LogicalPlan>,
+            ) -> crate::error::Result<crate::datatypes::schema::Field> {
+                Ok(crate::datatypes::schema::Field {
+                    name: format!("COUNT({})", self.expr.to_field(input)?.name),
                     data_type: arrow::datatypes::DataType::Int32,
-                }
+                    nullable: false,
+                })
             }
         }
 
         impl_fmt!(AggregateCount, "{:?}", expr);
     };
 
+    // AVG divides by the row count, so the result is a double whatever the input type
+    (AggregateAvg, $op_name:expr) => {
+        #[derive(Clone)]
+        pub struct AggregateAvg {
+            name: String,
+            pub expr: crate::logical_plan::expr::ExprRef,
+        }
+
+        impl AggregateAvg {
+            pub fn new(expr: crate::logical_plan::expr::ExprRef) -> Self {
+                Self {
+                    name: $op_name,
+                    expr,
+                }
+            }
+        }
+
+        impl crate::logical_plan::LogicalExpr for AggregateAvg {
+            fn to_field(
+                &self,
+                input: crate::logical_plan::Arc<crate::logical_plan::LogicalPlan>,
+            ) -> crate::error::Result<crate::datatypes::schema::Field> {
+                let field = self.expr.to_field(input)?;
+                check_numeric_input(&self.name, &field)?;
+                Ok(crate::datatypes::schema::Field {
+                    name: format!("{}({})", self.name, field.name),
+                    data_type: arrow::datatypes::DataType::Float64,
+                    nullable: true,
+                })
+            }
+        }
+
+        impl_fmt!(AggregateAvg, "{}({})", name, expr);
+    };
+
     // Generic aggregate case: return same data type as inner expression
     ($name:ident, $op_name:expr) => {
+        impl_aggregate_expr!($name, $op_name, numeric = false);
+    };
+    ($name:ident, $op_name:expr, numeric = $numeric:expr) => {
+        #[derive(Clone)]
         pub struct $name {
             name: String,
-            expr: crate::logical_plan::expr::ExprRef,
+            pub expr: crate::logical_plan::expr::ExprRef,
         }
 
         impl $name {
@@ -347,10 +531,15 @@ macro_rules! impl_aggregate_expr {
             fn to_field(
                 &self,
                 input: crate::logical_plan::Arc<crate::logical_plan::LogicalPlan>,
-            ) -> crate::datatypes::schema::Field {
-                let mut field = self.expr.to_field(input);
-                field.name = self.name.clone();
-                field
+            ) -> crate::error::Result<crate::datatypes::schema::Field> {
+                let mut field = self.expr.to_field(input)?;
+                if $numeric {
+                    check_numeric_input(&self.name, &field)?;
+                }
+                field.name = format!("{}({})", self.name, field.name);
+                // No input rows aggregate to null
+                field.nullable = true;
+                Ok(field)
             }
         }
 
@@ -360,15 +549,15 @@ macro_rules! impl_aggregate_expr {
 }
 
 /* Logical expression representing the SUM aggregate expression. */
-impl_aggregate_expr!(AggregateSum, String::from("Sum"));
+impl_aggregate_expr!(AggregateSum, String::from("SUM"), numeric = true);
 
 /* Logical expression representing the MIN aggregate expression. */
-impl_aggregate_expr!(AggregateMin, String::from("Min"));
+impl_aggregate_expr!(AggregateMin, String::from("MIN"));
 /* Logical expression representing the MAX aggregate expression. */
-impl_aggregate_expr!(AggregateMax, String::from("Max"));
+impl_aggregate_expr!(AggregateMax, String::from("MAX"));
 
 /* Logical expression representing the AVG aggregate expression. */
-impl_aggregate_expr!(AggregateAvg, String::from("Avg"));
+impl_aggregate_expr!(AggregateAvg, String::from("AVG"));
 
 /* Logical expression representing the COUNT aggregate expression. */
 impl_aggregate_expr!(AggregateCount, String::from("Count"));
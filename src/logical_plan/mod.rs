@@ -1,4 +1,5 @@
 pub mod aggregate;
+pub mod copy;
 pub mod data_frame;
 pub mod expr;
 pub mod expression;
@@ -8,6 +9,7 @@ pub mod macro_utils;
 pub mod projection;
 pub mod scan;
 pub mod selection;
+pub mod sort;
 pub mod test;
 pub mod helper;
 use std::{
@@ -17,8 +19,11 @@ use std::{
 
 use crate::{
     datatypes::schema::{Field, Schema},
+    error::Result,
     logical_plan::{
         aggregate::Aggregate,
+        copy::Copy,
+        expr::ExprRef,
         join::Join,
         limit::Limit,
         macro_utils::{
@@ -28,14 +33,14 @@ use crate::{
         projection::Projection,
         scan::Scan,
         selection::Selection,
+        sort::Sort,
     },
 };
 
 /**
  * An enum representing all aggregate variants
  */
-
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum AggregateExpr {
     Sum(AggregateSum),
     Min(AggregateMin),
@@ -45,11 +50,36 @@ pub enum AggregateExpr {
     CountDistinct(AggregateCountDistinct),
 }
 
+impl AggregateExpr {
+    /** The expression whose values are aggregated */
+    pub fn expr(&self) -> &ExprRef {
+        match self {
+            AggregateExpr::Sum(sum) => &sum.expr,
+            AggregateExpr::Min(min) => &min.expr,
+            AggregateExpr::Max(max) => &max.expr,
+            AggregateExpr::Avg(avg) => &avg.expr,
+            AggregateExpr::Count(count) => &count.expr,
+            AggregateExpr::CountDistinct(count_distinct) => &count_distinct.expr,
+        }
+    }
+
+    /** Return meta-data about the value produced by this aggregate over the given input. */
+    pub fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field> {
+        match self {
+            AggregateExpr::Sum(sum) => sum.to_field(input),
+            AggregateExpr::Min(min) => min.to_field(input),
+            AggregateExpr::Max(max) => max.to_field(input),
+            AggregateExpr::Avg(avg) => avg.to_field(input),
+            AggregateExpr::Count(count) => count.to_field(input),
+            AggregateExpr::CountDistinct(count_distinct) => count_distinct.to_field(input),
+        }
+    }
+}
+
 /**
  * A logical plan represents a data transformation or action that returns a relation (a set of
  * tuples).
  */
-
 pub enum LogicalPlan {
     JoinPlan(Join),
     LimitPlan(Limit),
@@ -57,6 +87,8 @@ pub enum LogicalPlan {
     ScanPlan(Scan),
     SelectionPlan(Selection),
     AggregatePlan(Aggregate),
+    SortPlan(Sort),
+    CopyPlan(Copy),
 }
 
 /// This enum likely makes all the dyn traits null and void
@@ -66,7 +98,7 @@ pub enum LogicalPlan {
 /// Replace schema and children function with implementation macros
 impl LogicalPlan {
     /** Returns the schema of the data that will be produced by this logical plan. */
-    fn schema(&self) -> Arc<Schema> {
+    pub fn schema(&self) -> Result<Arc<Schema>> {
         match self {
             LogicalPlan::JoinPlan(join) => join.schema(),
             LogicalPlan::LimitPlan(limit) => limit.schema(),
@@ -74,6 +106,8 @@ impl LogicalPlan {
             LogicalPlan::ScanPlan(scan) => scan.schema(),
             LogicalPlan::SelectionPlan(selection) => selection.schema(),
             LogicalPlan::AggregatePlan(aggregate) => aggregate.schema(),
+            LogicalPlan::SortPlan(sort) => sort.schema(),
+            LogicalPlan::CopyPlan(copy) => copy.schema(),
         }
     }
 
@@ -81,8 +115,7 @@ impl LogicalPlan {
      * Returns the children (inputs) of this logical plan. This method is used to enable use of the
      * visitor pattern to walk a query tree.
      */
-
-    fn children(&self) -> Vec<Arc<LogicalPlan>> {
+    pub fn children(&self) -> Vec<Arc<LogicalPlan>> {
         match self {
             LogicalPlan::JoinPlan(join) => join.children(),
             LogicalPlan::LimitPlan(limit) => limit.children(),
@@ -90,6 +123,8 @@ impl LogicalPlan {
             LogicalPlan::ScanPlan(scan) => scan.children(),
             LogicalPlan::SelectionPlan(selection) => selection.children(),
             LogicalPlan::AggregatePlan(aggregate) => aggregate.children(),
+            LogicalPlan::SortPlan(sort) => sort.children(),
+            LogicalPlan::CopyPlan(copy) => copy.children(),
         }
     }
 }
@@ -98,22 +133,28 @@ impl std::fmt::Display for LogicalPlan {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             LogicalPlan::JoinPlan(join) => {
-                write!(f, "{}", join.to_string())
+                write!(f, "{}", join)
             }
             LogicalPlan::LimitPlan(limit) => {
-                write!(f, "{}", limit.to_string())
+                write!(f, "{}", limit)
             }
             LogicalPlan::ProjectionPlan(projection) => {
-                write!(f, "{}", projection.to_string())
+                write!(f, "{}", projection)
             }
             LogicalPlan::ScanPlan(scan) => {
-                write!(f, "{}", scan.to_string())
+                write!(f, "{}", scan)
             }
             LogicalPlan::SelectionPlan(selection) => {
-                write!(f, "{}", selection.to_string())
+                write!(f, "{}", selection)
             }
             LogicalPlan::AggregatePlan(aggregate) => {
-                write!(f, "{}", aggregate.to_string())
+                write!(f, "{}", aggregate)
+            }
+            LogicalPlan::SortPlan(sort) => {
+                write!(f, "{}", sort)
+            }
+            LogicalPlan::CopyPlan(copy) => {
+                write!(f, "{}", copy)
             }
         }
     }
@@ -128,7 +169,7 @@ pub trait LogicalExpr: Display + Debug {
      * Return meta-data about the value that will be produced by this expression when evaluated
      * against a particular input.
      */
-    fn to_field(&self, input: Arc<LogicalPlan>) -> Field;
+    fn to_field(&self, input: Arc<LogicalPlan>) -> Result<Field>;
 }
 
 pub fn format_plan(plan: &LogicalPlan) -> String {
@@ -145,7 +186,7 @@ pub fn pretty_format(plan: &LogicalPlan, indent: usize) -> String {
     b.push('\n');
 
     for child in plan.children() {
-        b.push_str(&pretty_format(&*child, indent + 1));
+        b.push_str(&pretty_format(&child, indent + 1));
     }
 
     b
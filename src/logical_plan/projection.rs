@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use crate::{
     datatypes::schema::{Field, Schema},
+    error::Result,
     logical_plan::{LogicalExpr, LogicalPlan, expr::ExprRef},
 };
 
@@ -15,14 +16,14 @@ impl Projection {
         vec![self.input.clone()]
     }
 
-    pub fn schema(&self) -> Arc<Schema> {
+    pub fn schema(&self) -> Result<Arc<Schema>> {
         let fields: Vec<Field> = self
             .expr
             .iter()
             .map(|it| it.state.to_field(self.input.clone()))
-            .collect();
+            .collect::<Result<_>>()?;
 
-        Arc::new(Schema { fields })
+        Ok(Arc::new(Schema { fields }))
     }
 }
 
@@ -1,39 +1,45 @@
-use std::{ sync::Arc};
+use std::sync::Arc;
 
 use crate::{
     datasource::{DataSource, DataSourceTrait},
     datatypes::schema::Schema,
-    logical_plan::LogicalPlan,
+    error::Result,
+    logical_plan::{LogicalPlan, expr::ExprRef},
 };
 
 pub struct Scan {
-    data_source: DataSource,
-    path: String,
-    projection: Arc<Vec<String>>,
+    pub data_source: Arc<DataSource>,
+    pub path: String,
+    pub projection: Arc<Vec<String>>,
+    /**
+     * Predicates pushed down by the optimizer. Sources may use them to skip data, the rows they
+     * return are still filtered by the selection above the scan.
+     */
+    pub filters: Vec<ExprRef>,
 }
 
 impl Scan {
     pub fn new(path: String, data_source: DataSource, projection: Arc<Vec<String>>) -> Scan {
         Scan {
-            data_source,
+            data_source: Arc::new(data_source),
             projection,
             path,
+            filters: vec![],
         }
     }
     pub fn children(&self) -> Vec<Arc<LogicalPlan>> {
         vec![]
     }
 
-    pub fn schema(&self) -> Arc<Schema> {
-        let schema = self.derive_schema();
-        schema
+    pub fn schema(&self) -> Result<Arc<Schema>> {
+        self.derive_schema()
     }
-    pub fn derive_schema(&self) -> Arc<Schema> {
+    pub fn derive_schema(&self) -> Result<Arc<Schema>> {
         let schema = self.data_source.schema();
         if self.projection.is_empty() {
-            return schema;
+            Ok(schema)
         } else {
-            return Arc::new(schema.select(self.projection.clone()).unwrap());
+            Ok(Arc::new(schema.select(self.projection.clone())?))
         }
     }
 }
@@ -41,9 +47,15 @@ impl Scan {
 impl std::fmt::Display for Scan {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.projection.is_empty() {
-            write!(f, "Scan: {}; projection=None;", self.path)
+            write!(f, "Scan: {}; projection=None;", self.path)?;
         } else {
-            write!(f, "Scan: {}; projection={:?};", self.path, self.projection)
+            write!(f, "Scan: {}; projection={:?};", self.path, self.projection)?;
         }
+
+        if !self.filters.is_empty() {
+            let filters: Vec<String> = self.filters.iter().map(|it| it.state.to_string()).collect();
+            write!(f, " filters=[{}];", filters.join(", "))?;
+        }
+        Ok(())
     }
 }
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use crate::{
     datatypes::schema::Schema,
+    error::Result,
     logical_plan::{LogicalPlan, expr::ExprRef},
 };
 
@@ -15,7 +16,7 @@ impl Selection {
         vec![self.input.clone()]
     }
 
-    pub fn schema(&self) -> Arc<Schema> {
+    pub fn schema(&self) -> Result<Arc<Schema>> {
         self.input.schema()
     }
 }
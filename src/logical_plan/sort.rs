@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use crate::{
+    datatypes::schema::Schema,
+    error::Result,
+    logical_plan::{LogicalPlan, expr::ExprRef},
+};
+
+/** One ORDER BY key. Ascending keys sort nulls last, descending keys sort nulls first */
+#[derive(Clone)]
+pub struct SortExpr {
+    pub expr: ExprRef,
+    pub asc: bool,
+}
+
+impl SortExpr {
+    pub fn new(expr: ExprRef, asc: bool) -> Self {
+        SortExpr { expr, asc }
+    }
+}
+
+impl std::fmt::Display for SortExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let direction = if self.asc { "ASC" } else { "DESC" };
+        write!(f, "{} {}", self.expr.state, direction)
+    }
+}
+
+pub struct Sort {
+    pub input: Arc<LogicalPlan>,
+    pub order_by: Vec<SortExpr>,
+}
+
+impl Sort {
+    pub fn children(&self) -> Vec<Arc<LogicalPlan>> {
+        vec![self.input.clone()]
+    }
+
+    pub fn schema(&self) -> Result<Arc<Schema>> {
+        self.input.schema()
+    }
+}
+
+impl std::fmt::Display for Sort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let order_by: Vec<String> = self.order_by.iter().map(|it| it.to_string()).collect();
+        write!(f, "Sort: {}", order_by.join(", "))
+    }
+}
@@ -1,26 +1,22 @@
 #[cfg(test)]
 pub mod test {
-    use std::sync::Arc;
-
-    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::datatypes::DataType;
 
     use crate::{
-        datasource::{DataSource, csv::CsvDataSource},
         logical_plan::{
-            LogicalPlan,
-            data_frame::{DataFrame, Frame},
+            data_frame::DataFrame,
             expr::AsAlias,
             format_plan,
-            helper::{column, count, max, min},
+            helper::{call_function, case, column, count, max, min, round, sqrt},
             join::JoinType,
             macro_utils::{eq, literal_float, literal_string, literal_u64},
-            scan::Scan,
         },
+        test_util::uk_cities,
     };
 
     #[test]
     fn build_data_frame() {
-        let df = csv()
+        let df = uk_cities()
             .filter(eq(column("city"), literal_string("Uk")))
             .project(vec![column("city"), column("lat"), column("lng")]);
         println!("{}", format_plan(&df.plan));
@@ -28,7 +24,7 @@ pub mod test {
 
     #[test]
     fn aggregate_data_frame() {
-        let df = csv().aggregate(
+        let df = uk_cities().aggregate(
             vec![column("state")],
             vec![min("city"), count("lat"), max("lng")],
         );
@@ -41,7 +37,7 @@ pub mod test {
         // column("city").eq(literal_string("London")) and eq(column("city"), literal_string("London"))
         // are logically equivalent
 
-        let df = csv()
+        let df = uk_cities()
             .filter(column("city").eq(literal_string("London")))
             .project(vec![column("city"), column("lat")])
             .filter((column("lat") * literal_float(1000.75)).alias("alias"));
@@ -51,13 +47,14 @@ pub mod test {
 
     #[test]
     fn limit_and_join() {
-        let df_two = csv().aggregate(vec![column("state")], vec![max("lng")]);
+        let df_two = uk_cities().aggregate(vec![column("state")], vec![max("lng")]);
 
         // In the future, implement try_from and try_into for all rust based numeric type to literal numeric type conversions
 
-        let df = csv()
+        let df = uk_cities()
             .project(vec![column("city"), column("lat")])
             .limit(literal_u64(2))
+            .unwrap()
             .join(
                 df_two,
                 JoinType::Left,
@@ -67,28 +64,80 @@ pub mod test {
         println!("{}", format_plan(&df.plan));
     }
 
-    fn csv() -> Frame {
-        let has_headers = false;
-        let file_path = String::from("/home/spaceriot/unakitesql/src/test_data/uk_cities.csv");
-
-        let data = CsvDataSource::new(
-            file_path,
-            has_headers,
-            Schema::new(vec![
-                Field::new("city", DataType::Utf8, false),
-                Field::new("lat", DataType::Float64, false),
-                Field::new("lng", DataType::Float64, false),
-            ]),
+    #[test]
+    fn scalar_functions() {
+        let power = call_function("POWER", vec![column("lat"), column("lng")]).unwrap();
+        let df = uk_cities().project(vec![
+            sqrt(column("lat")).alias("root"),
+            round(column("lng")),
+            power,
+        ]);
+
+        let schema = df.plan.schema().unwrap();
+        let fields: Vec<(String, DataType)> = schema
+            .fields
+            .iter()
+            .map(|it| (it.name.clone(), it.data_type.clone()))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("root".to_string(), DataType::Float64),
+                ("round(lng)".to_string(), DataType::Float64),
+                ("power(lat, lng)".to_string(), DataType::Float64),
+            ]
         );
 
-        let scan = Scan::new(
-            "uk_cities".to_string(),
-            DataSource::CSV(data),
-            Arc::new(vec![]),
+        assert!(call_function("sqrt", vec![]).is_err());
+        assert!(call_function("missing", vec![column("lat")]).is_err());
+        let city = uk_cities().project(vec![sqrt(column("city"))]);
+        assert!(city.plan.schema().is_err());
+    }
+
+    #[test]
+    fn conditional_expressions() {
+        let north = column("lat").between(column("lng"), column("lat"));
+        let larger = case(
+            None,
+            vec![(column("lat").gt(column("lng")), column("lat"))],
+            Some(column("lng")),
+        )
+        .unwrap();
+        let df = uk_cities().project(vec![
+            north.or(!column("lat").gt(column("lng"))).alias("flag"),
+            column("city")
+                .in_list(vec![literal_string("London")], true)
+                .alias("outside"),
+            (-column("lng")).alias("east"),
+            larger.alias("larger"),
+        ]);
+
+        let schema = df.plan.schema().unwrap();
+        let types: Vec<DataType> = schema
+            .fields
+            .iter()
+            .map(|it| it.data_type.clone())
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                DataType::Boolean,
+                DataType::Boolean,
+                DataType::Float64,
+                DataType::Float64
+            ]
         );
 
-        Frame {
-            plan: Arc::new(LogicalPlan::ScanPlan(scan)),
+        // Operands of the wrong type are rejected when the schema is resolved
+        let invalid = vec![
+            !column("lat"),
+            -column("city"),
+            column("city").between(column("lat"), column("lng")),
+            case(None, vec![(column("lat"), column("lng"))], None).unwrap(),
+        ];
+        for expr in invalid {
+            assert!(uk_cities().project(vec![expr]).plan.schema().is_err());
         }
+        assert!(case(None, vec![], Some(column("lat"))).is_err());
     }
 }
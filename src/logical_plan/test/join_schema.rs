@@ -0,0 +1,69 @@
+#[cfg(test)]
+pub mod test {
+    use crate::{
+        logical_plan::{data_frame::DataFrame, join::JoinType},
+        test_util::{uk_cities, uk_cities_named},
+    };
+
+    #[test]
+    fn clashing_names_are_qualified() {
+        let df = uk_cities().join(uk_cities_named("towns"), JoinType::Inner, on_city());
+
+        let names: Vec<String> = df
+            .schema()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|it| it.name.clone())
+            .collect();
+        assert_eq!(
+            names,
+            vec!["city", "lat", "lng", "towns.city", "towns.lat", "towns.lng"]
+        );
+    }
+
+    #[test]
+    fn outer_sides_are_nullable() {
+        let nullability = |join_type: JoinType| -> Vec<bool> {
+            uk_cities()
+                .join(uk_cities_named("towns"), join_type, on_city())
+                .schema()
+                .unwrap()
+                .fields
+                .iter()
+                .map(|it| it.nullable)
+                .collect()
+        };
+
+        assert_eq!(nullability(JoinType::Inner), vec![false; 6]);
+        assert_eq!(
+            nullability(JoinType::Left),
+            vec![false, false, false, true, true, true]
+        );
+        assert_eq!(
+            nullability(JoinType::Right),
+            vec![true, true, true, false, false, false]
+        );
+        assert_eq!(nullability(JoinType::Full), vec![true; 6]);
+    }
+
+    #[test]
+    fn semi_and_anti_joins_keep_left_columns() {
+        for join_type in [JoinType::LeftSemi, JoinType::LeftAnti] {
+            let df = uk_cities().join(uk_cities_named("towns"), join_type, on_city());
+
+            let names: Vec<String> = df
+                .schema()
+                .unwrap()
+                .fields
+                .iter()
+                .map(|it| it.name.clone())
+                .collect();
+            assert_eq!(names, vec!["city", "lat", "lng"]);
+        }
+    }
+
+    fn on_city() -> Vec<(String, String)> {
+        vec![("city".to_string(), "city".to_string())]
+    }
+}
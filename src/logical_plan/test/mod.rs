@@ -1 +1,2 @@
 pub mod data_frame;
+pub mod join_schema;
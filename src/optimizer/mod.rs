@@ -0,0 +1,73 @@
+pub mod predicate_push_down;
+pub mod projection_push_down;
+pub mod test;
+
+use std::{collections::HashSet, sync::Arc};
+
+use crate::{
+    error::Result,
+    logical_plan::{LogicalPlan, expr::Expr},
+    optimizer::{
+        predicate_push_down::PredicatePushDownRule, projection_push_down::ProjectionPushDownRule,
+    },
+};
+
+/**
+ * A rule rewrites a logical plan into an equivalent plan that is cheaper to execute. Rules are
+ * applied one after another by the optimizer.
+ */
+pub trait OptimizerRule {
+    fn optimize(&self, plan: Arc<LogicalPlan>) -> Result<Arc<LogicalPlan>>;
+}
+
+/** Applies a list of rules to a logical plan, in order */
+pub struct Optimizer {
+    rules: Vec<Box<dyn OptimizerRule>>,
+}
+
+impl Optimizer {
+    /**
+     * An optimizer with the default rules. Predicates are pushed down first so the columns they
+     * use are known when scans are narrowed.
+     */
+    pub fn new() -> Self {
+        Self {
+            rules: vec![
+                Box::new(PredicatePushDownRule),
+                Box::new(ProjectionPushDownRule),
+            ],
+        }
+    }
+
+    pub fn with_rules(rules: Vec<Box<dyn OptimizerRule>>) -> Self {
+        Self { rules }
+    }
+
+    pub fn optimize(&self, plan: Arc<LogicalPlan>) -> Result<Arc<LogicalPlan>> {
+        let mut plan = plan;
+        for rule in self.rules.iter() {
+            plan = rule.optimize(plan)?;
+        }
+        Ok(plan)
+    }
+}
+
+impl Default for Optimizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/** Collect the names of all columns referenced by an expression */
+pub fn extract_columns(expr: &Expr, accum: &mut HashSet<String>) {
+    match expr {
+        Expr::ColumnExpr(column) => {
+            accum.insert(column.name.clone());
+        }
+        _ => {
+            for child in expr.children() {
+                extract_columns(&child, accum);
+            }
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use std::{collections::HashSet, sync::Arc};
+
+use crate::{
+    error::Result,
+    logical_plan::{
+        LogicalPlan,
+        aggregate::Aggregate,
+        copy::Copy,
+        expr::{Expr, ExprRef},
+        join::{Join, JoinType},
+        limit::Limit,
+        projection::Projection,
+        scan::Scan,
+        selection::Selection,
+        sort::Sort,
+    },
+    optimizer::{OptimizerRule, extract_columns},
+};
+
+/**
+ * Moves filters as close to the scans as possible. Selections are split on `AND` so every part
+ * moves on its own, a part stops below the last node whose output it can be evaluated against.
+ * Parts that reach a scan are also attached to it so the data source can use them to skip data.
+ */
+pub struct PredicatePushDownRule;
+
+impl OptimizerRule for PredicatePushDownRule {
+    fn optimize(&self, plan: Arc<LogicalPlan>) -> Result<Arc<LogicalPlan>> {
+        push_down(&plan, vec![])
+    }
+}
+
+/** Push the predicates of the parent into `plan`, returning the rewritten plan */
+fn push_down(plan: &LogicalPlan, predicates: Vec<ExprRef>) -> Result<Arc<LogicalPlan>> {
+    match plan {
+        LogicalPlan::SelectionPlan(selection) => {
+            let mut predicates = predicates;
+            split_conjunction(&selection.expr, &mut predicates);
+
+            push_down(&selection.input, predicates)
+        }
+        LogicalPlan::ProjectionPlan(projection) => {
+            // Columns passed through unchanged mean the same thing below the projection
+            let passed: HashSet<String> = projection
+                .expr
+                .iter()
+                .filter_map(|it| match it.state.as_ref() {
+                    Expr::ColumnExpr(column) => Some(column.name.clone()),
+                    _ => None,
+                })
+                .collect();
+            let (below, above) = partition(predicates, &passed);
+
+            let plan = LogicalPlan::ProjectionPlan(Projection {
+                input: push_down(&projection.input, below)?,
+                expr: projection.expr.clone(),
+            });
+            Ok(filter(Arc::new(plan), above))
+        }
+        LogicalPlan::SortPlan(sort) => Ok(Arc::new(LogicalPlan::SortPlan(Sort {
+            input: push_down(&sort.input, predicates)?,
+            order_by: sort.order_by.clone(),
+        }))),
+        LogicalPlan::LimitPlan(limit) => {
+            // Filtering before a limit changes which rows are kept
+            let plan = LogicalPlan::LimitPlan(Limit {
+                input: push_down(&limit.input, vec![])?,
+                limit: limit.limit,
+            });
+            Ok(filter(Arc::new(plan), predicates))
+        }
+        LogicalPlan::AggregatePlan(aggregate) => {
+            // Predicates on grouping columns remove whole groups, so they can run first. Any
+            // other predicate, including a constant one, has to see the aggregated rows: a global
+            // aggregate yields a row even for empty input
+            let grouped: HashSet<String> = aggregate
+                .group_expr
+                .iter()
+                .filter_map(|it| match it.state.as_ref() {
+                    Expr::ColumnExpr(column) => Some(column.name.clone()),
+                    _ => None,
+                })
+                .collect();
+            let (below, above) = predicates.into_iter().partition(|it| {
+                let referenced = referenced_columns(it);
+                !referenced.is_empty() && referenced.is_subset(&grouped)
+            });
+
+            let plan = LogicalPlan::AggregatePlan(Aggregate {
+                input: push_down(&aggregate.input, below)?,
+                group_expr: aggregate.group_expr.clone(),
+                aggregate_expr: aggregate.aggregate_expr.clone(),
+            });
+            Ok(filter(Arc::new(plan), above))
+        }
+        LogicalPlan::CopyPlan(copy) => {
+            // Predicates above a copy refer to its row count, not to the written rows
+            let plan = LogicalPlan::CopyPlan(Copy {
+                input: push_down(&copy.input, vec![])?,
+                path: copy.path.clone(),
+                format: copy.format,
+                options: copy.options.clone(),
+            });
+            Ok(filter(Arc::new(plan), predicates))
+        }
+        LogicalPlan::JoinPlan(join) => push_down_join(join, predicates),
+        LogicalPlan::ScanPlan(scan) => {
+            let mut filters = scan.filters.clone();
+            filters.extend(predicates.iter().cloned());
+
+            let plan = LogicalPlan::ScanPlan(Scan {
+                data_source: scan.data_source.clone(),
+                path: scan.path.clone(),
+                projection: scan.projection.clone(),
+                filters,
+            });
+            Ok(filter(Arc::new(plan), predicates))
+        }
+    }
+}
+
+/**
+ * Send every predicate to the join input that owns all of its columns. A side is only eligible
+ * when the join never pads it with nulls, since filtering a padded side before the join would
+ * turn removed rows into null padded rows instead. Right columns qualified in the join output
+ * are not pushed, their name differs below the join.
+ */
+fn push_down_join(join: &Join, predicates: Vec<ExprRef>) -> Result<Arc<LogicalPlan>> {
+    let (push_left, push_right) = match join.join_type {
+        JoinType::Inner | JoinType::Cross => (true, true),
+        JoinType::Left | JoinType::LeftSemi | JoinType::LeftAnti => (true, false),
+        JoinType::Right => (false, true),
+        JoinType::Full => (false, false),
+    };
+
+    let left_columns = column_names(&join.left)?;
+    let mut right_columns = column_names(&join.right)?;
+    right_columns.retain(|it| !left_columns.contains(it));
+
+    let mut above = predicates;
+    let mut left = vec![];
+    let mut right = vec![];
+
+    if push_left {
+        let (below, rest) = partition(above, &left_columns);
+        left = below;
+        above = rest;
+    }
+    if push_right {
+        let (below, rest) = partition(above, &right_columns);
+        right = below;
+        above = rest;
+    }
+
+    let plan = LogicalPlan::JoinPlan(Join {
+        left: push_down(&join.left, left)?,
+        right: push_down(&join.right, right)?,
+        join_type: join.join_type.clone(),
+        on: join.on.clone(),
+    });
+    Ok(filter(Arc::new(plan), above))
+}
+
+/** Split `a AND b AND c` into `[a, b, c]` */
+fn split_conjunction(expr: &ExprRef, accum: &mut Vec<ExprRef>) {
+    match expr.state.as_ref() {
+        Expr::AndExpr(and) => {
+            split_conjunction(&ExprRef::new(and.l.clone()), accum);
+            split_conjunction(&ExprRef::new(and.r.clone()), accum);
+        }
+        _ => accum.push(expr.clone()),
+    }
+}
+
+/** Predicates that only reference `columns`, followed by the remaining predicates */
+fn partition(predicates: Vec<ExprRef>, columns: &HashSet<String>) -> (Vec<ExprRef>, Vec<ExprRef>) {
+    predicates
+        .into_iter()
+        .partition(|it| referenced_columns(it).is_subset(columns))
+}
+
+fn referenced_columns(expr: &ExprRef) -> HashSet<String> {
+    let mut referenced = HashSet::new();
+    extract_columns(&expr.state, &mut referenced);
+    referenced
+}
+
+/** Wrap `plan` in a selection holding the conjunction of `predicates`, if there are any */
+fn filter(plan: Arc<LogicalPlan>, predicates: Vec<ExprRef>) -> Arc<LogicalPlan> {
+    match predicates.into_iter().reduce(|l, r| l.and(r)) {
+        Some(expr) => Arc::new(LogicalPlan::SelectionPlan(Selection { input: plan, expr })),
+        None => plan,
+    }
+}
+
+fn column_names(plan: &LogicalPlan) -> Result<HashSet<String>> {
+    Ok(plan
+        .schema()?
+        .fields
+        .iter()
+        .map(|it| it.name.clone())
+        .collect())
+}
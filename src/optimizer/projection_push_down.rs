@@ -0,0 +1,198 @@
+use std::{collections::HashSet, sync::Arc};
+
+use crate::{
+    error::Result,
+    logical_plan::{
+        LogicalPlan,
+        aggregate::Aggregate,
+        copy::Copy,
+        expr::ExprRef,
+        join::{Join, JoinType},
+        limit::Limit,
+        projection::Projection,
+        scan::Scan,
+        selection::Selection,
+        sort::Sort,
+    },
+    optimizer::{OptimizerRule, extract_columns},
+};
+
+/**
+ * Rewrites every scan to read only the columns the rest of the plan references. The plan is
+ * walked top down carrying the set of column names the parent needs, every node adds the
+ * columns used by its own expressions before passing the set on to its inputs.
+ */
+pub struct ProjectionPushDownRule;
+
+impl OptimizerRule for ProjectionPushDownRule {
+    fn optimize(&self, plan: Arc<LogicalPlan>) -> Result<Arc<LogicalPlan>> {
+        let required: HashSet<String> = plan
+            .schema()?
+            .fields
+            .iter()
+            .map(|it| it.name.clone())
+            .collect();
+
+        push_down(&plan, &required)
+    }
+}
+
+fn push_down(plan: &LogicalPlan, required: &HashSet<String>) -> Result<Arc<LogicalPlan>> {
+    let plan = match plan {
+        LogicalPlan::ProjectionPlan(projection) => {
+            // Only the columns used by the projected expressions are needed below it
+            let accum = columns_of(&projection.expr);
+
+            LogicalPlan::ProjectionPlan(Projection {
+                input: push_down(&projection.input, &accum)?,
+                expr: projection.expr.clone(),
+            })
+        }
+        LogicalPlan::SelectionPlan(selection) => {
+            let mut accum = required.clone();
+            extract_columns(&selection.expr.state, &mut accum);
+
+            LogicalPlan::SelectionPlan(Selection {
+                input: push_down(&selection.input, &accum)?,
+                expr: selection.expr.clone(),
+            })
+        }
+        LogicalPlan::SortPlan(sort) => {
+            let mut accum = required.clone();
+            for sort_expr in sort.order_by.iter() {
+                extract_columns(&sort_expr.expr.state, &mut accum);
+            }
+
+            LogicalPlan::SortPlan(Sort {
+                input: push_down(&sort.input, &accum)?,
+                order_by: sort.order_by.clone(),
+            })
+        }
+        LogicalPlan::LimitPlan(limit) => LogicalPlan::LimitPlan(Limit {
+            input: push_down(&limit.input, required)?,
+            limit: limit.limit,
+        }),
+        LogicalPlan::AggregatePlan(aggregate) => {
+            let mut accum = columns_of(&aggregate.group_expr);
+            for aggregate_expr in aggregate.aggregate_expr.iter() {
+                extract_columns(&aggregate_expr.expr().state, &mut accum);
+            }
+
+            LogicalPlan::AggregatePlan(Aggregate {
+                input: push_down(&aggregate.input, &accum)?,
+                group_expr: aggregate.group_expr.clone(),
+                aggregate_expr: aggregate.aggregate_expr.clone(),
+            })
+        }
+        LogicalPlan::JoinPlan(join) => {
+            let (left, right) = join_requirements(join, required)?;
+
+            LogicalPlan::JoinPlan(Join {
+                left: push_down(&join.left, &left)?,
+                right: push_down(&join.right, &right)?,
+                join_type: join.join_type.clone(),
+                on: join.on.clone(),
+            })
+        }
+        LogicalPlan::ScanPlan(scan) => LogicalPlan::ScanPlan(project_scan(scan, required)?),
+        LogicalPlan::CopyPlan(copy) => {
+            // Every column of the input is written
+            let accum: HashSet<String> = copy
+                .input
+                .schema()?
+                .fields
+                .iter()
+                .map(|it| it.name.clone())
+                .collect();
+
+            LogicalPlan::CopyPlan(Copy {
+                input: push_down(&copy.input, &accum)?,
+                path: copy.path.clone(),
+                format: copy.format,
+                options: copy.options.clone(),
+            })
+        }
+    };
+
+    Ok(Arc::new(plan))
+}
+
+/**
+ * Split the columns required from a join into the columns required from each side. Right
+ * columns are found by position, since a right column whose name clashes with a left column is
+ * qualified in the join output. Left columns that cause such a clash are kept so the qualified
+ * names seen by the parent do not change.
+ */
+fn join_requirements(
+    join: &Join,
+    required: &HashSet<String>,
+) -> Result<(HashSet<String>, HashSet<String>)> {
+    let left_schema = join.left.schema()?;
+    let right_schema = join.right.schema()?;
+
+    let mut left: HashSet<String> = join.on.iter().map(|(l, _)| l.clone()).collect();
+    let mut right: HashSet<String> = join.on.iter().map(|(_, r)| r.clone()).collect();
+
+    for field in left_schema.fields.iter() {
+        if required.contains(&field.name) {
+            left.insert(field.name.clone());
+        }
+    }
+
+    // Semi and anti joins only output left columns
+    if matches!(join.join_type, JoinType::LeftSemi | JoinType::LeftAnti) {
+        return Ok((left, right));
+    }
+
+    let output = join.schema()?;
+    let right_output = &output.fields[left_schema.fields.len()..];
+
+    for (field, output_field) in right_schema.fields.iter().zip(right_output.iter()) {
+        if required.contains(&output_field.name) {
+            right.insert(field.name.clone());
+
+            if field.name != output_field.name {
+                left.insert(field.name.clone());
+            }
+        }
+    }
+
+    Ok((left, right))
+}
+
+/**
+ * Restrict a scan to the required columns, in the order the scan produced them. An empty
+ * projection reads every column, so a scan none of whose columns are needed (e.g. below
+ * `COUNT(*)`) keeps its first column.
+ */
+fn project_scan(scan: &Scan, required: &HashSet<String>) -> Result<Scan> {
+    let schema = scan.schema()?;
+
+    let mut projection: Vec<String> = schema
+        .fields
+        .iter()
+        .filter(|it| required.contains(&it.name))
+        .map(|it| it.name.clone())
+        .collect();
+
+    if projection.is_empty()
+        && let Some(first) = schema.fields.first()
+    {
+        projection.push(first.name.clone());
+    }
+
+    Ok(Scan {
+        data_source: scan.data_source.clone(),
+        path: scan.path.clone(),
+        projection: Arc::new(projection),
+        filters: scan.filters.clone(),
+    })
+}
+
+fn columns_of(exprs: &[ExprRef]) -> HashSet<String> {
+    let mut accum = HashSet::new();
+    for expr in exprs.iter() {
+        extract_columns(&expr.state, &mut accum);
+    }
+    accum
+}
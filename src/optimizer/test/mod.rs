@@ -0,0 +1,2 @@
+pub mod predicate_push_down;
+pub mod projection_push_down;
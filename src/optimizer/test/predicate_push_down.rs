@@ -0,0 +1,196 @@
+#[cfg(test)]
+pub mod test {
+    use std::sync::Arc;
+
+    use crate::{
+        datatypes::record_batch::RecordBatch,
+        error::Result,
+        logical_plan::{
+            LogicalPlan,
+            data_frame::{DataFrame, Frame},
+            expr::AsAlias,
+            format_plan,
+            helper::{column, count, sum},
+            join::JoinType,
+            macro_utils::{literal_i64, literal_string},
+            scan::Scan,
+        },
+        optimizer::{Optimizer, OptimizerRule, predicate_push_down::PredicatePushDownRule},
+        query_planner::QueryPlanner,
+        test_util::{regions, sales},
+    };
+
+    #[test]
+    fn conjunction_is_split_and_attached_to_scan() {
+        let df = sales()
+            .project(vec![column("region"), column("quantity")])
+            .filter(
+                column("region")
+                    .eq(literal_string("North"))
+                    .and(column("quantity").gt(literal_i64(4))),
+            );
+
+        let plan = PredicatePushDownRule.optimize(df.plan.clone()).unwrap();
+
+        let LogicalPlan::ProjectionPlan(projection) = plan.as_ref() else {
+            panic!("expected the projection on top:\n{}", format_plan(&plan));
+        };
+        let LogicalPlan::SelectionPlan(selection) = projection.input.as_ref() else {
+            panic!(
+                "expected the filter below the projection:\n{}",
+                format_plan(&plan)
+            );
+        };
+        let LogicalPlan::ScanPlan(scan) = selection.input.as_ref() else {
+            panic!(
+                "expected the filter right above the scan:\n{}",
+                format_plan(&plan)
+            );
+        };
+        assert_eq!(scan.filters.len(), 2);
+    }
+
+    #[test]
+    fn filters_on_computed_columns_stay_above() {
+        let df = sales()
+            .project(vec![column("region"), column("quantity").alias("q")])
+            .filter(column("q").gt(literal_i64(4)));
+
+        let plan = PredicatePushDownRule.optimize(df.plan.clone()).unwrap();
+
+        assert!(matches!(plan.as_ref(), LogicalPlan::SelectionPlan(_)));
+        assert!(scans(&plan).iter().all(|it| it.filters.is_empty()));
+    }
+
+    #[test]
+    fn inner_join_filters_move_to_the_owning_side() {
+        let df = sales()
+            .join(
+                regions(),
+                JoinType::Inner,
+                vec![("region".to_string(), "region".to_string())],
+            )
+            .filter(
+                column("manager")
+                    .eq(literal_string("Alice"))
+                    .and(column("quantity").gt(literal_i64(4))),
+            );
+
+        let plan = PredicatePushDownRule.optimize(df.plan.clone()).unwrap();
+
+        assert!(matches!(plan.as_ref(), LogicalPlan::JoinPlan(_)));
+        let filters: Vec<usize> = scans(&plan).iter().map(|it| it.filters.len()).collect();
+        assert_eq!(filters, vec![1, 1]);
+
+        assert_eq!(count_rows(&df), count_rows(&Frame { plan }));
+    }
+
+    #[test]
+    fn outer_join_keeps_filters_on_the_padded_side() {
+        let df = sales()
+            .join(
+                regions(),
+                JoinType::Left,
+                vec![("region".to_string(), "region".to_string())],
+            )
+            .filter(column("manager").eq(literal_string("Alice")));
+
+        let plan = PredicatePushDownRule.optimize(df.plan.clone()).unwrap();
+
+        assert!(matches!(plan.as_ref(), LogicalPlan::SelectionPlan(_)));
+        assert!(scans(&plan).iter().all(|it| it.filters.is_empty()));
+    }
+
+    #[test]
+    fn having_on_group_column_runs_before_aggregation() {
+        let df = sales()
+            .aggregate(vec![column("region")], vec![sum("quantity")])
+            .filter(column("region").eq(literal_string("North")));
+
+        let plan = PredicatePushDownRule.optimize(df.plan.clone()).unwrap();
+
+        assert!(matches!(plan.as_ref(), LogicalPlan::AggregatePlan(_)));
+        assert_eq!(scans(&plan)[0].filters.len(), 1);
+        assert_eq!(count_rows(&df), count_rows(&Frame { plan }));
+    }
+
+    #[test]
+    fn having_without_group_columns_stays_above_aggregation() {
+        // A global aggregate yields one row even when its input is empty
+        let df = sales()
+            .aggregate(vec![], vec![count("quantity")])
+            .filter(literal_i64(1).eq(literal_i64(0)));
+
+        let plan = PredicatePushDownRule.optimize(df.plan.clone()).unwrap();
+
+        assert!(matches!(plan.as_ref(), LogicalPlan::SelectionPlan(_)));
+        assert!(scans(&plan)[0].filters.is_empty());
+        assert_eq!(count_rows(&Frame { plan }), 0);
+
+        let df = sales()
+            .aggregate(vec![column("region")], vec![sum("quantity")])
+            .filter(literal_i64(1).eq(literal_i64(0)));
+
+        let plan = PredicatePushDownRule.optimize(df.plan.clone()).unwrap();
+
+        assert!(matches!(plan.as_ref(), LogicalPlan::SelectionPlan(_)));
+        assert!(scans(&plan)[0].filters.is_empty());
+    }
+
+    #[test]
+    fn filters_are_not_pushed_below_limit() {
+        let df = sales()
+            .limit(literal_i64(3))
+            .unwrap()
+            .filter(column("region").eq(literal_string("North")));
+
+        let plan = PredicatePushDownRule.optimize(df.plan.clone()).unwrap();
+
+        assert!(matches!(plan.as_ref(), LogicalPlan::SelectionPlan(_)));
+        assert!(scans(&plan)[0].filters.is_empty());
+    }
+
+    #[test]
+    fn rules_compose() {
+        let df = sales()
+            .filter(column("region").eq(literal_string("North")))
+            .project(vec![column("product")]);
+
+        let plan = Optimizer::new().optimize(df.plan.clone()).unwrap();
+
+        let scans = scans(&plan);
+        assert_eq!(scans[0].filters.len(), 1);
+        assert_eq!(
+            scans[0].projection.as_ref(),
+            &vec!["region".to_string(), "product".to_string()]
+        );
+    }
+
+    fn scans(plan: &Arc<LogicalPlan>) -> Vec<&Scan> {
+        let mut scans = vec![];
+        collect_scans(plan, &mut scans);
+        scans
+    }
+
+    fn collect_scans<'a>(plan: &'a LogicalPlan, scans: &mut Vec<&'a Scan>) {
+        match plan {
+            LogicalPlan::ScanPlan(scan) => scans.push(scan),
+            LogicalPlan::JoinPlan(join) => {
+                collect_scans(&join.left, scans);
+                collect_scans(&join.right, scans);
+            }
+            LogicalPlan::ProjectionPlan(it) => collect_scans(&it.input, scans),
+            LogicalPlan::SelectionPlan(it) => collect_scans(&it.input, scans),
+            LogicalPlan::AggregatePlan(it) => collect_scans(&it.input, scans),
+            LogicalPlan::SortPlan(it) => collect_scans(&it.input, scans),
+            LogicalPlan::LimitPlan(it) => collect_scans(&it.input, scans),
+            LogicalPlan::CopyPlan(it) => collect_scans(&it.input, scans),
+        }
+    }
+
+    fn count_rows(df: &Frame) -> usize {
+        let plan = QueryPlanner::create_physical_plan(&df.plan).unwrap();
+        let batches: Vec<RecordBatch> = plan.execute().unwrap().collect::<Result<_>>().unwrap();
+        batches.iter().map(|it| it.row_count()).sum()
+    }
+}
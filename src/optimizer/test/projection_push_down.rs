@@ -0,0 +1,126 @@
+#[cfg(test)]
+pub mod test {
+
+    use crate::{
+        logical_plan::{
+            LogicalPlan,
+            data_frame::{DataFrame, Frame},
+            helper::{column, sum},
+            join::JoinType,
+            macro_utils::{literal_i64, literal_string},
+            sort::SortExpr,
+        },
+        optimizer::{OptimizerRule, projection_push_down::ProjectionPushDownRule},
+        test_util::{regions, sales, sales_named},
+    };
+
+    #[test]
+    fn projection_reads_only_referenced_columns() {
+        let df = sales().project(vec![column("price"), column("product")]);
+
+        assert_eq!(projections(&df), vec!["sales: product, price"]);
+    }
+
+    #[test]
+    fn filter_and_sort_columns_are_kept() {
+        let df = sales()
+            .filter(column("region").eq(literal_string("North")))
+            .sort(vec![SortExpr::new(column("quantity"), false)])
+            .project(vec![column("product")]);
+
+        assert_eq!(projections(&df), vec!["sales: region, product, quantity"]);
+    }
+
+    #[test]
+    fn aggregate_reads_group_and_aggregate_columns() {
+        let df = sales().aggregate(vec![column("region")], vec![sum("quantity")]);
+        assert_eq!(projections(&df), vec!["sales: region, quantity"]);
+
+        // No column is referenced, one is still read so the rows can be counted
+        let df = sales().project(vec![literal_i64(1)]);
+        assert_eq!(projections(&df), vec!["sales: region"]);
+    }
+
+    #[test]
+    fn join_keys_are_read_from_both_sides() {
+        let df = sales()
+            .join(
+                regions(),
+                JoinType::Inner,
+                vec![("region".to_string(), "region".to_string())],
+            )
+            .project(vec![column("product"), column("manager")]);
+
+        assert_eq!(
+            projections(&df),
+            vec![
+                "sales: region, product".to_string(),
+                "regions: region, manager".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn qualified_right_columns_keep_their_name() {
+        let df = sales()
+            .join(
+                sales_named("other"),
+                JoinType::Inner,
+                vec![("product".to_string(), "product".to_string())],
+            )
+            .project(vec![column("other.price")]);
+
+        let optimized = Frame {
+            plan: ProjectionPushDownRule.optimize(df.plan.clone()).unwrap(),
+        };
+
+        // `price` is kept on the left so the right column is still called `other.price`
+        assert_eq!(
+            projections(&df),
+            vec![
+                "sales: product, price".to_string(),
+                "other: product, price".to_string()
+            ]
+        );
+        assert_eq!(
+            optimized.schema().unwrap().fields[0].name,
+            df.schema().unwrap().fields[0].name
+        );
+    }
+
+    #[test]
+    fn semi_join_reads_only_right_keys() {
+        let df = sales().join(
+            regions(),
+            JoinType::LeftSemi,
+            vec![("region".to_string(), "region".to_string())],
+        );
+
+        assert_eq!(
+            projections(&df),
+            vec![
+                "sales: region, product, quantity, price".to_string(),
+                "regions: region".to_string()
+            ]
+        );
+    }
+
+    /** Projection of every scan in the optimized plan, left to right */
+    fn projections(df: &Frame) -> Vec<String> {
+        let plan = ProjectionPushDownRule.optimize(df.plan.clone()).unwrap();
+
+        let mut scans = vec![];
+        collect_scans(&plan, &mut scans);
+        scans
+    }
+
+    fn collect_scans(plan: &LogicalPlan, scans: &mut Vec<String>) {
+        if let LogicalPlan::ScanPlan(scan) = plan {
+            scans.push(format!("{}: {}", scan.path, scan.projection.join(", ")));
+        }
+
+        for child in plan.children() {
+            collect_scans(&child, scans);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::{fmt, iter, sync::Arc};
+
+use arrow::{
+    array::{RecordBatch as ArrowRecordBatch, UInt64Array},
+    datatypes::{DataType, Field as ArrowField, Schema as ArrowSchema},
+};
+
+use crate::{
+    datasource::sink::{WriteFormat, WriteOptions, write_batches},
+    datatypes::{
+        record_batch::RecordBatch,
+        schema::{Schema, schema_from_arrow_schema},
+    },
+    error::Result,
+    physical_plan::{PhysPlanTrait, PhysicaPlan, RecordBatchStream},
+};
+
+/** Streams the batches of the input to files, producing a single row holding the row count */
+pub struct CopyExec {
+    input: Arc<PhysicaPlan>,
+    path: String,
+    format: WriteFormat,
+    options: WriteOptions,
+    schema: Arc<ArrowSchema>,
+}
+
+impl CopyExec {
+    pub fn new(
+        input: Arc<PhysicaPlan>,
+        path: String,
+        format: WriteFormat,
+        options: WriteOptions,
+    ) -> Self {
+        let schema = Arc::new(ArrowSchema::new(vec![ArrowField::new(
+            "rows",
+            DataType::UInt64,
+            false,
+        )]));
+
+        Self {
+            input,
+            path,
+            format,
+            options,
+            schema,
+        }
+    }
+}
+
+impl PhysPlanTrait for CopyExec {
+    fn schema(&self) -> Schema {
+        schema_from_arrow_schema(self.schema.clone())
+    }
+
+    fn children(&self) -> Vec<Arc<PhysicaPlan>> {
+        vec![self.input.clone()]
+    }
+
+    /** The files are written when the first batch is pulled */
+    fn execute(&self) -> Result<RecordBatchStream> {
+        let batches = self.input.execute()?;
+        let input_schema = self.input.schema();
+        let path = self.path.clone();
+        let format = self.format;
+        let options = self.options.clone();
+        let schema = self.schema.clone();
+
+        Ok(Box::new(iter::once_with(move || {
+            let rows = write_batches(batches, &input_schema, &path, format, &options)?;
+
+            let count = Arc::new(UInt64Array::from(vec![rows as u64]));
+            let batch = ArrowRecordBatch::try_new(schema, vec![count])?;
+            Ok(RecordBatch::from(batch))
+        })))
+    }
+}
+
+impl fmt::Display for CopyExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "CopyExec: path={}, format={:?}", self.path, self.format)
+    }
+}
@@ -1,27 +1,35 @@
-use std::fmt::{self, Debug, Display};
+use std::{
+    collections::HashSet,
+    fmt::{self, Debug, Display},
+};
 
 use arrow::{
-    array::{ArrayRef, PrimitiveArray},
-    compute,
+    array::{Array, ArrayRef, AsArray, PrimitiveArray},
+    compute::{self, CastOptions},
     datatypes::{
         DataType, Float32Type, Float64Type, Int8Type, Int16Type, Int32Type, Int64Type, UInt8Type,
         UInt16Type, UInt32Type, UInt64Type,
     },
+    row::{OwnedRow, RowConverter, SortField},
 };
 
 use crate::{
     datatypes::{column_vector::ColumnVector, value::ArrowValue},
+    error::{Result, UnakiteError},
     physical_plan::expressions::{Expression, column_expressions::ColumnExpression},
 };
 
-pub trait AggregateExpression: Display + Debug {
+pub trait AggregateExpression: Display + Debug + Send + Sync {
     fn input_expression(&self) -> Expression;
     fn create_accumulator(&self) -> Box<dyn Accumulator>;
 }
 
 pub trait Accumulator {
-    fn update(&mut self, values: &ColumnVector) -> anyhow::Result<()>;
-    fn final_value(&self) -> ArrowValue;
+    /** Fold a batch of values into the accumulator. Null values are ignored. */
+    fn update(&mut self, values: &ColumnVector) -> Result<()>;
+
+    /** The aggregated value, or None when no non-null value was accumulated */
+    fn final_value(&self) -> Option<ArrowValue>;
 }
 
 #[macro_export]
@@ -35,11 +43,11 @@ macro_rules! match_primitive_op {
                     let arr = $array
                         .as_any()
                         .downcast_ref::<PrimitiveArray<$ty>>()
-                        .ok_or_else(|| anyhow::anyhow!(concat!("Failed to downcast to ", stringify!($ty))))?;
-                    Ok(ArrowValue::$variant(compute::$op::<$ty>(arr).unwrap_or_default()))
+                        .ok_or_else(|| $crate::error::UnakiteError::Type(concat!("Failed to downcast to ", stringify!($ty)).to_string()))?;
+                    Ok(compute::$op::<$ty>(arr).map(ArrowValue::$variant))
                 }
             )*
-            other => Err(anyhow::anyhow!("Unsupported data type: {:?}", other)),
+            other => Err($crate::error::UnakiteError::NotImplemented(format!("Unsupported data type: {:?}", other))),
         }
     };
 }
@@ -47,7 +55,7 @@ macro_rules! match_primitive_op {
 /// Creates a primitive aggregator function like min_primitive, max_primitive, etc.
 macro_rules! define_primitive_agg_fn {
     ($func_name:ident, $kernel_fn:ident) => {
-        fn $func_name(array: &ArrayRef) -> anyhow::Result<ArrowValue> {
+        fn $func_name(array: &ArrayRef) -> Result<Option<ArrowValue>> {
             match_primitive_op!(array, $kernel_fn,
                 DataType::Int8 => Int8Type => Int8Type,
                 DataType::Int16 => Int16Type => Int16Type,
@@ -68,8 +76,55 @@ define_primitive_agg_fn!(min_primitive, min);
 define_primitive_agg_fn!(max_primitive, max);
 define_primitive_agg_fn!(sum_primitive, sum);
 
-macro_rules! impl_aggregator {
-    ($( ($aggregate_name:ident, $accumulator_name:ident, $op_func:ident) ),* $(,)?) => {
+fn min_batch(array: &ArrayRef) -> Result<Option<ArrowValue>> {
+    match array.data_type() {
+        DataType::Utf8 => Ok(compute::min_string(array.as_string::<i32>())
+            .map(|it| ArrowValue::StringType(it.to_string()))),
+        _ => min_primitive(array),
+    }
+}
+
+fn max_batch(array: &ArrayRef) -> Result<Option<ArrowValue>> {
+    match array.data_type() {
+        DataType::Utf8 => Ok(compute::max_string(array.as_string::<i32>())
+            .map(|it| ArrowValue::StringType(it.to_string()))),
+        _ => max_primitive(array),
+    }
+}
+
+fn merge_min(current: ArrowValue, value: ArrowValue) -> Result<ArrowValue> {
+    Ok(if value < current { value } else { current })
+}
+
+fn merge_max(current: ArrowValue, value: ArrowValue) -> Result<ArrowValue> {
+    Ok(if value > current { value } else { current })
+}
+
+// Integer sums wrap on overflow, matching the Arrow sum kernel used within a batch
+fn merge_sum(current: ArrowValue, value: ArrowValue) -> Result<ArrowValue> {
+    use ArrowValue::*;
+    Ok(match (current, value) {
+        (Int8Type(a), Int8Type(b)) => Int8Type(a.wrapping_add(b)),
+        (Int16Type(a), Int16Type(b)) => Int16Type(a.wrapping_add(b)),
+        (Int32Type(a), Int32Type(b)) => Int32Type(a.wrapping_add(b)),
+        (Int64Type(a), Int64Type(b)) => Int64Type(a.wrapping_add(b)),
+        (UInt8Type(a), UInt8Type(b)) => UInt8Type(a.wrapping_add(b)),
+        (UInt16Type(a), UInt16Type(b)) => UInt16Type(a.wrapping_add(b)),
+        (UInt32Type(a), UInt32Type(b)) => UInt32Type(a.wrapping_add(b)),
+        (UInt64Type(a), UInt64Type(b)) => UInt64Type(a.wrapping_add(b)),
+        (FloatType(a), FloatType(b)) => FloatType(a + b),
+        (DoubleType(a), DoubleType(b)) => DoubleType(a + b),
+        (current, _) => {
+            return Err(UnakiteError::NotImplemented(format!(
+                "SUM is not implemented for data type {}",
+                current
+            )));
+        }
+    })
+}
+
+macro_rules! impl_aggregate_expression {
+    ($( ($aggregate_name:ident, $accumulator_name:ident) ),* $(,)?) => {
         $(
             pub struct $aggregate_name {
                 pub expr: Expression,
@@ -89,57 +144,150 @@ macro_rules! impl_aggregator {
 
             impl AggregateExpression for $aggregate_name {
                 fn create_accumulator(&self) -> Box<dyn Accumulator> {
-                    Box::new($accumulator_name { value: None })
+                    Box::new($accumulator_name::default())
                 }
 
                 fn input_expression(&self) -> Expression {
                     self.expr.clone()
                 }
             }
+        )*
+    };
+}
+
+impl_aggregate_expression!(
+    (MaxExpression, MaxAccumulator),
+    (MinExpression, MinAccumulator),
+    (SumExpression, SumAccumulator),
+    (AvgExpression, AvgAccumulator),
+    (CountExpression, CountAccumulator),
+    (CountDistinctExpression, CountDistinctAccumulator),
+);
 
+// Accumulators that reduce each batch to one value and merge it into the running value
+macro_rules! impl_accumulator {
+    ($( ($accumulator_name:ident, $batch_func:ident, $merge_func:ident) ),* $(,)?) => {
+        $(
+            #[derive(Default)]
             pub struct $accumulator_name {
                 value: Option<ArrowValue>,
             }
 
             impl Accumulator for $accumulator_name {
-                fn update(&mut self, values: &ColumnVector) -> anyhow::Result<()> {
-                    let vec = values.get_vector();
-                    let value = $op_func(&vec.field)?;
-
-                    self.value = Some(match &self.value {
-                    Some(current) => match value {
-                        ArrowValue::Int8Type(v) => ArrowValue::Int8Type(v + i8::from(current.clone())),
-                        ArrowValue::Int16Type(v) => ArrowValue::Int16Type(v + i16::from(current.clone())),
-                        ArrowValue::Int32Type(v) => ArrowValue::Int32Type(v + i32::from(current.clone())),
-                        ArrowValue::Int64Type(v) => ArrowValue::Int64Type(v + i64::from(current.clone())),
-                        ArrowValue::UInt8Type(v) => ArrowValue::UInt8Type(v + u8::from(current.clone())),
-                        ArrowValue::UInt16Type(v) => ArrowValue::UInt16Type(v + u16::from(current.clone())),
-                        ArrowValue::UInt32Type(v) => ArrowValue::UInt32Type(v + u32::from(current.clone())),
-                        ArrowValue::UInt64Type(v) => ArrowValue::UInt64Type(v + u64::from(current.clone())),
-                        ArrowValue::FloatType(v) => ArrowValue::FloatType(v + f32::from(current.clone())),
-                        ArrowValue::DoubleType(v) => ArrowValue::DoubleType(v + f64::from(current.clone())),
-                        _ => panic!("MAX is not implemented for data type"),
-                        },
+                fn update(&mut self, values: &ColumnVector) -> Result<()> {
+                    let value = match $batch_func(&values.to_array())? {
+                        Some(value) => value,
+                        None => return Ok(()),
+                    };
+
+                    self.value = Some(match self.value.take() {
+                        Some(current) => $merge_func(current, value)?,
                         None => value,
                     });
-                Ok(())
+                    Ok(())
                 }
 
-                fn final_value(&self) -> ArrowValue {
-                    self.value.clone().expect("Accumulator has no value")
+                fn final_value(&self) -> Option<ArrowValue> {
+                    self.value.clone()
                 }
             }
-
         )*
     };
 }
 
-impl_aggregator!(
-    (MaxExpression, MaxAccumulator, max_primitive),
-    (MinExpression, MinAccumulator, min_primitive),
-    (SumExpression, SumAccumulator, sum_primitive)
+impl_accumulator!(
+    (MaxAccumulator, max_batch, merge_max),
+    (MinAccumulator, min_batch, merge_min),
+    (SumAccumulator, sum_primitive, merge_sum),
 );
 
+#[derive(Default)]
+pub struct AvgAccumulator {
+    sum: f64,
+    count: usize,
+}
+
+impl Accumulator for AvgAccumulator {
+    fn update(&mut self, values: &ColumnVector) -> Result<()> {
+        // Values that are not numbers are an error rather than a null left out of the average
+        let options = CastOptions {
+            safe: false,
+            ..Default::default()
+        };
+        let array = compute::cast_with_options(&values.to_array(), &DataType::Float64, &options)?;
+        let doubles = array.as_primitive::<Float64Type>();
+
+        self.sum += compute::sum(doubles).unwrap_or_default();
+        self.count += doubles.len() - doubles.null_count();
+        Ok(())
+    }
+
+    fn final_value(&self) -> Option<ArrowValue> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(ArrowValue::DoubleType(self.sum / self.count as f64))
+    }
+}
+
+#[derive(Default)]
+pub struct CountAccumulator {
+    count: i32,
+}
+
+impl Accumulator for CountAccumulator {
+    fn update(&mut self, values: &ColumnVector) -> Result<()> {
+        let array = values.to_array();
+        self.count = i32::try_from(array.len() - array.null_count())
+            .ok()
+            .and_then(|rows| self.count.checked_add(rows))
+            .ok_or_else(|| UnakiteError::Execution("COUNT overflowed Int32".to_string()))?;
+        Ok(())
+    }
+
+    fn final_value(&self) -> Option<ArrowValue> {
+        Some(ArrowValue::Int32Type(self.count))
+    }
+}
+
+/** Tracks distinct non-null values encoded in the Arrow row format */
+#[derive(Default)]
+pub struct CountDistinctAccumulator {
+    converter: Option<RowConverter>,
+    seen: HashSet<OwnedRow>,
+}
+
+impl Accumulator for CountDistinctAccumulator {
+    fn update(&mut self, values: &ColumnVector) -> Result<()> {
+        let array = values.to_array();
+        let non_null = compute::filter(&array, &compute::is_not_null(&array)?)?;
+
+        let converter = match &mut self.converter {
+            Some(converter) => converter,
+            None => self
+                .converter
+                .insert(RowConverter::new(vec![SortField::new(
+                    array.data_type().clone(),
+                )])?),
+        };
+
+        let rows = converter.convert_columns(&[non_null])?;
+        for row in rows.iter() {
+            self.seen.insert(row.owned());
+        }
+        if i32::try_from(self.seen.len()).is_err() {
+            return Err(UnakiteError::Execution(
+                "COUNT DISTINCT overflowed Int32".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn final_value(&self) -> Option<ArrowValue> {
+        Some(ArrowValue::Int32Type(self.seen.len() as i32))
+    }
+}
+
 // Helper
 pub fn sum_expression() -> SumExpression {
     SumExpression {
@@ -148,17 +296,31 @@ pub fn sum_expression() -> SumExpression {
 }
 
 pub fn min_expression() -> MinExpression {
-    let a = MinExpression {
+    MinExpression {
         expr: Expression::Column(ColumnExpression { i: 0 }),
-    };
-
-    a
+    }
 }
 
 pub fn max_expression() -> MaxExpression {
-    let a = MaxExpression {
+    MaxExpression {
         expr: Expression::Column(ColumnExpression { i: 0 }),
-    };
+    }
+}
+
+pub fn avg_expression() -> AvgExpression {
+    AvgExpression {
+        expr: Expression::Column(ColumnExpression { i: 0 }),
+    }
+}
+
+pub fn count_expression() -> CountExpression {
+    CountExpression {
+        expr: Expression::Column(ColumnExpression { i: 0 }),
+    }
+}
 
-    a
+pub fn count_distinct_expression() -> CountDistinctExpression {
+    CountDistinctExpression {
+        expr: Expression::Column(ColumnExpression { i: 0 }),
+    }
 }
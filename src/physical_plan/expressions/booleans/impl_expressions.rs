@@ -1,29 +1,41 @@
 use std::sync::Arc;
 
 use crate::datatypes::arrow_field_vector::ArrowFieldVector;
+use crate::error::{Result, UnakiteError};
 use crate::physical_plan::expressions::booleans::BooleanPair;
 
 use crate::physical_plan::expressions::booleans::ColumnVector;
-use arrow::array::AsArray;
-use arrow::compute::and;
+use arrow::array::{ArrayRef, AsArray, BooleanArray};
+use arrow::compute::and_kleene;
+use arrow::compute::kernels::cmp::{distinct, not_distinct};
 // use arrow::compute::kernels::cmp::*;
-use arrow::compute::or;
+use arrow::compute::or_kleene;
 
 pub struct AndPlan;
 
+/** Logical operators only accept boolean operands */
+fn as_boolean(array: &ArrayRef, op: &str) -> Result<BooleanArray> {
+    array.as_boolean_opt().cloned().ok_or_else(|| {
+        UnakiteError::Type(format!(
+            "{} expects boolean operands, got {}",
+            op,
+            array.data_type()
+        ))
+    })
+}
+
 impl BooleanPair for AndPlan {
-    fn evaluate_pair(&self, l: ColumnVector, r: ColumnVector) -> ColumnVector {
-        let vec = and(
-            &l.get_vector().field.as_boolean(),
-            &r.get_vector().field.as_boolean(),
-        )
-        .unwrap();
+    fn evaluate_pair(&self, l: ColumnVector, r: ColumnVector) -> Result<ColumnVector> {
+        let vec = and_kleene(
+            &as_boolean(&l.to_array(), "AND")?,
+            &as_boolean(&r.to_array(), "AND")?,
+        )?;
 
         let coulumn_vec = ColumnVector::ArrowVector(ArrowFieldVector {
             field: Arc::new(vec),
         });
 
-        coulumn_vec
+        Ok(coulumn_vec)
     }
 }
 
@@ -42,18 +54,17 @@ impl std::fmt::Debug for AndPlan {
 pub struct OrPlan;
 
 impl BooleanPair for OrPlan {
-    fn evaluate_pair(&self, l: ColumnVector, r: ColumnVector) -> ColumnVector {
-        let vec = or(
-            &l.get_vector().field.as_boolean(),
-            &r.get_vector().field.as_boolean(),
-        )
-        .unwrap();
+    fn evaluate_pair(&self, l: ColumnVector, r: ColumnVector) -> Result<ColumnVector> {
+        let vec = or_kleene(
+            &as_boolean(&l.to_array(), "OR")?,
+            &as_boolean(&r.to_array(), "OR")?,
+        )?;
 
         let coulumn_vec = ColumnVector::ArrowVector(ArrowFieldVector {
             field: Arc::new(vec),
         });
 
-        coulumn_vec
+        Ok(coulumn_vec)
     }
 }
 
@@ -81,11 +92,11 @@ macro_rules! impl_binary_infix_op {
                     l: ColumnVector,
                     r: ColumnVector,
 
-                ) -> ColumnVector {
+                ) -> Result<ColumnVector> {
                     let coulumn_vec = ColumnVector::ArrowVector(ArrowFieldVector {
-                        field: Arc::new(arrow::compute::kernels::cmp::$cmp_function(&l.get_vector().field, &r.get_vector().field).unwrap())
+                        field: Arc::new(arrow::compute::kernels::cmp::$cmp_function(&l.to_array(), &r.to_array())?)
                     });
-                    coulumn_vec
+                    Ok(coulumn_vec)
                 }
 
             }
@@ -121,3 +132,48 @@ impl_binary_infix_op!(
     (GteqPlan, >=,gt_eq),
 
 );
+
+/** Equality that treats two nulls as equal and never yields null */
+pub struct DistinctPlan;
+
+impl BooleanPair for DistinctPlan {
+    fn evaluate_pair(&self, l: ColumnVector, r: ColumnVector) -> Result<ColumnVector> {
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+            field: Arc::new(distinct(&l.to_array(), &r.to_array())?),
+        }))
+    }
+}
+
+impl std::fmt::Display for DistinctPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "IS DISTINCT FROM")
+    }
+}
+
+impl std::fmt::Debug for DistinctPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "IS DISTINCT FROM ")
+    }
+}
+
+pub struct NotDistinctPlan;
+
+impl BooleanPair for NotDistinctPlan {
+    fn evaluate_pair(&self, l: ColumnVector, r: ColumnVector) -> Result<ColumnVector> {
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+            field: Arc::new(not_distinct(&l.to_array(), &r.to_array())?),
+        }))
+    }
+}
+
+impl std::fmt::Display for NotDistinctPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "IS NOT DISTINCT FROM")
+    }
+}
+
+impl std::fmt::Debug for NotDistinctPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "IS NOT DISTINCT FROM ")
+    }
+}
@@ -5,11 +5,16 @@ use std::{
     sync::Arc,
 };
 
+use arrow::{array::new_null_array, datatypes::DataType};
+
 use crate::{
     datatypes::{
+        arrow_field_vector::ArrowFieldVector,
+        coercion::{coerce_array, comparison_type},
         column_vector::{ColumnVector, ColumnVectorTrait},
         record_batch::RecordBatch,
     },
+    error::{Result, UnakiteError},
     physical_plan::expressions::{
         Expression,
         booleans::impl_expressions::{
@@ -27,29 +32,38 @@ pub struct BooleanExpression {
 }
 
 impl BooleanExpression {
-    pub fn evaluate(&self, input: RecordBatch) -> ColumnVector {
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
         let (l, r) = { (self.get_l(), self.get_r()) };
 
-        let ll = l.evaluate(input.clone());
-        let rr = r.evaluate(input);
-        assert_eq!(ll.size(), rr.size());
-
-        if ll.get_type() != rr.get_type() {
-            let error_msg = format!(
-                "Boolean expression operands do not have the same type: {} != {}",
-                ll.get_type(),
-                rr.get_type()
-            );
-            panic!("{}", error_msg)
+        let ll = l.evaluate(input.clone())?;
+        let rr = r.evaluate(input)?;
+
+        if ll.size() != rr.size() {
+            return Err(UnakiteError::Execution(format!(
+                "Boolean expression operands have different lengths: {} != {}",
+                ll.size(),
+                rr.size()
+            )));
+        }
+
+        let (ll, rr) = null_operands(ll, rr);
+        if ll.get_type() == rr.get_type() {
+            return self.compare(ll, rr);
         }
 
-        self.compare(ll, rr)
+        // Numbers of different types are compared in their promoted type
+        let data_type = comparison_type(&[ll.get_type(), rr.get_type()])?;
+        let coerce = |vector: ColumnVector| -> Result<ColumnVector> {
+            Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+                field: coerce_array(&vector.to_array(), &data_type)?,
+            }))
+        };
+        self.compare(coerce(ll)?, coerce(rr)?)
     }
 
-    pub fn compare(&self, l: ColumnVector, r: ColumnVector) -> ColumnVector {
+    pub fn compare(&self, l: ColumnVector, r: ColumnVector) -> Result<ColumnVector> {
         let expr = { self.inner.clone() };
-        let c: ColumnVector = expr.evaluate_pair(l, r);
-        c
+        expr.evaluate_pair(l, r)
     }
 
     pub fn get_l(&self) -> Arc<Expression> {
@@ -60,8 +74,36 @@ impl BooleanExpression {
     }
 }
 
-pub trait BooleanPair: Debug + Display {
-    fn evaluate_pair(&self, l: ColumnVector, r: ColumnVector) -> ColumnVector;
+impl Display for BooleanExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.l, self.inner.to_string().trim(), self.r)
+    }
+}
+
+/**
+ * A NULL literal has no type of its own, it takes the type of the other operand so that the
+ * comparison yields null rather than a type error. Two NULLs compare as booleans.
+ */
+fn null_operands(l: ColumnVector, r: ColumnVector) -> (ColumnVector, ColumnVector) {
+    let nulls = |data_type: &DataType, size: usize| {
+        ColumnVector::ArrowVector(ArrowFieldVector {
+            field: new_null_array(data_type, size),
+        })
+    };
+
+    match (l.get_type(), r.get_type()) {
+        (DataType::Null, DataType::Null) => (
+            nulls(&DataType::Boolean, l.size()),
+            nulls(&DataType::Boolean, r.size()),
+        ),
+        (DataType::Null, data_type) => (nulls(&data_type, l.size()), r),
+        (data_type, DataType::Null) => (l, nulls(&data_type, r.size())),
+        _ => (l, r),
+    }
+}
+
+pub trait BooleanPair: Debug + Display + Send + Sync {
+    fn evaluate_pair(&self, l: ColumnVector, r: ColumnVector) -> Result<ColumnVector>;
 }
 
 // Helpers
@@ -0,0 +1,120 @@
+use std::{fmt::Display, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef, AsArray, UInt32Array, new_null_array},
+    compute::{filter, interleave, kernels::cmp::eq, not, prep_null_mask_filter},
+    datatypes::{DataType, UInt32Type},
+};
+
+use crate::{
+    datatypes::{
+        arrow_field_vector::ArrowFieldVector,
+        coercion::{coerce_array, common_type, comparison_type},
+        column_vector::ColumnVector,
+        record_batch::RecordBatch,
+    },
+    error::Result,
+    physical_plan::expressions::{Expression, predicates::boolean_values},
+};
+
+/**
+ * CASE evaluated one branch at a time. Each WHEN only sees the rows no earlier branch took and
+ * each THEN only the rows its WHEN matched, so a branch guarded by a condition never fails on the
+ * rows the condition excludes, e.g. `CASE WHEN x = 0 THEN 0 ELSE 10 / x END`.
+ */
+#[derive(Debug)]
+pub struct CaseExpression {
+    pub expr: Option<Arc<Expression>>,
+    pub when_then: Vec<(Expression, Expression)>,
+    pub else_expr: Option<Arc<Expression>>,
+}
+
+impl CaseExpression {
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
+        let row_count = input.row_count();
+
+        // Rows of the input not taken by a branch yet, and their part of the batch
+        let mut rows: ArrayRef = Arc::new(UInt32Array::from_iter_values(0..row_count as u32));
+        let mut rest = input;
+        let mut operand = match &self.expr {
+            Some(expr) => Some(expr.evaluate(rest.clone())?.to_array()),
+            None => None,
+        };
+
+        // The input rows of every branch with the values it produced for them
+        let mut branches: Vec<(ArrayRef, ArrayRef)> = vec![];
+        for (when, then) in &self.when_then {
+            let when = when.evaluate(rest.clone())?.to_array();
+            let matched = match &operand {
+                Some(operand) => {
+                    let data_type =
+                        comparison_type(&[operand.data_type().clone(), when.data_type().clone()])?;
+                    eq(
+                        &coerce_array(operand, &data_type)?,
+                        &coerce_array(&when, &data_type)?,
+                    )?
+                }
+                None => boolean_values(&when, "CASE WHEN")?,
+            };
+            // A null condition does not take the row
+            let matched = match matched.null_count() {
+                0 => matched,
+                _ => prep_null_mask_filter(&matched),
+            };
+
+            let value = then.evaluate(rest.filter(&matched)?)?.to_array();
+            branches.push((filter(&rows, &matched)?, value));
+
+            let unmatched = not(&matched)?;
+            rest = rest.filter(&unmatched)?;
+            rows = filter(&rows, &unmatched)?;
+            operand = operand.map(|it| filter(&it, &unmatched)).transpose()?;
+        }
+
+        let value = match &self.else_expr {
+            Some(else_expr) => else_expr.evaluate(rest)?.to_array(),
+            None => new_null_array(&DataType::Null, rows.len()),
+        };
+        branches.push((rows, value));
+
+        let types: Vec<DataType> = branches
+            .iter()
+            .map(|(_, values)| values.data_type().clone())
+            .collect();
+        let data_type = common_type(&types)?;
+        let values = branches
+            .iter()
+            .map(|(_, values)| coerce_array(values, &data_type))
+            .collect::<Result<Vec<ArrayRef>>>()?;
+
+        // Put every value back at the position of its input row
+        let mut indices = vec![(0, 0); row_count];
+        for (branch, (rows, _)) in branches.iter().enumerate() {
+            let rows = rows.as_primitive::<UInt32Type>();
+            for (i, row) in rows.values().iter().enumerate() {
+                indices[*row as usize] = (branch, i);
+            }
+        }
+        let values: Vec<&dyn Array> = values.iter().map(|it| it.as_ref()).collect();
+
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+            field: interleave(&values, &indices)?,
+        }))
+    }
+}
+
+impl Display for CaseExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CASE")?;
+        if let Some(expr) = &self.expr {
+            write!(f, " {}", expr)?;
+        }
+        for (when, then) in &self.when_then {
+            write!(f, " WHEN {} THEN {}", when, then)?;
+        }
+        if let Some(else_expr) = &self.else_expr {
+            write!(f, " ELSE {}", else_expr)?;
+        }
+        write!(f, " END")
+    }
+}
@@ -1,88 +1,70 @@
 use core::fmt;
 use std::sync::Arc;
 
-use arrow::datatypes::DataType;
+use arrow::{
+    compute::{CastOptions, cast_with_options},
+    datatypes::DataType,
+};
 
 use crate::{
     datatypes::{
-        arrow_field_vector::ArrowFieldVector, column_vector::ColumnVector,
+        arrow_field_vector::ArrowFieldVector,
+        coercion::check_cast,
+        column_vector::{ColumnVector, ColumnVectorTrait},
         record_batch::RecordBatch,
     },
-    physical_plan::expressions::{Expression, column_expressions::ColumnExpression},
+    error::{Result, UnakiteError},
+    physical_plan::expressions::Expression,
 };
 
+/**
+ * Converts the value of an expression to another type with the Arrow cast kernel. A value that
+ * cannot be represented in the target type fails the query, unless the expression is a TRY_CAST
+ * which yields null for it instead.
+ */
+#[derive(Debug)]
 pub struct CastExpression {
-    expr: Expression,
-    data_type: DataType,
+    pub expr: Arc<Expression>,
+    pub data_type: DataType,
+    pub try_cast: bool,
 }
 
 impl fmt::Display for CastExpression {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "CAST({:?} as{}", self.expr, self.data_type)
+        let name = if self.try_cast { "TRY_CAST" } else { "CAST" };
+        write!(f, "{}({} AS {})", name, self.expr, self.data_type)
     }
 }
 
-macro_rules! builded_cast_array {
-    (  $data_type: ident, $value:expr, $(($variant:ident, $rust_type:ty)),* $(,)?  ) => {
-
-        match $data_type {
-
-            $(
-                DataType::$variant => {
-
-                    let vec = &$value.get_vector().field;
-                    let casted = arrow::compute::kernels::cast(vec, &$data_type).unwrap();
-
-                    return ColumnVector::ArrowVector(ArrowFieldVector {
-                                field: Arc::new(casted)
-                    });
-
-
-                }
-            )*,
-            DataType::Utf8 => {
-
-                    let vec = &$value.get_vector().field;
-                    let casted = arrow::compute::kernels::cast(vec, &DataType::Utf8).unwrap();
-
-                    return ColumnVector::ArrowVector(ArrowFieldVector {
-                        field: Arc::new(casted)
-                    });
-
-                }
-
-            _ => todo!(),
-        }
-
-
-    };
-}
-
 impl CastExpression {
-    pub fn new(expr: ColumnExpression, data_type: DataType) -> CastExpression {
+    pub fn new(expr: Expression, data_type: DataType) -> CastExpression {
         CastExpression {
-            expr: Expression::Column(expr),
+            expr: Arc::new(expr),
             data_type,
+            try_cast: false,
         }
     }
-    pub fn evaluate(&self, input: RecordBatch) -> ColumnVector {
-        let value = self.expr.evaluate(input);
 
-        let data_type = self.data_type.clone();
+    pub fn try_cast(expr: Expression, data_type: DataType) -> CastExpression {
+        CastExpression {
+            try_cast: true,
+            ..CastExpression::new(expr, data_type)
+        }
+    }
 
-        builded_cast_array!(
-            data_type,
-            value,
-            (Int8, i8),
-            (Int16, i16),
-            (Int32, i32),
-            (Int64, i64),
-            (UInt8, u8),
-            (UInt16, u16),
-            (UInt32, u32),
-            (UInt64, u64),
-            (Float32, f32),
-            (Float64, f64),
-        )
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
+        let value = self.expr.evaluate(input)?;
+        check_cast(&value.get_type(), &self.data_type)?;
+
+        let options = CastOptions {
+            safe: self.try_cast,
+            ..Default::default()
+        };
+        let casted = cast_with_options(&value.to_array(), &self.data_type, &options)
+            .map_err(|e| UnakiteError::Execution(format!("{} failed: {}", self, e)))?;
+
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+            field: casted,
+        }))
     }
 }
@@ -1,6 +1,9 @@
 use std::fmt;
 
-use crate::datatypes::{column_vector::ColumnVector, record_batch::RecordBatch};
+use crate::{
+    datatypes::{column_vector::ColumnVector, record_batch::RecordBatch},
+    error::{Result, UnakiteError},
+};
 
 #[derive(Debug, Clone, Copy)]
 pub struct ColumnExpression {
@@ -8,8 +11,14 @@ pub struct ColumnExpression {
 }
 
 impl ColumnExpression {
-    pub fn evaluate(&self, input: RecordBatch) -> ColumnVector {
-        return input.field(self.i);
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
+        input.fields.get(self.i).cloned().ok_or_else(|| {
+            UnakiteError::Execution(format!(
+                "Column index {} is out of bounds for a batch of {} columns",
+                self.i,
+                input.column_count()
+            ))
+        })
     }
 }
 
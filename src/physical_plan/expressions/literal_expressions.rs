@@ -10,14 +10,45 @@ pub struct LiteralStringExpression {
     value: String,
 }
 impl LiteralStringExpression {
+    pub fn new(value: String) -> Self {
+        Self { value }
+    }
+
+
     pub fn evaluate(&self, input: RecordBatch) -> ColumnVector {
-        return ColumnVector::Literal(Arc::new(LiteralValueVector {
+        ColumnVector::Literal(Arc::new(LiteralValueVector {
             arrow_type: arrow::datatypes::DataType::Utf8,
             value: Some(crate::datatypes::value::ArrowValue::StringType(
                 self.value.clone(),
             )),
             size: input.row_count(),
-        }));
+        }))
+    }
+}
+
+impl std::fmt::Display for LiteralStringExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}'", self.value)
+    }
+}
+
+/** The NULL literal, a column of nulls of type Null until it meets a typed operand */
+#[derive(Debug, Clone, Copy)]
+pub struct LiteralNullExpression;
+
+impl LiteralNullExpression {
+    pub fn evaluate(&self, input: RecordBatch) -> ColumnVector {
+        ColumnVector::Literal(Arc::new(LiteralValueVector {
+            arrow_type: arrow::datatypes::DataType::Null,
+            value: None,
+            size: input.row_count(),
+        }))
+    }
+}
+
+impl std::fmt::Display for LiteralNullExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NULL")
     }
 }
 
@@ -35,6 +66,10 @@ macro_rules! impl_literal_expression {
             }
 
             impl $struct_name {
+                pub fn new(value: $dt) -> Self {
+                    Self { value }
+                }
+
                 pub fn evaluate(&self, input: RecordBatch) -> ColumnVector {
                     return ColumnVector::Literal(Arc::new(LiteralValueVector {
                         arrow_type: arrow::datatypes::DataType::$data_type_variant,
@@ -43,6 +78,12 @@ macro_rules! impl_literal_expression {
                     }));
                 }
             }
+
+            impl std::fmt::Display for $struct_name {
+                fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                    write!(f, "{:?}", self.value)
+                }
+            }
         )*
     };
 }
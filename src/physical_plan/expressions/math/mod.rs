@@ -1,88 +1,135 @@
-// pub mod math_expression;
-
-// use core::fmt;
-// use std::sync::Arc;
-
-// use arrow::datatypes::DataType;
-
-// use crate::{
-//     datatypes::{
-//         arrow_vector_builder::ArrowVectorBuilder,
-//         column_vector::{ColumnVector, ColumnVectorTrait},
-//         value::ArrowValue,
-//     },
-//     physical_plan::expressions::{
-//         Expression,
-//         math::math_expression::{
-//             AddExpression, DivideExpression, MultiplyExpression, SubtractExpression,
-//         },
-//     },
-// };
-
-// pub struct BinaryExpression {
-//     inner: Arc<dyn MathExpr>,
-//     l: Arc<Expression>,
-//     r: Arc<Expression>,
-// }
-
-// impl BinaryExpression {
-
-// }
-
-// impl BinaryExpression {
-//     pub fn get_l(&self) -> Arc<Expression> {
-//         self.l.clone()
-//     }
-//     pub fn get_r(&self) -> Arc<Expression> {
-//         self.r.clone()
-//     }
-// }
-
-// impl fmt::Display for BinaryExpression {
-//     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-//         write!(f, "{:?} {} {:?}", self.l, self.inner, self.r)
-//     }
-// }
-
-// pub trait MathExpr: fmt::Display + fmt::Debug {
-//     fn evaluate(&self, l: ColumnVector, r: ColumnVector) -> ColumnVector {
-//         let mut builder = ArrowVectorBuilder::new(l.get_type());
-
-//         for i in 0..l.size() {
-//             let value = self.evaluate_pair(l.get_value(i), r.get_value(i), l.get_type());
-//             builder.set(i, value);
-//         }
-
-//         builder.build()
-//     }
-
-//     fn evaluate_pair(
-//         &self,
-//         _l: Option<ArrowValue>,
-//         _r: Option<ArrowValue>,
-//         arrow_type: DataType,
-//     ) -> Option<ArrowValue>;
-// }
-
-// // Helpers to create physical math expressions
-// macro_rules! impl_phys_math_expr {
-//     ($ (($func_name:ident,$variant:ident)),* $(,)?  ) => {
-
-//         $(
-//             pub fn $func_name(l: Expression, r: Expression) -> BinaryExpression {
-//                 BinaryExpression {
-//                     inner: Arc::new($variant),
-//                     l: Arc::new(l),
-//                     r: Arc::new(r),
-//                 }
-//             }
-//         )*
-//     };
-// }
-
-// impl_phys_math_expr!(
-//     (add_expr, AddExpression),
-//     (sub_expr, SubtractExpression),
-//     (mul_expr, MultiplyExpression),
-//     (div_expr, DivideExpression),
-// );
+pub mod math_expression;
+
+use std::{
+    fmt::{Debug, Display},
+    sync::Arc,
+};
+
+use arrow::{
+    array::{ArrayRef, Datum, Scalar},
+    compute::{cast, kernels::numeric::neg},
+    datatypes::DataType,
+    error::ArrowError,
+};
+
+use crate::{
+    datatypes::{
+        arrow_field_vector::ArrowFieldVector,
+        coercion::numeric_promotion,
+        column_vector::{ColumnVector, ColumnVectorTrait},
+        record_batch::RecordBatch,
+    },
+    error::{Result, UnakiteError},
+    physical_plan::expressions::Expression,
+};
+
+/**
+ * Binary arithmetic evaluated with the Arrow kernels. Both operands are converted to their
+ * promoted type first, a literal operand is applied to every row as a scalar without being
+ * materialized. Integer overflow and integer division by zero fail the query, floating point
+ * division by zero follows IEEE 754.
+ */
+#[derive(Debug)]
+pub struct MathExpression {
+    pub inner: Arc<dyn MathPair>,
+    pub l: Arc<Expression>,
+    pub r: Arc<Expression>,
+}
+
+impl MathExpression {
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
+        let l = self.l.evaluate(input.clone())?;
+        let r = self.r.evaluate(input)?;
+
+        if l.size() != r.size() {
+            return Err(UnakiteError::Execution(format!(
+                "Math expression operands have different lengths: {} != {}",
+                l.size(),
+                r.size()
+            )));
+        }
+
+        let data_type = numeric_promotion(&l.get_type(), &r.get_type())?;
+
+        // Two literals still produce one value per row
+        let r_scalar = matches!(r, ColumnVector::Literal(_));
+        let l_scalar = matches!(l, ColumnVector::Literal(_)) && !r_scalar;
+
+        let l = operand(&l, &data_type, l_scalar)?;
+        let r = operand(&r, &data_type, r_scalar)?;
+
+        let result = self
+            .inner
+            .evaluate_pair(l.as_ref(), r.as_ref())
+            .map_err(|e| match e {
+                ArrowError::DivideByZero => {
+                    UnakiteError::Execution(format!("Division by zero in {}", self))
+                }
+                ArrowError::ArithmeticOverflow(msg) => {
+                    UnakiteError::Execution(format!("{} in {}", msg, self))
+                }
+                e => UnakiteError::Arrow(e),
+            })?;
+
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+            field: result,
+        }))
+    }
+}
+
+/** The values of an operand as the promoted type, a literal becomes a single value scalar */
+fn operand(vector: &ColumnVector, data_type: &DataType, scalar: bool) -> Result<Box<dyn Datum>> {
+    let array = match scalar {
+        true => vector.slice(0, 1).to_array(),
+        false => vector.to_array(),
+    };
+    let array = match array.data_type() == data_type {
+        true => array,
+        false => cast(&array, data_type)?,
+    };
+
+    Ok(match scalar {
+        true => Box::new(Scalar::new(array)),
+        false => Box::new(array),
+    })
+}
+
+impl Display for MathExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.l, self.inner, self.r)
+    }
+}
+
+/** Numeric negation, negating the minimum of a signed integer type fails the query */
+#[derive(Debug)]
+pub struct NegativeExpression {
+    pub expr: Arc<Expression>,
+}
+
+impl NegativeExpression {
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
+        let value = self.expr.evaluate(input)?.to_array();
+        if value.data_type() == &DataType::Null {
+            return Ok(ColumnVector::ArrowVector(ArrowFieldVector { field: value }));
+        }
+
+        let result = neg(&value).map_err(|e| match e {
+            ArrowError::ArithmeticOverflow(msg) => {
+                UnakiteError::Execution(format!("{} in {}", msg, self))
+            }
+            e => UnakiteError::Arrow(e),
+        })?;
+
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector { field: result }))
+    }
+}
+
+impl Display for NegativeExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "-{}", self.expr)
+    }
+}
+
+pub trait MathPair: Debug + Display + Send + Sync {
+    fn evaluate_pair(&self, l: &dyn Datum, r: &dyn Datum) -> Result<ArrayRef, ArrowError>;
+}
@@ -1,20 +1,33 @@
 pub mod aggregates;
 pub mod booleans;
+pub mod case_expression;
 pub mod cast_expression;
 pub mod column_expressions;
 pub mod literal_expressions;
 pub mod math;
-pub mod unary_expression;
+pub mod nulls;
+pub mod predicates;
+pub mod scalar_function;
 
 use crate::{
     datatypes::{
-        column_vector::ColumnVector, concrete_type::ConcreteType, record_batch::RecordBatch,
+        column_vector::ColumnVector, record_batch::RecordBatch,
     },
+    error::{Result, UnakiteError},
     physical_plan::expressions::{
-        booleans::BooleanExpression, column_expressions::ColumnExpression, literal_expressions::*,
+        booleans::BooleanExpression, case_expression::CaseExpression,
+        cast_expression::CastExpression,
+        column_expressions::ColumnExpression, literal_expressions::*,
+        math::{MathExpression, NegativeExpression},
+        nulls::{CoalesceExpression, IsNullExpression, NullIfExpression},
+        predicates::{BetweenExpression, InListExpression, NotExpression},
+        scalar_function::ScalarFunctionExpression,
     },
 };
-use std::{fmt::Debug, sync::Arc};
+use std::{
+    fmt::{self, Debug},
+    sync::Arc,
+};
 
 
 
@@ -39,9 +52,18 @@ pub enum Expression {
     Literal(LiteralExpression),
     Boolean(Arc<BooleanExpression>),
     Column(ColumnExpression),
+    Math(Arc<MathExpression>),
     // Aggregations(Arc<dyn AggregateExpression>),
-    Cast,
-    Unary,
+    Cast(Arc<CastExpression>),
+    ScalarFunction(Arc<ScalarFunctionExpression>),
+    IsNull(Arc<IsNullExpression>),
+    Coalesce(Arc<CoalesceExpression>),
+    NullIf(Arc<NullIfExpression>),
+    Not(Arc<NotExpression>),
+    Negative(Arc<NegativeExpression>),
+    Case(Arc<CaseExpression>),
+    InList(Arc<InListExpression>),
+    Between(Arc<BetweenExpression>),
 }
 
 
@@ -52,7 +74,7 @@ pub enum LiteralExpression {
     Int8(LiteralSmallExpression),
     Int16(LiteralShortExpression),
     Int32(LiteralIntExpression),
-    Int64(LiteralULongExpression),
+    Int64(LiteralLongExpression),
     UInt8(LiteralUSmallExpression),
     UInt16(LiteralUShortExpression),
     UInt32(LiteralUIntExpression),
@@ -60,6 +82,7 @@ pub enum LiteralExpression {
     Float32(LiteralFloatExpression),
     Float64(LiteralDoubleExpression),
     String(LiteralStringExpression),
+    Null(LiteralNullExpression),
 }
 
 impl LiteralExpression {
@@ -77,34 +100,86 @@ impl LiteralExpression {
             Float32(expr) => expr.evaluate(input),
             Float64(expr) => expr.evaluate(input),
             String(expr) => expr.evaluate(input),
+            Null(expr) => expr.evaluate(input),
         }
     }
 }
 
 impl Expression {
     /// Evaluate the expression against an input record batch and produce a column of data as output
-    pub fn evaluate(&self, input: RecordBatch) -> ColumnVector {
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
         use Expression::*;
         match self {
             Boolean(expr) => expr.evaluate(input),
             Column(expr) => expr.evaluate(input),
-            Literal(expr) => expr.evaluate(input),
+            Math(expr) => expr.evaluate(input),
+            Literal(expr) => Ok(expr.evaluate(input)),
             // Aggregations(expr) => expr.input_expression().evaluate(input),
-            Unary => todo!("Unary expressions not yet implemented"),
-            Cast => todo!("Cast expressions not yet implemented"),
+            ScalarFunction(expr) => expr.evaluate(input),
+            Cast(expr) => expr.evaluate(input),
+            IsNull(expr) => expr.evaluate(input),
+            Coalesce(expr) => expr.evaluate(input),
+            NullIf(expr) => expr.evaluate(input),
+            Not(expr) => expr.evaluate(input),
+            Negative(expr) => expr.evaluate(input),
+            Case(expr) => expr.evaluate(input),
+            InList(expr) => expr.evaluate(input),
+            Between(expr) => expr.evaluate(input),
+        }
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expression::Literal(expr) => write!(f, "{}", expr),
+            Expression::Boolean(expr) => write!(f, "{}", expr),
+            Expression::Column(expr) => write!(f, "#{}", expr),
+            Expression::Math(expr) => write!(f, "{}", expr),
+            Expression::Cast(expr) => write!(f, "{}", expr),
+            Expression::ScalarFunction(expr) => write!(f, "{}", expr),
+            Expression::IsNull(expr) => write!(f, "{}", expr),
+            Expression::Coalesce(expr) => write!(f, "{}", expr),
+            Expression::NullIf(expr) => write!(f, "{}", expr),
+            Expression::Not(expr) => write!(f, "{}", expr),
+            Expression::Negative(expr) => write!(f, "{}", expr),
+            Expression::Case(expr) => write!(f, "{}", expr),
+            Expression::InList(expr) => write!(f, "{}", expr),
+            Expression::Between(expr) => write!(f, "{}", expr),
+        }
+    }
+}
+
+impl fmt::Display for LiteralExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use LiteralExpression::*;
+        match self {
+            Int8(expr) => write!(f, "{}", expr),
+            Int16(expr) => write!(f, "{}", expr),
+            Int32(expr) => write!(f, "{}", expr),
+            Int64(expr) => write!(f, "{}", expr),
+            UInt8(expr) => write!(f, "{}", expr),
+            UInt16(expr) => write!(f, "{}", expr),
+            UInt32(expr) => write!(f, "{}", expr),
+            UInt64(expr) => write!(f, "{}", expr),
+            Float32(expr) => write!(f, "{}", expr),
+            Float64(expr) => write!(f, "{}", expr),
+            String(expr) => write!(f, "{}", expr),
+            Null(expr) => write!(f, "{}", expr),
         }
     }
 }
 
-impl ConcreteType for Expression {
-    fn get_conc_type(&self) -> arrow::datatypes::DataType {
+impl Expression {
+    /** Data type of an expression whose type does not depend on its input */
+    pub fn get_conc_type(&self) -> Result<arrow::datatypes::DataType> {
         use Expression::*;
         use LiteralExpression::*;
         use arrow::datatypes::DataType;
 
-        match self {
-            Boolean(_) => DataType::Boolean,
-            Unary => DataType::Float64, // TODO: This should depend on the actual unary operation. The only unary operations to be be added will yield the double/float64 type
+        Ok(match self {
+            Boolean(_) | IsNull(_) | Not(_) | InList(_) | Between(_) => DataType::Boolean,
+            Cast(expr) => expr.data_type.clone(),
             Literal(literal) => match literal {
                 Int8(_) => DataType::Int8,
                 Int16(_) => DataType::Int16,
@@ -117,10 +192,16 @@ impl ConcreteType for Expression {
                 Float32(_) => DataType::Float32,
                 Float64(_) => DataType::Float64,
                 String(_) => DataType::Utf8,
+                Null(_) => DataType::Null,
             },
 
             // Aggregations(expr) => expr.input_expression().get_conc_type(),
-            _ => panic!("Expression does not yeild a constant type"),
-        }
+            _ => {
+                return Err(UnakiteError::Type(format!(
+                    "Expression {} does not yeild a constant type",
+                    self
+                )));
+            }
+        })
     }
 }
@@ -0,0 +1,128 @@
+use std::{fmt::Display, sync::Arc};
+
+use arrow::{
+    compute::{is_not_null, is_null, kernels::cmp::eq, kernels::zip::zip, nullif},
+    datatypes::DataType,
+};
+
+use crate::{
+    datatypes::{
+        arrow_field_vector::ArrowFieldVector,
+        coercion::{coerce_array, common_type},
+        column_vector::{ColumnVector, ColumnVectorTrait},
+        record_batch::RecordBatch,
+    },
+    error::{Result, UnakiteError},
+    physical_plan::expressions::Expression,
+};
+
+/** IS NULL, or IS NOT NULL when negated */
+#[derive(Debug)]
+pub struct IsNullExpression {
+    pub expr: Arc<Expression>,
+    pub negated: bool,
+}
+
+impl IsNullExpression {
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
+        let value = self.expr.evaluate(input)?.to_array();
+        let result = match self.negated {
+            true => is_not_null(&value)?,
+            false => is_null(&value)?,
+        };
+
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+            field: Arc::new(result),
+        }))
+    }
+}
+
+impl Display for IsNullExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let not = if self.negated { "NOT " } else { "" };
+        write!(f, "{} IS {}NULL", self.expr, not)
+    }
+}
+
+/** The first argument that is not null, in the common type of all arguments */
+#[derive(Debug)]
+pub struct CoalesceExpression {
+    pub args: Vec<Expression>,
+}
+
+impl CoalesceExpression {
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
+        let args = self
+            .args
+            .iter()
+            .map(|it| it.evaluate(input.clone()))
+            .collect::<Result<Vec<ColumnVector>>>()?;
+        let arg_types: Vec<DataType> = args.iter().map(|it| it.get_type()).collect();
+        let data_type = common_type(&arg_types)?;
+
+        let Some((first, rest)) = args.split_first() else {
+            return Err(UnakiteError::Execution(
+                "COALESCE expects at least one argument".to_string(),
+            ));
+        };
+
+        let mut result = coerce_array(&first.to_array(), &data_type)?;
+        for arg in rest {
+            if result.null_count() == 0 {
+                break;
+            }
+            let next = coerce_array(&arg.to_array(), &data_type)?;
+            result = zip(&is_not_null(&result)?, &result, &next)?;
+        }
+
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+            field: result,
+        }))
+    }
+}
+
+impl Display for CoalesceExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let args = self
+            .args
+            .iter()
+            .map(|it| it.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "COALESCE({})", args)
+    }
+}
+
+/** Null where `l` equals `r`, the value of `l` otherwise */
+#[derive(Debug)]
+pub struct NullIfExpression {
+    pub l: Arc<Expression>,
+    pub r: Arc<Expression>,
+}
+
+impl NullIfExpression {
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
+        let l = self.l.evaluate(input.clone())?;
+        let r = self.r.evaluate(input)?;
+
+        let data_type = common_type(&[l.get_type(), r.get_type()])?;
+        let l = l.to_array();
+        if data_type == DataType::Null {
+            return Ok(ColumnVector::ArrowVector(ArrowFieldVector { field: l }));
+        }
+
+        let equal = eq(
+            &coerce_array(&l, &data_type)?,
+            &coerce_array(&r.to_array(), &data_type)?,
+        )?;
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+            field: nullif(&l, &equal)?,
+        }))
+    }
+}
+
+impl Display for NullIfExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NULLIF({}, {})", self.l, self.r)
+    }
+}
@@ -0,0 +1,161 @@
+use std::{fmt::Display, sync::Arc};
+
+use arrow::{
+    array::{ArrayRef, AsArray, BooleanArray},
+    compute::{
+        and_kleene,
+        kernels::cmp::{eq, gt_eq, lt_eq},
+        not, or_kleene,
+    },
+    datatypes::DataType,
+};
+
+use crate::{
+    datatypes::{
+        arrow_field_vector::ArrowFieldVector,
+        coercion::{coerce_array, comparison_type},
+        column_vector::ColumnVector,
+        record_batch::RecordBatch,
+    },
+    error::{Result, UnakiteError},
+    physical_plan::expressions::Expression,
+};
+
+/** Logical NOT, null stays null */
+#[derive(Debug)]
+pub struct NotExpression {
+    pub expr: Arc<Expression>,
+}
+
+impl NotExpression {
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
+        let value = boolean_values(&self.expr.evaluate(input)?.to_array(), "NOT")?;
+
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+            field: Arc::new(not(&value)?),
+        }))
+    }
+}
+
+impl Display for NotExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NOT {}", self.expr)
+    }
+}
+
+/**
+ * `expr IN (list)` as a disjunction of equalities, so a row that matches no value but meets a
+ * null in the list is null rather than false
+ */
+#[derive(Debug)]
+pub struct InListExpression {
+    pub expr: Arc<Expression>,
+    pub list: Vec<Expression>,
+    pub negated: bool,
+}
+
+impl InListExpression {
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
+        let value = self.expr.evaluate(input.clone())?.to_array();
+        let list = self
+            .list
+            .iter()
+            .map(|it| Ok(it.evaluate(input.clone())?.to_array()))
+            .collect::<Result<Vec<ArrayRef>>>()?;
+
+        let types: Vec<DataType> = std::iter::once(&value)
+            .chain(&list)
+            .map(|it| it.data_type().clone())
+            .collect();
+        let data_type = comparison_type(&types)?;
+        let value = coerce_array(&value, &data_type)?;
+
+        let mut found = BooleanArray::from(vec![false; value.len()]);
+        for item in &list {
+            let equal = eq(&value, &coerce_array(item, &data_type)?)?;
+            found = or_kleene(&found, &equal)?;
+        }
+        if self.negated {
+            found = not(&found)?;
+        }
+
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+            field: Arc::new(found),
+        }))
+    }
+}
+
+impl Display for InListExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let list = self
+            .list
+            .iter()
+            .map(|it| it.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let not = if self.negated { "NOT " } else { "" };
+        write!(f, "{} {}IN ({})", self.expr, not, list)
+    }
+}
+
+/** `low <= expr AND expr <= high` with both sides compared in their common type */
+#[derive(Debug)]
+pub struct BetweenExpression {
+    pub expr: Arc<Expression>,
+    pub low: Arc<Expression>,
+    pub high: Arc<Expression>,
+    pub negated: bool,
+}
+
+impl BetweenExpression {
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
+        let value = self.expr.evaluate(input.clone())?.to_array();
+        let low = self.low.evaluate(input.clone())?.to_array();
+        let high = self.high.evaluate(input)?.to_array();
+
+        let data_type = comparison_type(&[
+            value.data_type().clone(),
+            low.data_type().clone(),
+            high.data_type().clone(),
+        ])?;
+        let value = coerce_array(&value, &data_type)?;
+
+        let mut between = and_kleene(
+            &gt_eq(&value, &coerce_array(&low, &data_type)?)?,
+            &lt_eq(&value, &coerce_array(&high, &data_type)?)?,
+        )?;
+        if self.negated {
+            between = not(&between)?;
+        }
+
+        Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+            field: Arc::new(between),
+        }))
+    }
+}
+
+impl Display for BetweenExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let not = if self.negated { "NOT " } else { "" };
+        write!(
+            f,
+            "{} {}BETWEEN {} AND {}",
+            self.expr, not, self.low, self.high
+        )
+    }
+}
+
+/** The values of a boolean operand of `op`, a NULL literal is a column of null booleans */
+pub fn boolean_values(array: &ArrayRef, op: &str) -> Result<BooleanArray> {
+    let array = match array.data_type() {
+        DataType::Null => coerce_array(array, &DataType::Boolean)?,
+        _ => array.clone(),
+    };
+    array.as_boolean_opt().cloned().ok_or_else(|| {
+        UnakiteError::Type(format!(
+            "{} expects boolean operands, got {}",
+            op,
+            array.data_type()
+        ))
+    })
+}
@@ -0,0 +1,49 @@
+use std::fmt::Display;
+
+use crate::{
+    datatypes::{
+        column_vector::{ColumnVector, ColumnVectorTrait},
+        record_batch::RecordBatch,
+    },
+    error::{Result, UnakiteError},
+    functions::BuiltinFunction,
+    physical_plan::expressions::Expression,
+};
+
+/** Call of a built-in function, evaluated a whole batch of arguments at a time */
+#[derive(Debug)]
+pub struct ScalarFunctionExpression {
+    pub fun: BuiltinFunction,
+    pub args: Vec<Expression>,
+}
+
+impl ScalarFunctionExpression {
+    pub fn evaluate(&self, input: RecordBatch) -> Result<ColumnVector> {
+        let args = self
+            .args
+            .iter()
+            .map(|it| it.evaluate(input.clone()))
+            .collect::<Result<Vec<ColumnVector>>>()?;
+
+        if args.iter().any(|it| it.size() != input.row_count()) {
+            return Err(UnakiteError::Execution(format!(
+                "Arguments of {} do not have one value per row",
+                self
+            )));
+        }
+
+        self.fun.evaluate(&args)
+    }
+}
+
+impl Display for ScalarFunctionExpression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let args = self
+            .args
+            .iter()
+            .map(|it| it.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(f, "{}({})", self.fun, args)
+    }
+}
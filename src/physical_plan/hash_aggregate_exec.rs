@@ -0,0 +1,261 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef, UInt32Array, new_null_array},
+    compute::{concat, kernels::take::take},
+    row::{OwnedRow, RowConverter, SortField},
+};
+
+use crate::{
+    datatypes::{
+        arrow_field_vector::ArrowFieldVector, column_vector::ColumnVector,
+        concrete_type::ConcreteType, record_batch::RecordBatch, schema::Schema,
+    },
+    error::{Result, UnakiteError},
+    physical_plan::{
+        PhysPlanTrait, PhysicaPlan, RecordBatchStream,
+        expressions::{
+            Expression,
+            aggregates::{Accumulator, AggregateExpression},
+        },
+    },
+};
+
+pub struct HashAggregateExec {
+    input: Arc<PhysicaPlan>,
+    group_expr: Vec<Expression>,
+    aggregate_expr: Vec<Arc<dyn AggregateExpression>>,
+    schema: Schema,
+}
+
+impl HashAggregateExec {
+    pub fn new(
+        input: Arc<PhysicaPlan>,
+        group_expr: Vec<Expression>,
+        aggregate_expr: Vec<Arc<dyn AggregateExpression>>,
+        schema: Schema,
+    ) -> Self {
+        Self {
+            input,
+            group_expr,
+            aggregate_expr,
+            schema,
+        }
+    }
+}
+
+impl PhysPlanTrait for HashAggregateExec {
+    fn schema(&self) -> Schema {
+        self.schema.clone()
+    }
+
+    fn children(&self) -> Vec<Arc<PhysicaPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn execute(&self) -> Result<RecordBatchStream> {
+        let input = self.input.execute()?;
+        let group_expr = self.group_expr.clone();
+        let aggregate_expr = self.aggregate_expr.clone();
+        let schema = self.schema.clone();
+
+        Ok(Box::new(std::iter::once_with(move || {
+            let mut state = GroupedAggregation::new(&schema, &group_expr, &aggregate_expr)?;
+
+            for batch in input {
+                state.update(&batch?)?;
+            }
+
+            state.finish()
+        })))
+    }
+}
+
+impl fmt::Display for HashAggregateExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let group_expr: Vec<String> = self.group_expr.iter().map(|it| it.to_string()).collect();
+        let aggregate_expr: Vec<String> = self
+            .aggregate_expr
+            .iter()
+            .map(|it| it.to_string())
+            .collect();
+
+        write!(
+            f,
+            "HashAggregateExec: groupExpr={:?}, aggrExpr={:?}",
+            group_expr, aggregate_expr
+        )
+    }
+}
+
+/**
+ * Accumulator state for a single execution of a hash aggregate. Group keys are encoded with the
+ * Arrow row format so that composite, string and floating point keys hash uniformly.
+ */
+struct GroupedAggregation<'a> {
+    schema: &'a Schema,
+    group_expr: &'a [Expression],
+    aggregate_expr: &'a [Arc<dyn AggregateExpression>],
+
+    converter: RowConverter,
+    group_index: HashMap<OwnedRow, usize>,
+    group_keys: Vec<OwnedRow>,
+    accumulators: Vec<Vec<Box<dyn Accumulator>>>,
+}
+
+impl<'a> GroupedAggregation<'a> {
+    fn new(
+        schema: &'a Schema,
+        group_expr: &'a [Expression],
+        aggregate_expr: &'a [Arc<dyn AggregateExpression>],
+    ) -> Result<Self> {
+        let sort_fields: Vec<SortField> = schema.fields[..group_expr.len()]
+            .iter()
+            .map(|it| SortField::new(it.data_type.clone()))
+            .collect();
+
+        Ok(Self {
+            schema,
+            group_expr,
+            aggregate_expr,
+            converter: RowConverter::new(sort_fields)?,
+            group_index: HashMap::new(),
+            group_keys: vec![],
+            accumulators: vec![],
+        })
+    }
+
+    fn update(&mut self, batch: &RecordBatch) -> Result<()> {
+        let row_count = batch.row_count();
+
+        let group_columns: Vec<ArrayRef> = self
+            .group_expr
+            .iter()
+            .map(|it| Ok(it.evaluate(batch.clone())?.to_array()))
+            .collect::<Result<_>>()?;
+
+        let aggregate_columns: Vec<ArrayRef> = self
+            .aggregate_expr
+            .iter()
+            .map(|it| Ok(it.input_expression().evaluate(batch.clone())?.to_array()))
+            .collect::<Result<_>>()?;
+
+        // Row indices of this batch, bucketed by the group they belong to
+        let mut batch_groups: Vec<(usize, Vec<u32>)> = vec![];
+        let mut batch_group_index: HashMap<usize, usize> = HashMap::new();
+
+        let rows = if self.group_expr.is_empty() {
+            None
+        } else {
+            Some(self.converter.convert_columns(&group_columns)?)
+        };
+
+        for i in 0..row_count {
+            let group = self.group_for(rows.as_ref().map(|it| it.row(i).owned()));
+
+            let slot = *batch_group_index.entry(group).or_insert_with(|| {
+                batch_groups.push((group, vec![]));
+                batch_groups.len() - 1
+            });
+            batch_groups[slot].1.push(i as u32);
+        }
+
+        for (group, indices) in batch_groups {
+            let indices = UInt32Array::from(indices);
+
+            for (accumulator, column) in self.accumulators[group]
+                .iter_mut()
+                .zip(aggregate_columns.iter())
+            {
+                let values = ColumnVector::ArrowVector(ArrowFieldVector {
+                    field: take(column.as_ref(), &indices, None)?,
+                });
+                accumulator.update(&values)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /** Index of the group for the given key, creating the group on first sight */
+    fn group_for(&mut self, key: Option<OwnedRow>) -> usize {
+        let key = match key {
+            Some(key) => key,
+            None => {
+                // Without grouping expressions every row belongs to one global group
+                if self.accumulators.is_empty() {
+                    self.accumulators.push(self.create_accumulators());
+                }
+                return 0;
+            }
+        };
+
+        if let Some(group) = self.group_index.get(&key) {
+            return *group;
+        }
+
+        let group = self.accumulators.len();
+        self.group_index.insert(key.clone(), group);
+        self.group_keys.push(key);
+        self.accumulators.push(self.create_accumulators());
+        group
+    }
+
+    fn create_accumulators(&self) -> Vec<Box<dyn Accumulator>> {
+        self.aggregate_expr
+            .iter()
+            .map(|it| it.create_accumulator())
+            .collect()
+    }
+
+    fn finish(mut self) -> Result<RecordBatch> {
+        // A global aggregate yields one row even when the input is empty
+        if self.group_expr.is_empty() && self.accumulators.is_empty() {
+            self.accumulators.push(self.create_accumulators());
+        }
+
+        let mut fields: Vec<ColumnVector> = vec![];
+
+        if !self.group_expr.is_empty() {
+            let group_columns = self
+                .converter
+                .convert_rows(self.group_keys.iter().map(|it| it.row()))?;
+
+            for field in group_columns {
+                fields.push(ColumnVector::ArrowVector(ArrowFieldVector { field }));
+            }
+        }
+
+        let aggregate_fields = &self.schema.fields[self.group_expr.len()..];
+
+        for (i, field) in aggregate_fields.iter().enumerate() {
+            // Groups without a value contribute a null of the output type
+            let values: Vec<ArrayRef> = self
+                .accumulators
+                .iter()
+                .map(|accumulators| match accumulators[i].final_value() {
+                    Some(value) if value.get_conc_type() == field.data_type => Ok(value.to_array()),
+                    Some(value) => Err(UnakiteError::Type(format!(
+                        "aggregate {} produced {} for a column of type {}",
+                        field.name, value, field.data_type
+                    ))),
+                    None => Ok(new_null_array(&field.data_type, 1)),
+                })
+                .collect::<Result<_>>()?;
+
+            let field = if values.is_empty() {
+                new_null_array(&field.data_type, 0)
+            } else {
+                let values: Vec<&dyn Array> = values.iter().map(|it| it.as_ref()).collect();
+                concat(&values)?
+            };
+
+            fields.push(ColumnVector::ArrowVector(ArrowFieldVector { field }));
+        }
+
+        Ok(RecordBatch {
+            schema: self.schema.clone(),
+            fields,
+        })
+    }
+}
@@ -0,0 +1,340 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef, BooleanBufferBuilder, UInt32Array, new_empty_array},
+    compute::{concat, kernels::take::take},
+    datatypes::DataType,
+    row::{OwnedRow, RowConverter, SortField},
+};
+
+use crate::{
+    datatypes::{
+        arrow_field_vector::ArrowFieldVector, coercion::coerce_array, column_vector::ColumnVector,
+        record_batch::RecordBatch, schema::Schema,
+    },
+    error::Result,
+    logical_plan::join::JoinType,
+    physical_plan::{PhysPlanTrait, PhysicaPlan, RecordBatchStream},
+};
+
+/**
+ * Equi-join that builds a hash table over the left input and probes it with batches of the
+ * right input. Output rows are the left columns followed by the right columns.
+ */
+pub struct HashJoinExec {
+    left: Arc<PhysicaPlan>,
+    right: Arc<PhysicaPlan>,
+    join_type: JoinType,
+    on: Vec<(usize, usize)>,
+    /** The common type the keys of each pair are compared in */
+    key_types: Vec<DataType>,
+    schema: Schema,
+}
+
+impl HashJoinExec {
+    pub fn new(
+        left: Arc<PhysicaPlan>,
+        right: Arc<PhysicaPlan>,
+        join_type: JoinType,
+        on: Vec<(usize, usize)>,
+        key_types: Vec<DataType>,
+        schema: Schema,
+    ) -> Self {
+        Self {
+            left,
+            right,
+            join_type,
+            on,
+            key_types,
+            schema,
+        }
+    }
+}
+
+impl PhysPlanTrait for HashJoinExec {
+    fn schema(&self) -> Schema {
+        self.schema.clone()
+    }
+
+    fn children(&self) -> Vec<Arc<PhysicaPlan>> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    fn execute(&self) -> Result<RecordBatchStream> {
+        Ok(Box::new(HashJoinStream {
+            left: self.left.clone(),
+            probe: self.right.execute()?,
+            join_type: self.join_type.clone(),
+            on: self.on.clone(),
+            key_types: self.key_types.clone(),
+            schema: self.schema.clone(),
+            build: None,
+            finished: false,
+        }))
+    }
+}
+
+impl fmt::Display for HashJoinExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "HashJoinExec: joinType={:?}, on={:?}",
+            self.join_type, self.on
+        )
+    }
+}
+
+/** Build side of the join, materialized on the first poll of the stream */
+struct BuildSide {
+    columns: Vec<ArrayRef>,
+    converter: RowConverter,
+    table: HashMap<OwnedRow, Vec<u32>>,
+    /** Every build row, the match list of a cross join */
+    all_rows: Vec<u32>,
+    visited: BooleanBufferBuilder,
+}
+
+impl BuildSide {
+    fn new(left: &PhysicaPlan, on: &[(usize, usize)], key_types: &[DataType]) -> Result<Self> {
+        let schema = left.schema();
+        let batches: Vec<RecordBatch> = left.execute()?.collect::<Result<_>>()?;
+
+        let columns: Vec<ArrayRef> = schema
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let arrays: Vec<ArrayRef> = batches
+                    .iter()
+                    .map(|it| Ok(it.field(i)?.to_array()))
+                    .collect::<Result<_>>()?;
+                if arrays.is_empty() {
+                    return Ok(new_empty_array(&field.data_type));
+                }
+                let arrays: Vec<&dyn Array> = arrays.iter().map(|it| it.as_ref()).collect();
+                Ok(concat(&arrays)?)
+            })
+            .collect::<Result<_>>()?;
+        let row_count = columns.first().map(|it| it.len()).unwrap_or(0);
+
+        let converter = RowConverter::new(
+            key_types
+                .iter()
+                .map(|it| SortField::new(it.clone()))
+                .collect(),
+        )?;
+
+        let mut table: HashMap<OwnedRow, Vec<u32>> = HashMap::new();
+        let mut all_rows: Vec<u32> = vec![];
+
+        if on.is_empty() {
+            all_rows = (0..row_count as u32).collect();
+        } else {
+            let keys: Vec<ArrayRef> = on
+                .iter()
+                .zip(key_types)
+                .map(|((l, _), data_type)| coerce_array(&columns[*l], data_type))
+                .collect::<Result<_>>()?;
+            let rows = converter.convert_columns(&keys)?;
+
+            for i in 0..rows.num_rows() {
+                // Null keys never compare equal, so they can not match any probe row
+                if has_null_key(&keys, i) {
+                    continue;
+                }
+                table.entry(rows.row(i).owned()).or_default().push(i as u32);
+            }
+        }
+
+        let mut visited = BooleanBufferBuilder::new(row_count);
+        visited.append_n(row_count, false);
+
+        Ok(Self {
+            columns,
+            converter,
+            table,
+            all_rows,
+            visited,
+        })
+    }
+}
+
+/** Paired build and probe row indices, `None` stands for a null padded row */
+type JoinIndices = (Vec<Option<u32>>, Vec<Option<u32>>);
+
+struct HashJoinStream {
+    left: Arc<PhysicaPlan>,
+    probe: RecordBatchStream,
+    join_type: JoinType,
+    on: Vec<(usize, usize)>,
+    key_types: Vec<DataType>,
+    schema: Schema,
+
+    build: Option<BuildSide>,
+    finished: bool,
+}
+
+impl HashJoinStream {
+    /**
+     * Pair up the rows of a probe batch with their matching build rows, marking matched build
+     * rows as visited. Unmatched probe rows are paired with a null build row for right and full
+     * joins.
+     */
+    fn match_batch(&mut self, batch: &RecordBatch) -> Result<JoinIndices> {
+        let build = self.build.as_mut().unwrap();
+        let pad_unmatched = matches!(self.join_type, JoinType::Right | JoinType::Full);
+
+        let keys: Vec<ArrayRef> = self
+            .on
+            .iter()
+            .zip(self.key_types.iter())
+            .map(|((_, r), data_type)| coerce_array(&batch.field(*r)?.to_array(), data_type))
+            .collect::<Result<_>>()?;
+        let rows = if keys.is_empty() {
+            None
+        } else {
+            Some(build.converter.convert_columns(&keys)?)
+        };
+
+        let mut left_indices: Vec<Option<u32>> = vec![];
+        let mut right_indices: Vec<Option<u32>> = vec![];
+
+        for i in 0..batch.row_count() {
+            let matches = match &rows {
+                None => Some(&build.all_rows),
+                Some(_) if has_null_key(&keys, i) => None,
+                Some(rows) => build.table.get(&rows.row(i).owned()),
+            };
+
+            match matches {
+                Some(matches) if !matches.is_empty() => {
+                    for l in matches {
+                        build.visited.set_bit(*l as usize, true);
+                        left_indices.push(Some(*l));
+                        right_indices.push(Some(i as u32));
+                    }
+                }
+                _ => {
+                    if pad_unmatched {
+                        left_indices.push(None);
+                        right_indices.push(Some(i as u32));
+                    }
+                }
+            }
+        }
+
+        Ok((left_indices, right_indices))
+    }
+
+    fn probe_batch(&mut self, batch: &RecordBatch) -> Result<RecordBatch> {
+        let (left_indices, right_indices) = self.match_batch(batch)?;
+
+        let right_columns: Vec<ArrayRef> = (0..batch.column_count())
+            .map(|i| Ok(batch.field(i)?.to_array()))
+            .collect::<Result<_>>()?;
+
+        self.output(&left_indices, Some((&right_columns, &right_indices)))
+    }
+
+    /** Build rows whose visited flag equals `visited`, padded with nulls on the right */
+    fn build_rows(&self, visited: bool) -> Result<RecordBatch> {
+        let build = self.build.as_ref().unwrap();
+
+        let left_indices: Vec<Option<u32>> = (0..build.visited.len())
+            .filter(|i| build.visited.get_bit(*i) == visited)
+            .map(|i| Some(i as u32))
+            .collect();
+
+        if matches!(self.join_type, JoinType::LeftSemi | JoinType::LeftAnti) {
+            return self.output(&left_indices, None);
+        }
+
+        let right_indices: Vec<Option<u32>> = vec![None; left_indices.len()];
+        let right_columns: Vec<ArrayRef> = self.schema.fields[build.columns.len()..]
+            .iter()
+            .map(|it| new_empty_array(&it.data_type))
+            .collect();
+
+        self.output(&left_indices, Some((&right_columns, &right_indices)))
+    }
+
+    fn output(
+        &self,
+        left_indices: &[Option<u32>],
+        right: Option<(&[ArrayRef], &[Option<u32>])>,
+    ) -> Result<RecordBatch> {
+        let build = self.build.as_ref().unwrap();
+        let left_indices = UInt32Array::from(left_indices.to_vec());
+
+        let mut fields: Vec<ArrayRef> = build
+            .columns
+            .iter()
+            .map(|it| take(it.as_ref(), &left_indices, None))
+            .collect::<std::result::Result<_, _>>()?;
+
+        if let Some((right_columns, right_indices)) = right {
+            let right_indices = UInt32Array::from(right_indices.to_vec());
+            for column in right_columns {
+                fields.push(take(column.as_ref(), &right_indices, None)?);
+            }
+        }
+
+        Ok(RecordBatch {
+            schema: self.schema.clone(),
+            fields: fields
+                .into_iter()
+                .map(|field| ColumnVector::ArrowVector(ArrowFieldVector { field }))
+                .collect(),
+        })
+    }
+
+    fn next_batch(&mut self) -> Result<Option<RecordBatch>> {
+        if self.build.is_none() {
+            self.build = Some(BuildSide::new(&self.left, &self.on, &self.key_types)?);
+        }
+
+        match self.join_type {
+            // Semi and anti joins only know their output once every probe row has been seen
+            JoinType::LeftSemi | JoinType::LeftAnti => {
+                while let Some(batch) = self.probe.next() {
+                    self.match_batch(&batch?)?;
+                }
+
+                self.finished = true;
+                Ok(Some(self.build_rows(self.join_type == JoinType::LeftSemi)?))
+            }
+            _ => {
+                if let Some(batch) = self.probe.next() {
+                    return Ok(Some(self.probe_batch(&batch?)?));
+                }
+
+                self.finished = true;
+                if matches!(self.join_type, JoinType::Left | JoinType::Full) {
+                    return Ok(Some(self.build_rows(false)?));
+                }
+                Ok(None)
+            }
+        }
+    }
+}
+
+impl Iterator for HashJoinStream {
+    type Item = Result<RecordBatch>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let batch = self.next_batch();
+        // The join can not recover from a failed batch, stop after reporting it
+        if batch.is_err() {
+            self.finished = true;
+        }
+        batch.transpose()
+    }
+}
+
+fn has_null_key(keys: &[ArrayRef], i: usize) -> bool {
+    keys.iter().any(|it| it.is_null(i))
+}
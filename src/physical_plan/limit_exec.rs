@@ -0,0 +1,62 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    datatypes::schema::Schema,
+    error::Result,
+    physical_plan::{PhysPlanTrait, PhysicaPlan, RecordBatchStream},
+};
+
+pub struct LimitExec {
+    input: Arc<PhysicaPlan>,
+    limit: usize,
+}
+
+impl LimitExec {
+    pub fn new(input: Arc<PhysicaPlan>, limit: usize) -> Self {
+        Self { input, limit }
+    }
+}
+
+impl PhysPlanTrait for LimitExec {
+    fn schema(&self) -> Schema {
+        self.input.schema()
+    }
+
+    fn children(&self) -> Vec<Arc<PhysicaPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn execute(&self) -> Result<RecordBatchStream> {
+        let mut remaining = self.limit;
+
+        Ok(Box::new(self.input.execute()?.map_while(move |batch| {
+            if remaining == 0 {
+                return None;
+            }
+
+            let batch = match batch {
+                Ok(batch) => batch,
+                Err(e) => {
+                    remaining = 0;
+                    return Some(Err(e));
+                }
+            };
+
+            let rows = batch.row_count();
+            if rows <= remaining {
+                remaining -= rows;
+                return Some(Ok(batch));
+            }
+
+            let sliced = batch.slice(0, remaining);
+            remaining = 0;
+            Some(Ok(sliced))
+        })))
+    }
+}
+
+impl fmt::Display for LimitExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "LimitExec: limit={}", self.limit)
+    }
+}
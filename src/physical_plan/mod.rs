@@ -1,34 +1,86 @@
 pub mod expressions;
 pub mod test;
 
+pub mod copy_exec;
 pub mod hash_aggregate_exec;
+pub mod hash_join_exec;
+pub mod limit_exec;
 pub mod projection_exec;
 pub mod scan_exec;
 pub mod selection_exec;
+pub mod sort_exec;
 
 use std::sync::Arc;
 
-use crate::{datasource::Iterators, datatypes::{record_batch::RecordBatch, schema::Schema}};
+use crate::{
+    datatypes::{record_batch::RecordBatch, schema::Schema},
+    error::Result,
+    physical_plan::{
+        copy_exec::CopyExec, hash_aggregate_exec::HashAggregateExec, hash_join_exec::HashJoinExec,
+        limit_exec::LimitExec, projection_exec::ProjectionExec, scan_exec::ScanExec,
+        selection_exec::SelectionExec, sort_exec::SortExec,
+    },
+};
+
+/**
+ * Lazily evaluated stream of record batches produced by executing a physical plan. A failure
+ * while producing a batch is yielded in its place.
+ */
+pub type RecordBatchStream = Box<dyn Iterator<Item = Result<RecordBatch>>>;
 
 pub trait PhysPlanTrait {
     fn schema(&self) -> Schema;
-    fn execute(&self) -> impl Iterator< Item = RecordBatch>;
+    /** Start executing the plan, failing early if an input can not be opened */
+    fn execute(&self) -> Result<RecordBatchStream>;
     fn children(&self) -> Vec<Arc<PhysicaPlan>>;
-
- 
-   
 }
-pub enum PhysicaPlan {}
+pub enum PhysicaPlan {
+    Scan(ScanExec),
+    Projection(ProjectionExec),
+    Selection(SelectionExec),
+    Limit(LimitExec),
+    HashAggregate(HashAggregateExec),
+    HashJoin(HashJoinExec),
+    Sort(SortExec),
+    Copy(CopyExec),
+}
 
 impl PhysicaPlan {
     pub fn schema(&self) -> Schema {
-        unimplemented!()
+        match self {
+            PhysicaPlan::Scan(scan) => scan.schema(),
+            PhysicaPlan::Projection(projection) => projection.schema(),
+            PhysicaPlan::Selection(selection) => selection.schema(),
+            PhysicaPlan::Limit(limit) => limit.schema(),
+            PhysicaPlan::HashAggregate(aggregate) => aggregate.schema(),
+            PhysicaPlan::HashJoin(join) => join.schema(),
+            PhysicaPlan::Sort(sort) => sort.schema(),
+            PhysicaPlan::Copy(copy) => copy.schema(),
+        }
     }
-    pub fn execute(&self) -> Iterators {
-        unimplemented!()
+    pub fn execute(&self) -> Result<RecordBatchStream> {
+        match self {
+            PhysicaPlan::Scan(scan) => scan.execute(),
+            PhysicaPlan::Projection(projection) => projection.execute(),
+            PhysicaPlan::Selection(selection) => selection.execute(),
+            PhysicaPlan::Limit(limit) => limit.execute(),
+            PhysicaPlan::HashAggregate(aggregate) => aggregate.execute(),
+            PhysicaPlan::HashJoin(join) => join.execute(),
+            PhysicaPlan::Sort(sort) => sort.execute(),
+            PhysicaPlan::Copy(copy) => copy.execute(),
+        }
     }
     pub fn children(&self) -> Vec<Arc<PhysicaPlan>> {
-        unimplemented!()
+        match self {
+            PhysicaPlan::Scan(scan) => scan.children(),
+            PhysicaPlan::Projection(projection) => projection.children(),
+            PhysicaPlan::Selection(selection) => selection.children(),
+            PhysicaPlan::Limit(limit) => limit.children(),
+            PhysicaPlan::HashAggregate(aggregate) => aggregate.children(),
+            PhysicaPlan::HashJoin(join) => join.children(),
+            PhysicaPlan::Sort(sort) => sort.children(),
+            PhysicaPlan::Copy(copy) => copy.children(),
+        }
     }
 
     pub fn format_plan(&self) -> String {
@@ -36,13 +88,22 @@ impl PhysicaPlan {
     }
 
     fn get_plan(&self) -> &PhysicaPlan {
-        unimplemented!()
+        self
     }
 }
 
 impl std::fmt::Display for PhysicaPlan {
-    fn fmt(&self, _f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        unimplemented!()
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PhysicaPlan::Scan(scan) => write!(f, "{}", scan),
+            PhysicaPlan::Projection(projection) => write!(f, "{}", projection),
+            PhysicaPlan::Selection(selection) => write!(f, "{}", selection),
+            PhysicaPlan::Limit(limit) => write!(f, "{}", limit),
+            PhysicaPlan::HashAggregate(aggregate) => write!(f, "{}", aggregate),
+            PhysicaPlan::HashJoin(join) => write!(f, "{}", join),
+            PhysicaPlan::Sort(sort) => write!(f, "{}", sort),
+            PhysicaPlan::Copy(copy) => write!(f, "{}", copy),
+        }
     }
 }
 
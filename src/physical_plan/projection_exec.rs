@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{fmt, sync::Arc};
 
 use arrow::datatypes::Schema;
 
@@ -6,7 +6,8 @@ use crate::{
     datatypes::{
         column_vector::ColumnVector, record_batch::RecordBatch, schema::schema_from_arrow_schema,
     },
-    physical_plan::{PhysPlanTrait, PhysicaPlan, expressions::Expression},
+    error::Result,
+    physical_plan::{PhysPlanTrait, PhysicaPlan, RecordBatchStream, expressions::Expression},
 };
 
 pub struct ProjectionExec {
@@ -15,6 +16,16 @@ pub struct ProjectionExec {
     expr: Vec<Expression>,
 }
 
+impl ProjectionExec {
+    pub fn new(input: Arc<PhysicaPlan>, schema: Arc<Schema>, expr: Vec<Expression>) -> Self {
+        Self {
+            input,
+            schema,
+            expr,
+        }
+    }
+}
+
 impl PhysPlanTrait for ProjectionExec {
     fn schema(&self) -> crate::datatypes::schema::Schema {
         schema_from_arrow_schema(self.schema.clone())
@@ -24,17 +35,30 @@ impl PhysPlanTrait for ProjectionExec {
         vec![self.input.clone()]
     }
 
-    fn execute(&self) -> impl Iterator<Item = RecordBatch> {
-        let return_vec = self.input.execute().map( move |batch| {
-            let columns: Vec<ColumnVector> =
-                self.expr.iter().map(|it| it.evaluate(batch.clone())).collect();
+    fn execute(&self) -> Result<RecordBatchStream> {
+        let schema = self.schema();
+        let expr = self.expr.clone();
+
+        let return_vec = self.input.execute()?.map(move |batch| {
+            let batch = batch?;
+            let columns: Vec<ColumnVector> = expr
+                .iter()
+                .map(|it| it.evaluate(batch.clone()))
+                .collect::<Result<_>>()?;
 
-            RecordBatch {
+            Ok(RecordBatch {
                 fields: columns,
-                schema: self.schema(),
-            }
+                schema: schema.clone(),
+            })
         });
 
-        return return_vec
+        Ok(Box::new(return_vec))
+    }
+}
+
+impl fmt::Display for ProjectionExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let expr: Vec<String> = self.expr.iter().map(|it| it.to_string()).collect();
+        write!(f, "ProjectionExec: {:?}", expr)
     }
 }
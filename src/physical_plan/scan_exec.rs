@@ -3,25 +3,57 @@ use std::{fmt, sync::Arc};
 use crate::{
     datasource::{DataSource, DataSourceTrait},
     datatypes::schema::Schema,
-    physical_plan::{PhysPlanTrait, PhysicaPlan},
+    error::Result,
+    logical_plan::expr::ExprRef,
+    physical_plan::{PhysPlanTrait, PhysicaPlan, RecordBatchStream},
 };
 
 pub struct ScanExec {
-    ds: DataSource,
+    ds: Arc<DataSource>,
     projection: Arc<Vec<String>>,
+    schema: Schema,
+    /** Predicates the data source may use to skip data, see `Scan::filters` */
+    filters: Vec<ExprRef>,
+}
+
+impl ScanExec {
+    /** Fails if a projected column does not exist in the data source */
+    pub fn new(ds: Arc<DataSource>, projection: Arc<Vec<String>>) -> Result<Self> {
+        let schema = ds.schema();
+        let schema = if projection.is_empty() {
+            schema.as_ref().clone()
+        } else {
+            schema.select(projection.clone())?
+        };
+
+        Ok(Self {
+            ds,
+            projection,
+            schema,
+            filters: vec![],
+        })
+    }
+
+    pub fn with_filters(mut self, filters: Vec<ExprRef>) -> Self {
+        self.filters = filters;
+        self
+    }
 }
 
 impl PhysPlanTrait for ScanExec {
     fn schema(&self) -> Schema {
-        self.ds.schema().select(self.projection.clone()).unwrap()
+        self.schema.clone()
     }
 
     fn children(&self) -> Vec<Arc<PhysicaPlan>> {
         vec![]
     }
 
-    fn execute(&self) -> impl Iterator<Item = crate::datatypes::record_batch::RecordBatch> {
-        self.ds.scan(self.projection.to_vec())
+    fn execute(&self) -> Result<RecordBatchStream> {
+        Ok(Box::new(self.ds.scan_with_filters(
+            self.projection.to_vec(),
+            &self.filters,
+        )?))
     }
 }
 
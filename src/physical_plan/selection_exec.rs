@@ -1,16 +1,14 @@
-use std::sync::Arc;
+use std::{fmt, sync::Arc};
 
-use arrow::{
-    array::BooleanArray,
-    compute:: kernels::filter,
-};
+use arrow::array::AsArray;
 
 use crate::{
     datatypes::{
-        arrow_field_vector::ArrowFieldVector, column_vector::ColumnVector,
+        column_vector::{ColumnVector, ColumnVectorTrait},
         record_batch::RecordBatch,
     },
-    physical_plan::{PhysPlanTrait, PhysicaPlan, expressions::Expression},
+    error::{Result, UnakiteError},
+    physical_plan::{PhysPlanTrait, PhysicaPlan, RecordBatchStream, expressions::Expression},
 };
 
 pub struct SelectionExec {
@@ -18,6 +16,12 @@ pub struct SelectionExec {
     expr: Expression,
 }
 
+impl SelectionExec {
+    pub fn new(input: Arc<PhysicaPlan>, expr: Expression) -> Self {
+        Self { input, expr }
+    }
+}
+
 impl PhysPlanTrait for SelectionExec {
     fn schema(&self) -> crate::datatypes::schema::Schema {
         self.input.schema()
@@ -26,32 +30,42 @@ impl PhysPlanTrait for SelectionExec {
     fn children(&self) -> Vec<std::sync::Arc<PhysicaPlan>> {
         vec![self.input.clone()]
     }
-    fn execute(&self) -> impl Iterator<Item = crate::datatypes::record_batch::RecordBatch> {
-        let input = self.input.execute();
-
-        let iter = input.map(|batch| {
-            if let ColumnVector::ArrowVector(result) = self.expr.evaluate(batch.clone()) {
-                let array_ref = result.field;
-
-                let predicate = array_ref
-                    .as_any()
-                    .downcast_ref::<BooleanArray>()
-                    .expect("Boolean Array");
-
-                let filtered_fields: ColumnVector = ColumnVector::ArrowVector(ArrowFieldVector {
-                    field: filter::filter(predicate, predicate).unwrap(),
-                    
-                });
-
-                RecordBatch {
-                    schema: self.schema(),
-                    fields: vec![filtered_fields],
-                }
-            } else {
-                panic!("Cannot execute on a literal value vector")
-            }
+    fn execute(&self) -> Result<RecordBatchStream> {
+        let schema = self.schema();
+        let expr = self.expr.clone();
+
+        let iter = self.input.execute()?.map(move |batch| {
+            let batch = batch?;
+            let result = expr.evaluate(batch.clone())?;
+
+            // Literal predicates are materialized so a constant filter keeps or drops every row
+            let array_ref = result.to_array();
+            let predicate = array_ref.as_boolean_opt().ok_or_else(|| {
+                UnakiteError::Type(format!(
+                    "Selection predicate must be boolean, got {}",
+                    result.get_type()
+                ))
+            })?;
+
+            // Rows where the predicate is null are dropped along with the false ones
+            let filtered_fields: Vec<ColumnVector> = batch
+                .fields
+                .iter()
+                .map(|it| it.filter(predicate))
+                .collect::<Result<_>>()?;
+
+            Ok(RecordBatch {
+                schema: schema.clone(),
+                fields: filtered_fields,
+            })
         });
 
-        iter
+        Ok(Box::new(iter))
+    }
+}
+
+impl fmt::Display for SelectionExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SelectionExec: {}", self.expr)
     }
 }
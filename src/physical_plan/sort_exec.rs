@@ -0,0 +1,129 @@
+use std::{fmt, sync::Arc};
+
+use arrow::{
+    array::{Array, ArrayRef},
+    compute::{SortColumn, SortOptions, concat, kernels::take::take, lexsort_to_indices},
+};
+
+use crate::{
+    datatypes::{
+        arrow_field_vector::ArrowFieldVector, column_vector::ColumnVector,
+        record_batch::RecordBatch, schema::Schema,
+    },
+    error::Result,
+    physical_plan::{PhysPlanTrait, PhysicaPlan, RecordBatchStream, expressions::Expression},
+};
+
+/** A sort key and its direction */
+#[derive(Clone)]
+pub struct PhysicalSortExpr {
+    pub expr: Expression,
+    pub asc: bool,
+}
+
+impl fmt::Display for PhysicalSortExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = if self.asc { "ASC" } else { "DESC" };
+        write!(f, "{} {}", self.expr, direction)
+    }
+}
+
+/**
+ * Fully materializes its input and emits it as one sorted batch. Ascending keys sort nulls last
+ * and descending keys sort nulls first.
+ */
+pub struct SortExec {
+    input: Arc<PhysicaPlan>,
+    order_by: Vec<PhysicalSortExpr>,
+}
+
+impl SortExec {
+    pub fn new(input: Arc<PhysicaPlan>, order_by: Vec<PhysicalSortExpr>) -> Self {
+        Self { input, order_by }
+    }
+}
+
+impl PhysPlanTrait for SortExec {
+    fn schema(&self) -> Schema {
+        self.input.schema()
+    }
+
+    fn children(&self) -> Vec<Arc<PhysicaPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn execute(&self) -> Result<RecordBatchStream> {
+        let input = self.input.execute()?;
+        let order_by = self.order_by.clone();
+
+        Ok(Box::new(
+            std::iter::once_with(move || sort_all(input, &order_by).transpose()).flatten(),
+        ))
+    }
+}
+
+/** Sort every input batch into a single batch, `None` if the input is empty */
+fn sort_all(
+    input: RecordBatchStream,
+    order_by: &[PhysicalSortExpr],
+) -> Result<Option<RecordBatch>> {
+    let batches: Vec<RecordBatch> = input.collect::<Result<_>>()?;
+    let schema = match batches.first() {
+        Some(first) => first.schema.clone(),
+        None => return Ok(None),
+    };
+    let batch = concat_batches(schema, &batches)?;
+
+    let sort_columns: Vec<SortColumn> = order_by
+        .iter()
+        .map(|it| {
+            Ok(SortColumn {
+                values: it.expr.evaluate(batch.clone())?.to_array(),
+                options: Some(SortOptions {
+                    descending: !it.asc,
+                    nulls_first: !it.asc,
+                }),
+            })
+        })
+        .collect::<Result<_>>()?;
+    let indices = lexsort_to_indices(&sort_columns, None)?;
+
+    let fields = batch
+        .fields
+        .iter()
+        .map(|it| {
+            Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+                field: take(it.to_array().as_ref(), &indices, None)?,
+            }))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(Some(RecordBatch {
+        schema: batch.schema.clone(),
+        fields,
+    }))
+}
+
+impl fmt::Display for SortExec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let order_by: Vec<String> = self.order_by.iter().map(|it| it.to_string()).collect();
+        write!(f, "SortExec: {:?}", order_by)
+    }
+}
+
+fn concat_batches(schema: Schema, batches: &[RecordBatch]) -> Result<RecordBatch> {
+    let fields = (0..schema.fields.len())
+        .map(|i| {
+            let arrays: Vec<ArrayRef> = batches
+                .iter()
+                .map(|it| Ok(it.field(i)?.to_array()))
+                .collect::<Result<_>>()?;
+            let arrays: Vec<&dyn Array> = arrays.iter().map(|it| it.as_ref()).collect();
+            Ok(ColumnVector::ArrowVector(ArrowFieldVector {
+                field: concat(&arrays)?,
+            }))
+        })
+        .collect::<Result<_>>()?;
+
+    Ok(RecordBatch { schema, fields })
+}
@@ -1,11 +1,21 @@
 #[cfg(test)]
 pub mod test {
-    use arrow::datatypes::DataType;
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{ArrayRef, Float64Array, Int32Array, Int64Array, RunArray, StringArray},
+        datatypes::DataType,
+    };
 
     use crate::{
-        datatypes::{arrow_vector_builder::ArrowVectorBuilder, value::ArrowValue},
+        datatypes::{
+            arrow_field_vector::ArrowFieldVector, arrow_vector_builder::ArrowVectorBuilder,
+            column_vector::ColumnVector, value::ArrowValue,
+        },
+        error::UnakiteError,
         physical_plan::expressions::aggregates::{
-            AggregateExpression, max_expression, min_expression, sum_expression,
+            AggregateExpression, avg_expression, count_distinct_expression, count_expression,
+            max_expression, min_expression, sum_expression,
         },
     };
 
@@ -15,22 +25,23 @@ pub mod test {
         let mut b_accumulator = min_expression().create_accumulator();
         let mut c_accumulator = max_expression().create_accumulator();
 
-        let mut b = ArrowVectorBuilder::new(&DataType::Int64);
+        let mut b = ArrowVectorBuilder::new(&DataType::Int64).unwrap();
 
-        b.set_all(&mut vec![
-            Some(ArrowValue::Int64Type(0 as i64)),
-            Some(ArrowValue::Int64Type(1 as i64)),
-            Some(ArrowValue::Int64Type(2 as i64)),
-            Some(ArrowValue::Int64Type(3 as i64)),
-            Some(ArrowValue::Int64Type(4 as i64)),
-            Some(ArrowValue::Int64Type(5 as i64)),
-            Some(ArrowValue::Int64Type(6 as i64)),
-            Some(ArrowValue::Int64Type(7 as i64)),
-            Some(ArrowValue::Int64Type(8 as i64)),
-            Some(ArrowValue::Int64Type(9 as i64)),
-        ]);
+        b.set_all(&mut [
+            Some(ArrowValue::Int64Type(0)),
+            Some(ArrowValue::Int64Type(1)),
+            Some(ArrowValue::Int64Type(2)),
+            Some(ArrowValue::Int64Type(3)),
+            Some(ArrowValue::Int64Type(4)),
+            Some(ArrowValue::Int64Type(5)),
+            Some(ArrowValue::Int64Type(6)),
+            Some(ArrowValue::Int64Type(7)),
+            Some(ArrowValue::Int64Type(8)),
+            Some(ArrowValue::Int64Type(9)),
+        ])
+        .unwrap();
 
-        let v = b.build();
+        let v = b.build().unwrap();
 
         let _ = a_accumulator.update(&v);
         let _ = b_accumulator.update(&v);
@@ -40,8 +51,139 @@ pub mod test {
         let g = b_accumulator.final_value();
         let h = c_accumulator.final_value();
 
-        println!("{:?}", f);
-        println!("{:?}", g);
-        println!("{:?}", h);
+        assert_eq!(f, Some(ArrowValue::Int64Type(45)));
+        assert_eq!(g, Some(ArrowValue::Int64Type(0)));
+        assert_eq!(h, Some(ArrowValue::Int64Type(9)));
+    }
+
+    #[test]
+    fn merge_across_batches() {
+        let first = vector(Arc::new(Int64Array::from(vec![5, 3, 8])));
+        let second = vector(Arc::new(Int64Array::from(vec![1, 9])));
+        let third = vector(Arc::new(Int64Array::from(vec![4])));
+
+        let mut min = min_expression().create_accumulator();
+        let mut max = max_expression().create_accumulator();
+        let mut sum = sum_expression().create_accumulator();
+
+        for batch in [&first, &second, &third] {
+            min.update(batch).unwrap();
+            max.update(batch).unwrap();
+            sum.update(batch).unwrap();
+        }
+
+        assert_eq!(min.final_value(), Some(ArrowValue::Int64Type(1)));
+        assert_eq!(max.final_value(), Some(ArrowValue::Int64Type(9)));
+        assert_eq!(sum.final_value(), Some(ArrowValue::Int64Type(30)));
+    }
+
+    #[test]
+    fn nulls_are_ignored() {
+        let values = vector(Arc::new(Float64Array::from(vec![
+            Some(2.0),
+            None,
+            Some(4.0),
+            None,
+        ])));
+
+        let mut min = min_expression().create_accumulator();
+        let mut avg = avg_expression().create_accumulator();
+        let mut count = count_expression().create_accumulator();
+
+        min.update(&values).unwrap();
+        avg.update(&values).unwrap();
+        count.update(&values).unwrap();
+
+        assert_eq!(min.final_value(), Some(ArrowValue::DoubleType(2.0)));
+        assert_eq!(avg.final_value(), Some(ArrowValue::DoubleType(3.0)));
+        assert_eq!(count.final_value(), Some(ArrowValue::Int32Type(2)));
+    }
+
+    #[test]
+    fn all_null_input() {
+        let values = vector(Arc::new(Int32Array::from(vec![None, None])));
+
+        let mut sum = sum_expression().create_accumulator();
+        let mut avg = avg_expression().create_accumulator();
+        let mut count = count_expression().create_accumulator();
+        let mut distinct = count_distinct_expression().create_accumulator();
+
+        sum.update(&values).unwrap();
+        avg.update(&values).unwrap();
+        count.update(&values).unwrap();
+        distinct.update(&values).unwrap();
+
+        assert_eq!(sum.final_value(), None);
+        assert_eq!(avg.final_value(), None);
+        assert_eq!(count.final_value(), Some(ArrowValue::Int32Type(0)));
+        assert_eq!(distinct.final_value(), Some(ArrowValue::Int32Type(0)));
+    }
+
+    #[test]
+    fn string_min_max_count() {
+        let first = vector(Arc::new(StringArray::from(vec![
+            Some("pear"),
+            None,
+            Some("apple"),
+        ])));
+        let second = vector(Arc::new(StringArray::from(vec![
+            Some("zucchini"),
+            Some("pear"),
+        ])));
+
+        let mut min = min_expression().create_accumulator();
+        let mut max = max_expression().create_accumulator();
+        let mut count = count_expression().create_accumulator();
+        let mut distinct = count_distinct_expression().create_accumulator();
+
+        for batch in [&first, &second] {
+            min.update(batch).unwrap();
+            max.update(batch).unwrap();
+            count.update(batch).unwrap();
+            distinct.update(batch).unwrap();
+        }
+
+        assert_eq!(
+            min.final_value(),
+            Some(ArrowValue::StringType("apple".into()))
+        );
+        assert_eq!(
+            max.final_value(),
+            Some(ArrowValue::StringType("zucchini".into()))
+        );
+        assert_eq!(count.final_value(), Some(ArrowValue::Int32Type(4)));
+        assert_eq!(distinct.final_value(), Some(ArrowValue::Int32Type(3)));
+    }
+
+    #[test]
+    fn sum_and_avg_of_strings_fail() {
+        let values = vector(Arc::new(StringArray::from(vec!["a", "b"])));
+        let mut sum = sum_expression().create_accumulator();
+        let mut avg = avg_expression().create_accumulator();
+
+        assert!(sum.update(&values).is_err());
+        assert!(avg.update(&values).is_err());
+    }
+
+    #[test]
+    fn count_overflow_is_an_error() {
+        // A run of i32::MAX equal values takes no memory to speak of
+        let run = |len: i32| {
+            let run_ends = Int32Array::from(vec![len]);
+            let values = Int64Array::from(vec![1]);
+            vector(Arc::new(RunArray::try_new(&run_ends, &values).unwrap()))
+        };
+        let mut count = count_expression().create_accumulator();
+
+        count.update(&run(i32::MAX)).unwrap();
+        assert_eq!(count.final_value(), Some(ArrowValue::Int32Type(i32::MAX)));
+        assert!(matches!(
+            count.update(&run(1)),
+            Err(UnakiteError::Execution(_))
+        ));
+    }
+
+    fn vector(field: ArrayRef) -> ColumnVector {
+        ColumnVector::ArrowVector(ArrowFieldVector { field })
     }
 }
@@ -13,6 +13,7 @@ pub mod test {
             schema::{Field, Schema},
             value::ArrowValue,
         },
+        error::UnakiteError,
         physical_plan::expressions::{
             Expression,
             booleans::{BooleanExpression, gteq, impl_expressions::GteqPlan},
@@ -42,24 +43,24 @@ pub mod test {
         let batch = RecordBatch {
             schema,
             fields: vec![
-                build_vector(DataType::Int8, &a_value),
-                build_vector(DataType::Int8, &b_value),
+                build_vector(DataType::Int8, &a_value).unwrap(),
+                build_vector(DataType::Int8, &b_value).unwrap(),
             ],
         };
 
         let expr = gteq();
 
-        let res = expr.evaluate(batch);
+        let res = expr.evaluate(batch).unwrap();
 
         for i in 0..res.size() {
-            let value = bool::from(res.get_value(i));
+            let value = bool::try_from(res.get_value(i).unwrap().unwrap()).unwrap();
             assert_eq!(a_v[i] >= b_v[i], value);
         }
     }
 
     #[test]
     fn gteq_shorts() {
-        let mut a = ArrowVectorBuilder::new(&DataType::Int16);
+        let mut a = ArrowVectorBuilder::new(&DataType::Int16).unwrap();
         let a_value: Vec<i16> = vec![
             1000,
             2000,
@@ -74,10 +75,10 @@ pub mod test {
         ];
 
         for (i, value) in a_value.iter().enumerate() {
-            a.set(i, Some(ArrowValue::Int16Type(*value)));
+            a.set(i, Some(ArrowValue::Int16Type(*value))).unwrap();
         }
 
-        let mut b = ArrowVectorBuilder::new(&DataType::Int16);
+        let mut b = ArrowVectorBuilder::new(&DataType::Int16).unwrap();
         let b_value: Vec<i16> = vec![
             1000,
             3000,
@@ -92,11 +93,11 @@ pub mod test {
         ];
 
         for (i, value) in b_value.iter().enumerate() {
-            b.set(i, Some(ArrowValue::Int16Type(*value)));
+            b.set(i, Some(ArrowValue::Int16Type(*value))).unwrap();
         }
 
-        let a_v = a.build();
-        let b_v = b.build();
+        let a_v = a.build().unwrap();
+        let b_v = b.build().unwrap();
 
         let vec_field = vec![
             Field::new("a", arrow::datatypes::DataType::Int16),
@@ -115,36 +116,38 @@ pub mod test {
             r: Arc::new(Expression::Column(ColumnExpression { i: 1 })),
         };
 
-        let res = expr.evaluate(batch);
+        let res = expr.evaluate(batch).unwrap();
 
         for i in 0..res.size() {
-            let value: bool = res.get_value(i).into();
+            let value: bool = res.get_value(i).unwrap().unwrap().try_into().unwrap();
             assert_eq!(a_value[i] >= b_value[i], value);
         }
     }
 
     #[test]
     fn gteq_string() {
-        let mut a = ArrowVectorBuilder::new(&DataType::Utf8);
+        let mut a = ArrowVectorBuilder::new(&DataType::Utf8).unwrap();
         let a_value = vec![
             "aac", "bbb", "bbc", "ccc", "ddd", "eee", "fff", "yya", "yyb", "zzz",
         ];
 
         for (i, value) in a_value.iter().enumerate() {
-            a.set(i, Some(ArrowValue::StringType(value.to_string())));
+            a.set(i, Some(ArrowValue::StringType(value.to_string())))
+                .unwrap();
         }
 
-        let mut b = ArrowVectorBuilder::new(&DataType::Utf8);
+        let mut b = ArrowVectorBuilder::new(&DataType::Utf8).unwrap();
         let b_value = vec![
             "aab", "bbc", "bbb", "ccc", "ddd", "eee", "fff", "yya", "yyb", "aaa",
         ];
 
         for (i, value) in b_value.iter().enumerate() {
-            b.set(i, Some(ArrowValue::StringType(value.to_string())));
+            b.set(i, Some(ArrowValue::StringType(value.to_string())))
+                .unwrap();
         }
 
-        let a_v = a.build();
-        let b_v = b.build();
+        let a_v = a.build().unwrap();
+        let b_v = b.build().unwrap();
 
         let vec_field = vec![
             Field::new("a", arrow::datatypes::DataType::Utf8),
@@ -163,11 +166,57 @@ pub mod test {
             r: Arc::new(Expression::Column(ColumnExpression { i: 1 })),
         };
 
-        let res = expr.evaluate(batch);
+        let res = expr.evaluate(batch).unwrap();
 
         for i in 0..res.size() {
-            let value: bool = res.get_value(i).into();
+            let value: bool = res.get_value(i).unwrap().unwrap().try_into().unwrap();
             assert_eq!(a_value[i] >= b_value[i], value);
         }
     }
+
+    #[test]
+    fn mixed_numeric_operands_are_promoted() {
+        let a = TypeVector::Int8(vec![1, 2, 3]);
+        let b = TypeVector::Int16(vec![1, 300, 2]);
+
+        let batch = RecordBatch {
+            schema: Schema {
+                fields: vec![
+                    Field::new("a", DataType::Int8),
+                    Field::new("b", DataType::Int16),
+                ],
+            },
+            fields: vec![
+                build_vector(DataType::Int8, &a).unwrap(),
+                build_vector(DataType::Int16, &b).unwrap(),
+            ],
+        };
+
+        let res = gteq().evaluate(batch).unwrap();
+        let values: Vec<bool> = (0..res.size())
+            .map(|i| res.get_value(i).unwrap().unwrap().try_into().unwrap())
+            .collect();
+        assert_eq!(values, vec![true, false, true]);
+    }
+
+    #[test]
+    fn mismatched_operand_types_are_errors() {
+        let a = TypeVector::Int8(vec![1, 2, 3]);
+        let b = TypeVector::String(vec!["1".to_string(), "2".to_string(), "3".to_string()]);
+
+        let batch = RecordBatch {
+            schema: Schema {
+                fields: vec![
+                    Field::new("a", DataType::Int8),
+                    Field::new("b", DataType::Utf8),
+                ],
+            },
+            fields: vec![
+                build_vector(DataType::Int8, &a).unwrap(),
+                build_vector(DataType::Utf8, &b).unwrap(),
+            ],
+        };
+
+        assert!(matches!(gteq().evaluate(batch), Err(UnakiteError::Type(_))));
+    }
 }
@@ -1,7 +1,15 @@
 #[cfg(test)]
-
 pub mod test {
-    use arrow::datatypes::DataType;
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{
+            Array, BooleanArray, Date32Array, Decimal128Array, Int32Array,
+            RecordBatch as ArrowRecordBatch, StringArray, TimestampMicrosecondArray,
+        },
+        datatypes::{DataType, Field as ArrowField, Schema as ArrowSchema, TimeUnit},
+    };
+    use chrono::NaiveDate;
 
     use crate::{
         datatypes::{
@@ -9,9 +17,8 @@ pub mod test {
             record_batch::RecordBatch,
             schema::{Field, Schema},
         },
-        physical_plan::expressions::{
-            cast_expression::CastExpression, column_expressions::ColumnExpression,
-        },
+        error::UnakiteError,
+        physical_plan::{expressions::cast_expression::CastExpression, test::util::column},
     };
 
     #[test]
@@ -35,15 +42,22 @@ pub mod test {
 
         let batch = RecordBatch {
             schema,
-            fields: vec![build_vector(DataType::Float32, &a_value)],
+            fields: vec![build_vector(DataType::Float32, &a_value).unwrap()],
         };
 
-        let expr = CastExpression::new(ColumnExpression { i: 0 }, DataType::Int32);
-
-        let result = expr.evaluate(batch);
-
-        // Fix issues surrounding null values
-        println!("{:?}", result);
+        // f32::MAX and f32::MIN do not fit in an Int32
+        let expr = CastExpression::new(column(0), DataType::Int32);
+        assert!(matches!(
+            expr.evaluate(batch.clone()),
+            Err(UnakiteError::Execution(_))
+        ));
+
+        let expr = CastExpression::try_cast(column(0), DataType::Int32);
+        let result = expr.evaluate(batch).unwrap().to_array();
+        let values = result.as_any().downcast_ref::<Int32Array>().unwrap();
+        assert_eq!(values.value(0), 36);
+        assert_eq!(values.null_count(), 2);
+        assert!(values.is_null(8) && values.is_null(9));
     }
 
     #[test]
@@ -67,12 +81,12 @@ pub mod test {
 
         let batch = RecordBatch {
             schema,
-            fields: vec![build_vector(DataType::Utf8, &a_value)],
+            fields: vec![build_vector(DataType::Utf8, &a_value).unwrap()],
         };
 
-        let expr = CastExpression::new(ColumnExpression { i: 0 }, DataType::Float32);
+        let expr = CastExpression::new(column(0), DataType::Float32);
 
-        let result = expr.evaluate(batch);
+        let result = expr.evaluate(batch).unwrap();
 
         // Fix issues surrounding null values
         println!("{:?}", result);
@@ -99,14 +113,88 @@ pub mod test {
 
         let batch = RecordBatch {
             schema,
-            fields: vec![build_vector(DataType::Int8, &a_value)],
+            fields: vec![build_vector(DataType::Int8, &a_value).unwrap()],
         };
 
-        let expr = CastExpression::new(ColumnExpression { i: 0 }, DataType::Utf8);
+        let expr = CastExpression::new(column(0), DataType::Utf8);
 
-        let result = expr.evaluate(batch);
+        let result = expr.evaluate(batch).unwrap();
 
         // Fix issues surrounding null values
         println!("{:?}", result);
     }
+
+    #[test]
+    fn unsupported_cast_is_an_error() {
+        let batch = RecordBatch {
+            schema: Schema {
+                fields: vec![Field::new("a", DataType::Int32)],
+            },
+            fields: vec![build_vector(DataType::Int32, &TypeVector::Int32(vec![1, 2])).unwrap()],
+        };
+
+        let expr = CastExpression::new(column(0), DataType::Binary);
+        assert!(matches!(
+            expr.evaluate(batch),
+            Err(UnakiteError::NotImplemented(_))
+        ));
+    }
+
+    #[test]
+    fn cast_strings_to_wider_types() {
+        let batch = strings(vec![Some("2024-02-29"), Some("not a value"), None]);
+
+        let expr = CastExpression::try_cast(column(0), DataType::Date32);
+        let result = expr.evaluate(batch.clone()).unwrap().to_array();
+        let days = result.as_any().downcast_ref::<Date32Array>().unwrap();
+        assert_eq!(days.value_as_date(0), NaiveDate::from_ymd_opt(2024, 2, 29));
+        assert!(days.is_null(1) && days.is_null(2));
+
+        let expr = CastExpression::new(column(0), DataType::Date32);
+        assert!(matches!(
+            expr.evaluate(batch),
+            Err(UnakiteError::Execution(msg)) if msg.starts_with("CAST(#0 AS Date32)")
+        ));
+
+        let batch = strings(vec![
+            Some("2024-02-29 13:45:00"),
+            Some("2024-03-01T00:00:01"),
+        ]);
+        let expr = CastExpression::new(column(0), DataType::Timestamp(TimeUnit::Microsecond, None));
+        let result = expr.evaluate(batch).unwrap().to_array();
+        let timestamps = result
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .unwrap();
+        assert_eq!(
+            timestamps.value_as_datetime(1),
+            NaiveDate::from_ymd_opt(2024, 3, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 1)
+        );
+
+        let batch = strings(vec![Some("true"), Some("no"), Some("maybe")]);
+        let expr = CastExpression::try_cast(column(0), DataType::Boolean);
+        let result = expr.evaluate(batch).unwrap().to_array();
+        let flags = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(
+            flags.iter().collect::<Vec<_>>(),
+            vec![Some(true), Some(false), None]
+        );
+
+        let batch = strings(vec![Some("12.345"), Some("-0.5"), Some("123456")]);
+        let expr = CastExpression::try_cast(column(0), DataType::Decimal128(6, 2));
+        let result = expr.evaluate(batch).unwrap().to_array();
+        let decimals = result.as_any().downcast_ref::<Decimal128Array>().unwrap();
+        assert_eq!(decimals.value_as_string(0), "12.35");
+        assert_eq!(decimals.value_as_string(1), "-0.50");
+        assert!(decimals.is_null(2));
+    }
+
+    fn strings(values: Vec<Option<&str>>) -> RecordBatch {
+        let schema = ArrowSchema::new(vec![ArrowField::new("a", DataType::Utf8, true)]);
+        ArrowRecordBatch::try_new(Arc::new(schema), vec![Arc::new(StringArray::from(values))])
+            .unwrap()
+            .into()
+    }
 }
@@ -0,0 +1,147 @@
+#[cfg(test)]
+pub mod test {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::AsArray,
+        datatypes::{Float64Type, Int64Type},
+    };
+
+    use crate::{
+        error::UnakiteError,
+        physical_plan::{
+            expressions::{
+                Expression,
+                booleans::impl_expressions::EqPlan,
+                case_expression::CaseExpression,
+                math::{MathExpression, NegativeExpression, math_expression::DividePlan},
+                predicates::{BetweenExpression, InListExpression, NotExpression},
+            },
+            test::util::{boolean, booleans, column, double, evaluate, long, longs, null, string},
+        },
+    };
+
+    #[test]
+    fn case_only_evaluates_the_rows_of_a_branch() {
+        let batch = longs(vec![Some(0), Some(2), None, Some(5)]);
+
+        // CASE WHEN a = 0 THEN 0 ELSE 10 / a END never divides by zero
+        let is_zero = boolean(EqPlan, column(0), long(0));
+        let divide = Expression::Math(Arc::new(MathExpression {
+            inner: Arc::new(DividePlan),
+            l: Arc::new(long(10)),
+            r: Arc::new(column(0)),
+        }));
+        let case = CaseExpression {
+            expr: None,
+            when_then: vec![(is_zero, long(0))],
+            else_expr: Some(Arc::new(divide)),
+        };
+
+        let result = case.evaluate(batch).unwrap().to_array();
+        let values: Vec<Option<i64>> = result.as_primitive::<Int64Type>().iter().collect();
+        assert_eq!(values, vec![Some(0), Some(5), None, Some(2)]);
+    }
+
+    #[test]
+    fn case_with_operand() {
+        let batch = longs(vec![Some(0), Some(2), None, Some(5)]);
+
+        let case = CaseExpression {
+            expr: Some(Arc::new(column(0))),
+            when_then: vec![(long(2), string("two")), (long(5), string("five"))],
+            else_expr: None,
+        };
+        let result = case.evaluate(batch.clone()).unwrap().to_array();
+        let values: Vec<Option<&str>> = result.as_string::<i32>().iter().collect();
+        assert_eq!(values, vec![None, Some("two"), None, Some("five")]);
+
+        // Results of different numeric types meet in their promoted type
+        let case = CaseExpression {
+            expr: Some(Arc::new(column(0))),
+            when_then: vec![(long(0), double(0.5))],
+            else_expr: Some(Arc::new(column(0))),
+        };
+        let result = case.evaluate(batch).unwrap().to_array();
+        let values: Vec<Option<f64>> = result.as_primitive::<Float64Type>().iter().collect();
+        assert_eq!(values, vec![Some(0.5), Some(2.0), None, Some(5.0)]);
+    }
+
+    #[test]
+    fn in_list_and_between() {
+        let batch = longs(vec![Some(0), Some(2), None, Some(5)]);
+
+        let in_list = |list, negated| {
+            Expression::InList(Arc::new(InListExpression {
+                expr: Arc::new(column(0)),
+                list,
+                negated,
+            }))
+        };
+        let result = evaluate(in_list(vec![long(0), long(5)], true), batch.clone());
+        assert_eq!(
+            booleans(&result),
+            vec![Some(false), Some(true), None, Some(false)]
+        );
+        // A value that is not found but meets a NULL is unknown
+        let result = evaluate(in_list(vec![long(2), null()], false), batch.clone());
+        assert_eq!(booleans(&result), vec![None, Some(true), None, None]);
+
+        let between = |low, high, negated| {
+            Expression::Between(Arc::new(BetweenExpression {
+                expr: Arc::new(column(0)),
+                low: Arc::new(low),
+                high: Arc::new(high),
+                negated,
+            }))
+        };
+        let result = evaluate(between(long(1), double(5.0), false), batch.clone());
+        assert_eq!(
+            booleans(&result),
+            vec![Some(false), Some(true), None, Some(true)]
+        );
+        let result = evaluate(between(long(1), long(4), true), batch);
+        assert_eq!(
+            booleans(&result),
+            vec![Some(true), Some(false), None, Some(true)]
+        );
+    }
+
+    #[test]
+    fn not_and_negative() {
+        let batch = longs(vec![Some(0), Some(2), None, Some(i64::MIN)]);
+
+        let in_list = Expression::InList(Arc::new(InListExpression {
+            expr: Arc::new(column(0)),
+            list: vec![long(2)],
+            negated: false,
+        }));
+        let not = Expression::Not(Arc::new(NotExpression {
+            expr: Arc::new(in_list),
+        }));
+        let result = evaluate(not, batch.clone());
+        assert_eq!(
+            booleans(&result),
+            vec![Some(true), Some(false), None, Some(true)]
+        );
+
+        let not = NotExpression {
+            expr: Arc::new(column(0)),
+        };
+        assert!(matches!(
+            not.evaluate(batch.clone()),
+            Err(UnakiteError::Type(_))
+        ));
+
+        let negative = NegativeExpression {
+            expr: Arc::new(column(0)),
+        };
+        let result = negative.evaluate(batch.slice(0, 3)).unwrap().to_array();
+        let values: Vec<Option<i64>> = result.as_primitive::<Int64Type>().iter().collect();
+        assert_eq!(values, vec![Some(0), Some(-2), None]);
+        assert!(matches!(
+            negative.evaluate(batch),
+            Err(UnakiteError::Execution(_))
+        ));
+    }
+}
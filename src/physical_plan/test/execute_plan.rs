@@ -0,0 +1,91 @@
+#[cfg(test)]
+pub mod test {
+    use crate::{
+        datatypes::{column_vector::ColumnVectorTrait, value::ArrowValue},
+        error::UnakiteError,
+        logical_plan::{
+            data_frame::DataFrame,
+            helper::column,
+            macro_utils::{literal_double, literal_u64},
+        },
+        query_planner::QueryPlanner,
+        test_util::uk_cities,
+    };
+
+    #[test]
+    fn execute_selection_and_projection() {
+        let df = uk_cities()
+            .filter(column("lat").gt(literal_double(53.0)))
+            .project(vec![column("lat"), column("city")]);
+
+        let plan = QueryPlanner::create_physical_plan(&df.plan).unwrap();
+
+        let mut rows = 0;
+        for batch in plan.execute().unwrap() {
+            let batch = batch.unwrap();
+            assert_eq!(batch.column_count(), 2);
+            assert_eq!(batch.schema.fields[0].name, "lat");
+
+            let lat = batch.field(0).unwrap();
+            for i in 0..lat.size() {
+                let value: f64 = lat.get_value(i).unwrap().unwrap().try_into().unwrap();
+                assert!(value > 53.0);
+            }
+            rows += batch.row_count();
+        }
+
+        assert_eq!(rows, 12);
+    }
+
+    #[test]
+    fn execute_limit() {
+        let df = uk_cities().limit(literal_u64(5)).unwrap();
+
+        let plan = QueryPlanner::create_physical_plan(&df.plan).unwrap();
+
+        let rows: usize = plan
+            .execute()
+            .unwrap()
+            .map(|it| it.unwrap().row_count())
+            .sum();
+        assert_eq!(rows, 5);
+
+        let first = plan.execute().unwrap().next().unwrap().unwrap();
+        assert_eq!(
+            first.field(0).unwrap().get_value(0).unwrap(),
+            Some(ArrowValue::StringType(
+                "Elgin, Scotland, the UK".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn format_physical_plan() {
+        let df = uk_cities()
+            .filter(column("lat").gt(literal_double(53.0)))
+            .project(vec![column("city")]);
+
+        let plan = QueryPlanner::create_physical_plan(&df.plan).unwrap();
+        let formatted = plan.format_plan();
+        let lines: Vec<&str> = formatted.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "ProjectionExec: [\"#0\"]");
+        assert_eq!(lines[1], "\tSelectionExec: #1 > 53.0");
+        assert!(lines[2].starts_with("\t\tScanExec:"));
+    }
+
+    #[test]
+    fn bad_queries_are_errors() {
+        let unknown_column = uk_cities().project(vec![column("population")]);
+        assert!(matches!(
+            QueryPlanner::create_physical_plan(&unknown_column.plan),
+            Err(UnakiteError::Schema(_))
+        ));
+
+        let non_boolean_filter = uk_cities().filter(column("lat"));
+        let plan = QueryPlanner::create_physical_plan(&non_boolean_filter.plan).unwrap();
+        let first = plan.execute().unwrap().next().unwrap();
+        assert!(matches!(first, Err(UnakiteError::Type(_))));
+    }
+}
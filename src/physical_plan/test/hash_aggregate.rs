@@ -0,0 +1,227 @@
+#[cfg(test)]
+pub mod test {
+    use std::collections::HashMap;
+
+    use arrow::datatypes::DataType;
+
+    use crate::{
+        logical_plan::{
+            data_frame::DataFrame,
+            helper::{avg, column, count, count_distinct, max, min, sum},
+            macro_utils::literal_i64,
+        },
+        test_util::{execute, sales},
+    };
+
+    #[test]
+    fn aggregate_by_string_key() {
+        let df = sales().aggregate(
+            vec![column("region")],
+            vec![sum("quantity"), max("price"), min("quantity")],
+        );
+
+        let batches = execute(&df);
+        assert_eq!(batches.len(), 1);
+
+        let batch = &batches[0];
+        assert_eq!(batch.row_count(), 3);
+        assert_eq!(batch.column_count(), 4);
+
+        let mut results: HashMap<String, (i64, f64, i64)> = HashMap::new();
+        for i in 0..batch.row_count() {
+            results.insert(
+                batch
+                    .field(0)
+                    .unwrap()
+                    .get_value(i)
+                    .unwrap()
+                    .unwrap()
+                    .try_into()
+                    .unwrap(),
+                (
+                    batch
+                        .field(1)
+                        .unwrap()
+                        .get_value(i)
+                        .unwrap()
+                        .unwrap()
+                        .try_into()
+                        .unwrap(),
+                    batch
+                        .field(2)
+                        .unwrap()
+                        .get_value(i)
+                        .unwrap()
+                        .unwrap()
+                        .try_into()
+                        .unwrap(),
+                    batch
+                        .field(3)
+                        .unwrap()
+                        .get_value(i)
+                        .unwrap()
+                        .unwrap()
+                        .try_into()
+                        .unwrap(),
+                ),
+            );
+        }
+
+        assert_eq!(results["North"], (20, 10.5, 2));
+        assert_eq!(results["South"], (11, 9.5, 1));
+        assert_eq!(results["East"], (27, 2.75, 7));
+    }
+
+    #[test]
+    fn aggregate_by_composite_key() {
+        let df = sales().aggregate(
+            vec![column("region"), column("product")],
+            vec![sum("quantity")],
+        );
+
+        let batch = &execute(&df)[0];
+        assert_eq!(batch.row_count(), 6);
+
+        let mut results: HashMap<(String, String), i64> = HashMap::new();
+        for i in 0..batch.row_count() {
+            results.insert(
+                (
+                    batch
+                        .field(0)
+                        .unwrap()
+                        .get_value(i)
+                        .unwrap()
+                        .unwrap()
+                        .try_into()
+                        .unwrap(),
+                    batch
+                        .field(1)
+                        .unwrap()
+                        .get_value(i)
+                        .unwrap()
+                        .unwrap()
+                        .try_into()
+                        .unwrap(),
+                ),
+                batch
+                    .field(2)
+                    .unwrap()
+                    .get_value(i)
+                    .unwrap()
+                    .unwrap()
+                    .try_into()
+                    .unwrap(),
+            );
+        }
+
+        assert_eq!(results[&("North".to_string(), "Widget".to_string())], 15);
+        assert_eq!(results[&("North".to_string(), "Gadget".to_string())], 5);
+        assert_eq!(results[&("South".to_string(), "Widget".to_string())], 10);
+        assert_eq!(results[&("South".to_string(), "Gadget".to_string())], 1);
+        assert_eq!(results[&("East".to_string(), "Widget".to_string())], 15);
+        assert_eq!(results[&("East".to_string(), "Gizmo".to_string())], 12);
+    }
+
+    #[test]
+    fn aggregate_without_grouping() {
+        let df = sales().aggregate(vec![], vec![sum("quantity"), max("price")]);
+
+        let batch = &execute(&df)[0];
+        assert_eq!(batch.row_count(), 1);
+
+        let total: i64 = batch
+            .field(0)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let highest: f64 = batch
+            .field(1)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(total, 58);
+        assert_eq!(highest, 10.5);
+    }
+
+    #[test]
+    fn aggregate_avg_and_counts() {
+        let df = sales().aggregate(
+            vec![column("region")],
+            vec![avg("quantity"), count("quantity"), count_distinct("price")],
+        );
+
+        let batch = &execute(&df)[0];
+        assert_eq!(batch.schema.fields[1].data_type, DataType::Float64);
+        assert_eq!(batch.schema.fields[2].data_type, DataType::Int32);
+
+        let mut results: HashMap<String, (f64, i32, i32)> = HashMap::new();
+        for i in 0..batch.row_count() {
+            results.insert(
+                batch
+                    .field(0)
+                    .unwrap()
+                    .get_value(i)
+                    .unwrap()
+                    .unwrap()
+                    .try_into()
+                    .unwrap(),
+                (
+                    batch
+                        .field(1)
+                        .unwrap()
+                        .get_value(i)
+                        .unwrap()
+                        .unwrap()
+                        .try_into()
+                        .unwrap(),
+                    batch
+                        .field(2)
+                        .unwrap()
+                        .get_value(i)
+                        .unwrap()
+                        .unwrap()
+                        .try_into()
+                        .unwrap(),
+                    batch
+                        .field(3)
+                        .unwrap()
+                        .get_value(i)
+                        .unwrap()
+                        .unwrap()
+                        .try_into()
+                        .unwrap(),
+                ),
+            );
+        }
+
+        assert_eq!(results["North"], (5.0, 4, 3));
+        assert_eq!(results["South"], (11.0 / 3.0, 3, 3));
+        assert_eq!(results["East"], (9.0, 3, 2));
+    }
+
+    #[test]
+    fn aggregate_empty_input_without_grouping() {
+        let empty = sales().filter(column("quantity").gt(literal_i64(100)));
+        let df = empty.aggregate(vec![], vec![count("quantity"), sum("quantity")]);
+
+        let batch = &execute(&df)[0];
+        assert_eq!(batch.row_count(), 1);
+
+        let rows: i32 = batch
+            .field(0)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(rows, 0);
+        assert!(batch.field(1).unwrap().to_array().is_null(0));
+    }
+}
@@ -0,0 +1,289 @@
+#[cfg(test)]
+pub mod test {
+    use std::collections::HashMap;
+
+    use arrow::{
+        array::Array,
+        datatypes::{DataType, Field},
+    };
+
+    use crate::{
+        error::UnakiteError,
+        logical_plan::{
+            data_frame::{DataFrame, Frame},
+            join::JoinType,
+        },
+        query_planner::QueryPlanner,
+        test_util::{csv, execute, float_values, int_values, regions, sales, sales_named},
+    };
+
+    #[test]
+    fn inner_join() {
+        let df = sales().join(regions(), JoinType::Inner, on(&[("region", "region")]));
+
+        let batches = execute(&df);
+        let rows: usize = batches.iter().map(|it| it.row_count()).sum();
+        assert_eq!(rows, 7);
+
+        for batch in &batches {
+            assert_eq!(batch.column_count(), 6);
+            for i in 0..batch.row_count() {
+                let region: String = batch
+                    .field(0)
+                    .unwrap()
+                    .get_value(i)
+                    .unwrap()
+                    .unwrap()
+                    .try_into()
+                    .unwrap();
+                let manager: String = batch
+                    .field(5)
+                    .unwrap()
+                    .get_value(i)
+                    .unwrap()
+                    .unwrap()
+                    .try_into()
+                    .unwrap();
+                let expected = if region == "North" { "Alice" } else { "Bob" };
+                assert_eq!(manager, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn left_join_pads_unmatched_rows() {
+        let df = sales().join(regions(), JoinType::Left, on(&[("region", "region")]));
+
+        let mut managers: HashMap<String, usize> = HashMap::new();
+        let mut rows = 0;
+        for batch in execute(&df) {
+            let manager = batch.field(5).unwrap().to_array();
+            for i in 0..batch.row_count() {
+                let key = if manager.is_null(i) {
+                    "NULL".to_string()
+                } else {
+                    batch
+                        .field(5)
+                        .unwrap()
+                        .get_value(i)
+                        .unwrap()
+                        .unwrap()
+                        .try_into()
+                        .unwrap()
+                };
+                *managers.entry(key).or_default() += 1;
+                rows += 1;
+            }
+        }
+
+        assert_eq!(rows, 10);
+        assert_eq!(managers["Alice"], 4);
+        assert_eq!(managers["Bob"], 3);
+        assert_eq!(managers["NULL"], 3);
+    }
+
+    #[test]
+    fn right_join_pads_unmatched_rows() {
+        let df = sales().join(regions(), JoinType::Right, on(&[("region", "region")]));
+
+        let mut rows = 0;
+        let mut unmatched = vec![];
+        for batch in execute(&df) {
+            let left_region = batch.field(0).unwrap().to_array();
+            for i in 0..batch.row_count() {
+                if left_region.is_null(i) {
+                    let manager: String = batch
+                        .field(5)
+                        .unwrap()
+                        .get_value(i)
+                        .unwrap()
+                        .unwrap()
+                        .try_into()
+                        .unwrap();
+                    unmatched.push(manager);
+                }
+                rows += 1;
+            }
+        }
+
+        assert_eq!(rows, 8);
+        assert_eq!(unmatched, vec!["Carol".to_string()]);
+    }
+
+    #[test]
+    fn composite_key_join() {
+        let df = sales().join(
+            targets(),
+            JoinType::Inner,
+            on(&[("region", "region"), ("product", "product")]),
+        );
+
+        let mut totals: HashMap<(String, String), (i64, i64)> = HashMap::new();
+        for batch in execute(&df) {
+            assert_eq!(batch.column_count(), 7);
+            for i in 0..batch.row_count() {
+                let entry = totals
+                    .entry((
+                        batch
+                            .field(0)
+                            .unwrap()
+                            .get_value(i)
+                            .unwrap()
+                            .unwrap()
+                            .try_into()
+                            .unwrap(),
+                        batch
+                            .field(1)
+                            .unwrap()
+                            .get_value(i)
+                            .unwrap()
+                            .unwrap()
+                            .try_into()
+                            .unwrap(),
+                    ))
+                    .or_default();
+                let quantity: i64 = batch
+                    .field(2)
+                    .unwrap()
+                    .get_value(i)
+                    .unwrap()
+                    .unwrap()
+                    .try_into()
+                    .unwrap();
+                entry.0 += quantity;
+                entry.1 = batch
+                    .field(6)
+                    .unwrap()
+                    .get_value(i)
+                    .unwrap()
+                    .unwrap()
+                    .try_into()
+                    .unwrap();
+            }
+        }
+
+        assert_eq!(totals.len(), 3);
+        assert_eq!(totals[&("North".into(), "Widget".into())], (15, 20));
+        assert_eq!(totals[&("North".into(), "Gadget".into())], (5, 4));
+        assert_eq!(totals[&("South".into(), "Widget".into())], (10, 8));
+    }
+
+    #[test]
+    fn keys_are_compared_in_their_common_type() {
+        // Int64 quantities meet Float64 prices as floats, 2.5 must not match a quantity of 2
+        let df = sales().join(
+            sales_named("prices"),
+            JoinType::Inner,
+            on(&[("quantity", "price")]),
+        );
+
+        let batches = execute(&df);
+        let rows: usize = batches.iter().map(|it| it.row_count()).sum();
+        assert_eq!(rows, 1);
+        assert_eq!(int_values(&batches, 2), vec![10]);
+        assert_eq!(float_values(&batches, 7), vec![10.0]);
+
+        let df = sales().join(regions(), JoinType::Inner, on(&[("quantity", "region")]));
+        assert!(matches!(
+            QueryPlanner::create_physical_plan(&df.plan),
+            Err(UnakiteError::Plan(_))
+        ));
+    }
+
+    #[test]
+    fn full_join_pads_both_sides() {
+        let df = sales().join(regions(), JoinType::Full, on(&[("region", "region")]));
+
+        let mut rows = 0;
+        let mut left_only = 0;
+        let mut right_only = 0;
+        for batch in execute(&df) {
+            let left_region = batch.field(0).unwrap().to_array();
+            let right_region = batch.field(4).unwrap().to_array();
+            for i in 0..batch.row_count() {
+                if left_region.is_null(i) {
+                    right_only += 1;
+                }
+                if right_region.is_null(i) {
+                    left_only += 1;
+                }
+                rows += 1;
+            }
+        }
+
+        assert_eq!(rows, 11);
+        assert_eq!(left_only, 3);
+        assert_eq!(right_only, 1);
+    }
+
+    #[test]
+    fn semi_and_anti_joins() {
+        let semi = sales().join(regions(), JoinType::LeftSemi, on(&[("region", "region")]));
+        let anti = sales().join(regions(), JoinType::LeftAnti, on(&[("region", "region")]));
+
+        let semi = execute(&semi);
+        assert_eq!(semi.len(), 1);
+        assert_eq!(semi[0].column_count(), 4);
+        assert_eq!(semi[0].row_count(), 7);
+
+        let anti = execute(&anti);
+        assert_eq!(anti.len(), 1);
+        assert_eq!(anti[0].row_count(), 3);
+        for i in 0..anti[0].row_count() {
+            let region: String = anti[0]
+                .field(0)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            assert_eq!(region, "East");
+        }
+    }
+
+    #[test]
+    fn cross_join() {
+        let df = sales().join(regions(), JoinType::Cross, vec![]);
+
+        let rows: usize = execute(&df).iter().map(|it| it.row_count()).sum();
+        assert_eq!(rows, 30);
+    }
+
+    #[test]
+    fn cross_join_rejects_keys() {
+        let df = sales().join(regions(), JoinType::Cross, on(&[("region", "region")]));
+
+        assert!(QueryPlanner::create_physical_plan(&df.plan).is_err());
+    }
+
+    #[test]
+    fn format_join_plan() {
+        let df = sales().join(regions(), JoinType::Inner, on(&[("region", "region")]));
+        let plan = QueryPlanner::create_physical_plan(&df.plan).unwrap();
+
+        assert!(
+            plan.format_plan()
+                .starts_with("HashJoinExec: joinType=Inner, on=[(0, 0)]\n")
+        );
+    }
+
+    fn on(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(l, r)| (l.to_string(), r.to_string()))
+            .collect()
+    }
+
+    fn targets() -> Frame {
+        csv(
+            "targets",
+            "targets.csv",
+            vec![
+                Field::new("region", DataType::Utf8, false),
+                Field::new("product", DataType::Utf8, false),
+                Field::new("target", DataType::Int64, false),
+            ],
+        )
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+pub mod test {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{Array, Float64Array, Int32Array, Int64Array, RecordBatch as ArrowRecordBatch},
+        datatypes::{DataType, Field, Schema},
+    };
+
+    use crate::{
+        datatypes::{column_vector::ColumnVector, record_batch::RecordBatch},
+        error::UnakiteError,
+        physical_plan::{
+            expressions::{
+                Expression,
+                math::{
+                    MathExpression, MathPair,
+                    math_expression::{
+                        AddPlan, DividePlan, ModuloPlan, MultiplyPlan, SubtractPlan,
+                    },
+                },
+            },
+            test::util::{column, double, long},
+        },
+    };
+
+    #[test]
+    fn promote_operand_types() {
+        // Int32 * Int64 is computed as Int64
+        let result = evaluate(MultiplyPlan, column(0), column(1)).unwrap();
+        let result = result.to_array();
+        let values = result.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(
+            values.iter().collect::<Vec<_>>(),
+            vec![Some(10), None, Some(-90)]
+        );
+
+        // Integers meet floats as Float64
+        let result = evaluate(SubtractPlan, column(2), column(0)).unwrap();
+        let result = result.to_array();
+        let values = result.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(
+            values.iter().collect::<Vec<_>>(),
+            vec![Some(0.5), None, None]
+        );
+    }
+
+    #[test]
+    fn broadcast_literals() {
+        let result = evaluate(AddPlan, column(1), long(100)).unwrap();
+        let result = result.to_array();
+        let values = result.as_any().downcast_ref::<Int64Array>().unwrap();
+        assert_eq!(
+            values.iter().collect::<Vec<_>>(),
+            vec![Some(110), Some(120), Some(130)]
+        );
+
+        let result = evaluate(DividePlan, double(1.0), column(2)).unwrap();
+        let result = result.to_array();
+        let values = result.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(values.value(0), 1.0 / 1.5);
+
+        // Two literals still give a value per row
+        let result = evaluate(MultiplyPlan, long(6), long(7)).unwrap();
+        assert_eq!(result.to_array().len(), 3);
+    }
+
+    #[test]
+    fn overflow_and_division_by_zero() {
+        let overflow = evaluate(AddPlan, long(i64::MAX), column(1));
+        assert!(matches!(overflow, Err(UnakiteError::Execution(_))));
+
+        let by_zero = evaluate(DividePlan, column(1), long(0));
+        assert!(
+            matches!(by_zero, Err(UnakiteError::Execution(msg)) if msg.contains("Division by zero"))
+        );
+        let by_zero = evaluate(ModuloPlan, column(1), long(0));
+        assert!(matches!(by_zero, Err(UnakiteError::Execution(_))));
+
+        // Floating point division follows IEEE 754
+        let result = evaluate(DividePlan, column(2), double(0.0)).unwrap();
+        let result = result.to_array();
+        let values = result.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert!(values.value(0).is_infinite());
+
+        let strings = evaluate(AddPlan, column(3), long(1));
+        assert!(matches!(strings, Err(UnakiteError::Type(_))));
+    }
+
+    fn evaluate(
+        inner: impl MathPair + 'static,
+        l: Expression,
+        r: Expression,
+    ) -> crate::error::Result<ColumnVector> {
+        let expr = MathExpression {
+            inner: Arc::new(inner),
+            l: Arc::new(l),
+            r: Arc::new(r),
+        };
+        expr.evaluate(batch())
+    }
+
+    fn batch() -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int64, false),
+            Field::new("c", DataType::Float64, true),
+            Field::new("d", DataType::Utf8, false),
+        ]);
+
+        ArrowRecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![Some(1), None, Some(-3)])),
+                Arc::new(Int64Array::from(vec![10, 20, 30])),
+                Arc::new(Float64Array::from(vec![Some(1.5), Some(2.5), None])),
+                Arc::new(arrow::array::StringArray::from(vec!["x", "y", "z"])),
+            ],
+        )
+        .unwrap()
+        .into()
+    }
+}
@@ -1,4 +1,91 @@
 pub mod boolean_expression_test;
 pub mod aggregate_expression;
 pub mod cast_expression;
-pub mod test_compute;
\ No newline at end of file
+pub mod test_compute;
+pub mod execute_plan;
+pub mod hash_aggregate;
+pub mod hash_join;
+pub mod math_expression;
+pub mod null_expression;
+pub mod conditional_expression;
+
+/** Expression and batch factories shared by the expression tests */
+#[cfg(test)]
+pub mod util {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{Array, ArrayRef, AsArray, Int64Array, RecordBatch as ArrowRecordBatch},
+        datatypes::{Field as ArrowField, Schema as ArrowSchema},
+    };
+
+    use crate::{
+        datatypes::record_batch::RecordBatch,
+        physical_plan::expressions::{
+            Expression, LiteralExpression,
+            booleans::{BooleanExpression, BooleanPair},
+            column_expressions::ColumnExpression,
+            literal_expressions::{
+                LiteralDoubleExpression, LiteralLongExpression, LiteralNullExpression,
+                LiteralStringExpression,
+            },
+        },
+    };
+
+    pub fn evaluate(expr: Expression, batch: RecordBatch) -> ArrayRef {
+        expr.evaluate(batch).unwrap().to_array()
+    }
+
+    pub fn boolean(inner: impl BooleanPair + 'static, l: Expression, r: Expression) -> Expression {
+        Expression::Boolean(Arc::new(BooleanExpression {
+            inner: Arc::new(inner),
+            l: Arc::new(l),
+            r: Arc::new(r),
+        }))
+    }
+
+    pub fn column(i: usize) -> Expression {
+        Expression::Column(ColumnExpression { i })
+    }
+
+    pub fn long(value: i64) -> Expression {
+        Expression::Literal(LiteralExpression::Int64(LiteralLongExpression::new(value)))
+    }
+
+    pub fn double(value: f64) -> Expression {
+        Expression::Literal(LiteralExpression::Float64(LiteralDoubleExpression::new(
+            value,
+        )))
+    }
+
+    pub fn string(value: &str) -> Expression {
+        Expression::Literal(LiteralExpression::String(LiteralStringExpression::new(
+            value.to_string(),
+        )))
+    }
+
+    pub fn null() -> Expression {
+        Expression::Literal(LiteralExpression::Null(LiteralNullExpression))
+    }
+
+    pub fn booleans(array: &ArrayRef) -> Vec<Option<bool>> {
+        array.as_boolean().iter().collect()
+    }
+
+    /** A batch with one nullable Int64 column named `a` */
+    pub fn longs(values: Vec<Option<i64>>) -> RecordBatch {
+        batch(vec![("a", Arc::new(Int64Array::from(values)))])
+    }
+
+    /** A batch of nullable columns with the given names and values */
+    pub fn batch(columns: Vec<(&str, ArrayRef)>) -> RecordBatch {
+        let fields: Vec<ArrowField> = columns
+            .iter()
+            .map(|(name, array)| ArrowField::new(*name, array.data_type().clone(), true))
+            .collect();
+        let arrays = columns.into_iter().map(|(_, array)| array).collect();
+        ArrowRecordBatch::try_new(Arc::new(ArrowSchema::new(fields)), arrays)
+            .unwrap()
+            .into()
+    }
+}
@@ -0,0 +1,174 @@
+#[cfg(test)]
+pub mod test {
+    use std::sync::Arc;
+
+    use arrow::{
+        array::{Array, AsArray, BooleanArray, Float64Array, Int64Array},
+        datatypes::{DataType, Float64Type, Int64Type},
+    };
+
+    use crate::physical_plan::{
+        expressions::{
+            Expression,
+            booleans::impl_expressions::{
+                AndPlan, DistinctPlan, EqPlan, GtPlan, NotDistinctPlan, OrPlan,
+            },
+            nulls::{CoalesceExpression, IsNullExpression, NullIfExpression},
+        },
+        test::util::{batch, boolean, booleans, column, evaluate, long, longs, null},
+    };
+
+    #[test]
+    fn kleene_and_or() {
+        let batch = batch(vec![
+            (
+                "l",
+                Arc::new(BooleanArray::from(vec![
+                    Some(true),
+                    Some(true),
+                    Some(true),
+                    Some(false),
+                    Some(false),
+                    Some(false),
+                    None,
+                    None,
+                    None,
+                ])),
+            ),
+            (
+                "r",
+                Arc::new(BooleanArray::from(vec![
+                    Some(true),
+                    Some(false),
+                    None,
+                    Some(true),
+                    Some(false),
+                    None,
+                    Some(true),
+                    Some(false),
+                    None,
+                ])),
+            ),
+        ]);
+
+        let result = evaluate(boolean(AndPlan, column(0), column(1)), batch.clone());
+        assert_eq!(
+            booleans(&result),
+            vec![
+                Some(true),
+                Some(false),
+                None,
+                Some(false),
+                Some(false),
+                Some(false),
+                None,
+                Some(false),
+                None
+            ]
+        );
+
+        let result = evaluate(boolean(OrPlan, column(0), column(1)), batch);
+        assert_eq!(
+            booleans(&result),
+            vec![
+                Some(true),
+                Some(true),
+                Some(true),
+                Some(true),
+                Some(false),
+                None,
+                Some(true),
+                None,
+                None
+            ]
+        );
+    }
+
+    #[test]
+    fn comparisons_with_null_are_null() {
+        let batch = longs(vec![Some(1), None, Some(3)]);
+
+        let result = evaluate(boolean(GtPlan, column(0), long(2)), batch.clone());
+        assert_eq!(booleans(&result), vec![Some(false), None, Some(true)]);
+
+        // A NULL literal takes the type of the column it is compared with
+        let result = evaluate(boolean(EqPlan, column(0), null()), batch.clone());
+        assert_eq!(booleans(&result), vec![None, None, None]);
+        let result = evaluate(boolean(EqPlan, null(), null()), batch);
+        assert_eq!(booleans(&result), vec![None, None, None]);
+    }
+
+    #[test]
+    fn is_null_and_is_distinct_from() {
+        let batch = batch(vec![
+            ("a", Arc::new(Int64Array::from(vec![Some(1), None, None]))),
+            (
+                "b",
+                Arc::new(Int64Array::from(vec![Some(1), Some(2), None])),
+            ),
+        ]);
+
+        let is_null = |negated| {
+            Expression::IsNull(Arc::new(IsNullExpression {
+                expr: Arc::new(column(0)),
+                negated,
+            }))
+        };
+        let result = evaluate(is_null(false), batch.clone());
+        assert_eq!(booleans(&result), vec![Some(false), Some(true), Some(true)]);
+        let result = evaluate(is_null(true), batch.clone());
+        assert_eq!(
+            booleans(&result),
+            vec![Some(true), Some(false), Some(false)]
+        );
+
+        let result = evaluate(boolean(DistinctPlan, column(0), column(1)), batch.clone());
+        assert_eq!(
+            booleans(&result),
+            vec![Some(false), Some(true), Some(false)]
+        );
+        let result = evaluate(
+            boolean(NotDistinctPlan, column(0), column(1)),
+            batch.clone(),
+        );
+        assert_eq!(booleans(&result), vec![Some(true), Some(false), Some(true)]);
+        let result = evaluate(boolean(DistinctPlan, column(0), null()), batch);
+        assert_eq!(
+            booleans(&result),
+            vec![Some(true), Some(false), Some(false)]
+        );
+    }
+
+    #[test]
+    fn coalesce_and_nullif() {
+        let batch = batch(vec![
+            ("a", Arc::new(Int64Array::from(vec![Some(1), None, None]))),
+            (
+                "b",
+                Arc::new(Float64Array::from(vec![Some(0.5), Some(2.5), None])),
+            ),
+        ]);
+
+        // Arguments are promoted to their common type
+        let coalesce = |args| Expression::Coalesce(Arc::new(CoalesceExpression { args }));
+        let result = evaluate(coalesce(vec![column(0), column(1), long(7)]), batch.clone());
+        let values: Vec<Option<f64>> = result.as_primitive::<Float64Type>().iter().collect();
+        assert_eq!(values, vec![Some(1.0), Some(2.5), Some(7.0)]);
+
+        let result = evaluate(coalesce(vec![null(), column(0)]), batch.clone());
+        assert_eq!(result.data_type(), &DataType::Int64);
+        assert_eq!(result.null_count(), 2);
+
+        let nullif = |l, r| {
+            Expression::NullIf(Arc::new(NullIfExpression {
+                l: Arc::new(l),
+                r: Arc::new(r),
+            }))
+        };
+        let result = evaluate(nullif(column(0), long(1)), batch.clone());
+        assert_eq!(result.null_count(), 3);
+        let result = evaluate(nullif(long(5), column(0)), batch);
+        let values: Vec<Option<i64>> = result.as_primitive::<Int64Type>().iter().collect();
+        assert_eq!(values, vec![Some(5), Some(5), Some(5)]);
+    }
+}
@@ -1,5 +1,4 @@
 #[cfg(test)]
-
 pub mod test {
     use arrow::{
         array::{AsArray, Float64Builder, Int32Array},
@@ -29,24 +28,25 @@ pub mod test {
 
     #[test]
     fn compute_aggregate() {
-        let mut b = ArrowVectorBuilder::new(&DataType::Int64);
-
-        b.set_all(&mut vec![
-            Some(ArrowValue::Int64Type(0 as i64)),
-            Some(ArrowValue::Int64Type(1 as i64)),
-            Some(ArrowValue::Int64Type(2 as i64)),
-            Some(ArrowValue::Int64Type(3 as i64)),
-            Some(ArrowValue::Int64Type(4 as i64)),
-            Some(ArrowValue::Int64Type(5 as i64)),
-            Some(ArrowValue::Int64Type(6 as i64)),
-            Some(ArrowValue::Int64Type(7 as i64)),
-            Some(ArrowValue::Int64Type(8 as i64)),
-            Some(ArrowValue::Int64Type(9 as i64)),
-        ]);
-
-        let v = b.build();
-
-        let a = v.get_vector();
+        let mut b = ArrowVectorBuilder::new(&DataType::Int64).unwrap();
+
+        b.set_all(&mut [
+            Some(ArrowValue::Int64Type(0)),
+            Some(ArrowValue::Int64Type(1)),
+            Some(ArrowValue::Int64Type(2)),
+            Some(ArrowValue::Int64Type(3)),
+            Some(ArrowValue::Int64Type(4)),
+            Some(ArrowValue::Int64Type(5)),
+            Some(ArrowValue::Int64Type(6)),
+            Some(ArrowValue::Int64Type(7)),
+            Some(ArrowValue::Int64Type(8)),
+            Some(ArrowValue::Int64Type(9)),
+        ])
+        .unwrap();
+
+        let v = b.build().unwrap();
+
+        let a = v.get_vector().unwrap();
 
         let min = compute::min(a.field.as_primitive::<Int64Type>()).unwrap();
         println!("{}", min)
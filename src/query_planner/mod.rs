@@ -0,0 +1,453 @@
+pub mod test;
+
+use std::sync::Arc;
+
+use arrow::datatypes::DataType;
+
+use crate::{
+    datatypes::{coercion::comparison_type, schema::Schema},
+    error::{Result, UnakiteError},
+    logical_plan::{
+        AggregateExpr, LogicalPlan,
+        join::JoinType,
+        expr::{
+            Expr, LiteralExpression as LogicalLiteral, MathExpression as LogicalMath,
+            NumericExpression,
+        },
+    },
+    physical_plan::{
+        PhysicaPlan,
+        expressions::{
+            Expression, LiteralExpression,
+            aggregates::{
+                AggregateExpression, AvgExpression, CountDistinctExpression, CountExpression,
+                MaxExpression, MinExpression, SumExpression,
+            },
+            booleans::{
+                BooleanExpression, BooleanPair,
+                impl_expressions::{
+                    AndPlan, DistinctPlan, EqPlan, GtPlan, GteqPlan, LtPlan, LteqPlan, NeqPlan,
+                    NotDistinctPlan, OrPlan,
+                },
+            },
+            case_expression::CaseExpression,
+            cast_expression::CastExpression,
+            column_expressions::ColumnExpression,
+            literal_expressions::*,
+            math::{
+                MathExpression, MathPair, NegativeExpression,
+                math_expression::{AddPlan, DividePlan, ModuloPlan, MultiplyPlan, SubtractPlan},
+            },
+            nulls::{CoalesceExpression, IsNullExpression, NullIfExpression},
+            predicates::{BetweenExpression, InListExpression, NotExpression},
+            scalar_function::ScalarFunctionExpression,
+        },
+        copy_exec::CopyExec,
+        hash_aggregate_exec::HashAggregateExec,
+        hash_join_exec::HashJoinExec,
+        limit_exec::LimitExec,
+        projection_exec::ProjectionExec,
+        scan_exec::ScanExec,
+        selection_exec::SelectionExec,
+        sort_exec::{PhysicalSortExpr, SortExec},
+    },
+};
+
+/**
+ * The query planner creates a physical query plan from a logical query plan.
+ */
+pub struct QueryPlanner;
+
+impl QueryPlanner {
+    /** Create a physical plan from a logical plan */
+    pub fn create_physical_plan(plan: &LogicalPlan) -> Result<PhysicaPlan> {
+        match plan {
+            LogicalPlan::ScanPlan(scan) => Ok(PhysicaPlan::Scan(
+                ScanExec::new(scan.data_source.clone(), scan.projection.clone())?
+                    .with_filters(scan.filters.clone()),
+            )),
+
+            LogicalPlan::SelectionPlan(selection) => {
+                let input = Self::create_physical_plan(&selection.input)?;
+                let filter_expr =
+                    Self::create_physical_expr(&selection.expr.state, &selection.input)?;
+
+                Ok(PhysicaPlan::Selection(SelectionExec::new(
+                    Arc::new(input),
+                    filter_expr,
+                )))
+            }
+
+            LogicalPlan::ProjectionPlan(projection) => {
+                let input = Self::create_physical_plan(&projection.input)?;
+                let projection_expr = projection
+                    .expr
+                    .iter()
+                    .map(|it| Self::create_physical_expr(&it.state, &projection.input))
+                    .collect::<Result<Vec<Expression>>>()?;
+
+                let schema = Arc::new(projection.schema()?.to_arrow());
+
+                Ok(PhysicaPlan::Projection(ProjectionExec::new(
+                    Arc::new(input),
+                    schema,
+                    projection_expr,
+                )))
+            }
+
+            LogicalPlan::LimitPlan(limit) => {
+                let input = Self::create_physical_plan(&limit.input)?;
+
+                Ok(PhysicaPlan::Limit(LimitExec::new(
+                    Arc::new(input),
+                    limit.limit,
+                )))
+            }
+
+            LogicalPlan::AggregatePlan(aggregate) => {
+                let input = Self::create_physical_plan(&aggregate.input)?;
+                let group_expr = aggregate
+                    .group_expr
+                    .iter()
+                    .map(|it| Self::create_physical_expr(&it.state, &aggregate.input))
+                    .collect::<Result<Vec<Expression>>>()?;
+                let aggregate_expr = aggregate
+                    .aggregate_expr
+                    .iter()
+                    .map(|it| Self::create_aggregate_expr(it, &aggregate.input))
+                    .collect::<Result<Vec<Arc<dyn AggregateExpression>>>>()?;
+
+                Ok(PhysicaPlan::HashAggregate(HashAggregateExec::new(
+                    Arc::new(input),
+                    group_expr,
+                    aggregate_expr,
+                    aggregate.schema()?.as_ref().clone(),
+                )))
+            }
+
+            LogicalPlan::SortPlan(sort) => {
+                let input = Self::create_physical_plan(&sort.input)?;
+                let order_by = sort
+                    .order_by
+                    .iter()
+                    .map(|it| {
+                        Ok(PhysicalSortExpr {
+                            expr: Self::create_physical_expr(&it.expr.state, &sort.input)?,
+                            asc: it.asc,
+                        })
+                    })
+                    .collect::<Result<Vec<PhysicalSortExpr>>>()?;
+
+                Ok(PhysicaPlan::Sort(SortExec::new(Arc::new(input), order_by)))
+            }
+
+            LogicalPlan::CopyPlan(copy) => {
+                let input = Self::create_physical_plan(&copy.input)?;
+
+                Ok(PhysicaPlan::Copy(CopyExec::new(
+                    Arc::new(input),
+                    copy.path.clone(),
+                    copy.format,
+                    copy.options.clone(),
+                )))
+            }
+
+            LogicalPlan::JoinPlan(join) => {
+                if join.join_type == JoinType::Cross && !join.on.is_empty() {
+                    return Err(UnakiteError::Plan(format!(
+                        "Cross joins take no join keys, got {:?}",
+                        join.on
+                    )));
+                }
+
+                let left = Self::create_physical_plan(&join.left)?;
+                let right = Self::create_physical_plan(&join.right)?;
+
+                let left_schema = join.left.schema()?;
+                let right_schema = join.right.schema()?;
+                let on = join
+                    .on
+                    .iter()
+                    .map(|(l, r)| {
+                        Ok((
+                            Self::column_index(&left_schema, l)?,
+                            Self::column_index(&right_schema, r)?,
+                        ))
+                    })
+                    .collect::<Result<Vec<(usize, usize)>>>()?;
+
+                // Both keys of a pair are compared in their common type, like the operands of =
+                let key_types = on
+                    .iter()
+                    .map(|(l, r)| {
+                        let l = &left_schema.fields[*l];
+                        let r = &right_schema.fields[*r];
+                        comparison_type(&[l.data_type.clone(), r.data_type.clone()]).map_err(|_| {
+                            UnakiteError::Plan(format!(
+                                "Cannot join {} of type {} with {} of type {}",
+                                l.name, l.data_type, r.name, r.data_type
+                            ))
+                        })
+                    })
+                    .collect::<Result<Vec<DataType>>>()?;
+
+                Ok(PhysicaPlan::HashJoin(HashJoinExec::new(
+                    Arc::new(left),
+                    Arc::new(right),
+                    join.join_type.clone(),
+                    on,
+                    key_types,
+                    join.schema()?.as_ref().clone(),
+                )))
+            }
+        }
+    }
+
+    /** Create a physical expression from a logical expression */
+    pub fn create_physical_expr(expr: &Expr, input: &LogicalPlan) -> Result<Expression> {
+        match expr {
+            Expr::ColumnExpr(column) => Ok(Expression::Column(ColumnExpression {
+                i: Self::column_index(input.schema()?.as_ref(), &column.name)?,
+            })),
+
+            Expr::LiteralExpr(literal) => Ok(Expression::Literal(Self::create_literal(literal))),
+
+            Expr::AliasExpr(alias) => Self::create_physical_expr(&alias.expr.state, input),
+
+            Expr::EqOpExpr(expr) => Self::create_boolean_expr(EqPlan, &expr.l, &expr.r, input),
+            Expr::NeqExpr(expr) => Self::create_boolean_expr(NeqPlan, &expr.l, &expr.r, input),
+            Expr::GtExpr(expr) => Self::create_boolean_expr(GtPlan, &expr.l, &expr.r, input),
+            Expr::GtEqExpr(expr) => Self::create_boolean_expr(GteqPlan, &expr.l, &expr.r, input),
+            Expr::LtExpr(expr) => Self::create_boolean_expr(LtPlan, &expr.l, &expr.r, input),
+            Expr::LtEqExpr(expr) => Self::create_boolean_expr(LteqPlan, &expr.l, &expr.r, input),
+            Expr::AndExpr(expr) => Self::create_boolean_expr(AndPlan, &expr.l, &expr.r, input),
+            Expr::OrExpr(expr) => Self::create_boolean_expr(OrPlan, &expr.l, &expr.r, input),
+            Expr::IsDistinctFromExpr(expr) => {
+                Self::create_boolean_expr(DistinctPlan, &expr.l, &expr.r, input)
+            }
+            Expr::IsNotDistinctFromExpr(expr) => {
+                Self::create_boolean_expr(NotDistinctPlan, &expr.l, &expr.r, input)
+            }
+
+            Expr::MathExpr(math) => match math {
+                LogicalMath::AddExpr(expr) => {
+                    Self::create_math_expr(AddPlan, &expr.l, &expr.r, input)
+                }
+                LogicalMath::SubExpr(expr) => {
+                    Self::create_math_expr(SubtractPlan, &expr.l, &expr.r, input)
+                }
+                LogicalMath::MulExpr(expr) => {
+                    Self::create_math_expr(MultiplyPlan, &expr.l, &expr.r, input)
+                }
+                LogicalMath::DivExpr(expr) => {
+                    Self::create_math_expr(DividePlan, &expr.l, &expr.r, input)
+                }
+                LogicalMath::ModExpr(expr) => {
+                    Self::create_math_expr(ModuloPlan, &expr.l, &expr.r, input)
+                }
+            },
+
+            Expr::ScalarFunctionExpr(function) => Ok(Expression::ScalarFunction(Arc::new(
+                ScalarFunctionExpression {
+                    fun: function.fun,
+                    args: function
+                        .args
+                        .iter()
+                        .map(|it| Self::create_physical_expr(it, input))
+                        .collect::<Result<Vec<Expression>>>()?,
+                },
+            ))),
+
+            Expr::CastExpr(cast) => Ok(Expression::Cast(Arc::new(CastExpression {
+                expr: Arc::new(Self::create_physical_expr(&cast.expr, input)?),
+                data_type: cast.data_type.clone(),
+                try_cast: cast.try_cast,
+            }))),
+
+            Expr::IsNullExpr(is_null) => Ok(Expression::IsNull(Arc::new(IsNullExpression {
+                expr: Arc::new(Self::create_physical_expr(&is_null.expr, input)?),
+                negated: is_null.negated,
+            }))),
+
+            Expr::CoalesceExpr(coalesce) => Ok(Expression::Coalesce(Arc::new(
+                CoalesceExpression {
+                    args: coalesce
+                        .args
+                        .iter()
+                        .map(|it| Self::create_physical_expr(it, input))
+                        .collect::<Result<Vec<Expression>>>()?,
+                },
+            ))),
+
+            Expr::NullIfExpr(nullif) => Ok(Expression::NullIf(Arc::new(NullIfExpression {
+                l: Arc::new(Self::create_physical_expr(&nullif.l, input)?),
+                r: Arc::new(Self::create_physical_expr(&nullif.r, input)?),
+            }))),
+
+            Expr::NotExpr(not) => Ok(Expression::Not(Arc::new(NotExpression {
+                expr: Arc::new(Self::create_physical_expr(&not.expr, input)?),
+            }))),
+
+            Expr::NegativeExpr(negative) => {
+                Ok(Expression::Negative(Arc::new(NegativeExpression {
+                    expr: Arc::new(Self::create_physical_expr(&negative.expr, input)?),
+                })))
+            }
+
+            Expr::CaseExpr(case) => {
+                let physical = |expr: &Arc<Expr>| -> Result<Arc<Expression>> {
+                    Ok(Arc::new(Self::create_physical_expr(expr, input)?))
+                };
+                Ok(Expression::Case(Arc::new(CaseExpression {
+                    expr: case.expr.as_ref().map(physical).transpose()?,
+                    when_then: case
+                        .when_then
+                        .iter()
+                        .map(|(when, then)| {
+                            Ok((
+                                Self::create_physical_expr(when, input)?,
+                                Self::create_physical_expr(then, input)?,
+                            ))
+                        })
+                        .collect::<Result<Vec<_>>>()?,
+                    else_expr: case.else_expr.as_ref().map(physical).transpose()?,
+                })))
+            }
+
+            Expr::InListExpr(in_list) => Ok(Expression::InList(Arc::new(InListExpression {
+                expr: Arc::new(Self::create_physical_expr(&in_list.expr, input)?),
+                list: in_list
+                    .list
+                    .iter()
+                    .map(|it| Self::create_physical_expr(it, input))
+                    .collect::<Result<Vec<Expression>>>()?,
+                negated: in_list.negated,
+            }))),
+
+            Expr::BetweenExpr(between) => Ok(Expression::Between(Arc::new(BetweenExpression {
+                expr: Arc::new(Self::create_physical_expr(&between.expr, input)?),
+                low: Arc::new(Self::create_physical_expr(&between.low, input)?),
+                high: Arc::new(Self::create_physical_expr(&between.high, input)?),
+                negated: between.negated,
+            }))),
+
+            Expr::MaxExpr(_)
+            | Expr::MinExpr(_)
+            | Expr::SumExpr(_)
+            | Expr::AvgExpr(_)
+            | Expr::CountExpr(_)
+            | Expr::CountDistinctExpr(_) => Err(UnakiteError::Plan(format!(
+                "Aggregate expression {} is only valid inside an aggregate",
+                expr
+            ))),
+        }
+    }
+
+    /** Create a physical aggregate expression from a logical aggregate expression */
+    pub fn create_aggregate_expr(
+        expr: &AggregateExpr,
+        input: &LogicalPlan,
+    ) -> Result<Arc<dyn AggregateExpression>> {
+        match expr {
+            AggregateExpr::Sum(sum) => Ok(Arc::new(SumExpression {
+                expr: Self::create_physical_expr(&sum.expr.state, input)?,
+            })),
+            AggregateExpr::Min(min) => Ok(Arc::new(MinExpression {
+                expr: Self::create_physical_expr(&min.expr.state, input)?,
+            })),
+            AggregateExpr::Max(max) => Ok(Arc::new(MaxExpression {
+                expr: Self::create_physical_expr(&max.expr.state, input)?,
+            })),
+            AggregateExpr::Avg(avg) => Ok(Arc::new(AvgExpression {
+                expr: Self::create_physical_expr(&avg.expr.state, input)?,
+            })),
+            AggregateExpr::Count(count) => Ok(Arc::new(CountExpression {
+                expr: Self::create_physical_expr(&count.expr.state, input)?,
+            })),
+            AggregateExpr::CountDistinct(count) => Ok(Arc::new(CountDistinctExpression {
+                expr: Self::create_physical_expr(&count.expr.state, input)?,
+            })),
+        }
+    }
+
+    fn column_index(schema: &Schema, name: &str) -> Result<usize> {
+        schema
+            .fields
+            .iter()
+            .position(|it| it.name == name)
+            .ok_or_else(|| UnakiteError::Schema(format!("No column named '{}'", name)))
+    }
+
+    fn create_boolean_expr(
+        inner: impl BooleanPair + 'static,
+        l: &Expr,
+        r: &Expr,
+        input: &LogicalPlan,
+    ) -> Result<Expression> {
+        let l = Self::create_physical_expr(l, input)?;
+        let r = Self::create_physical_expr(r, input)?;
+
+        Ok(Expression::Boolean(Arc::new(BooleanExpression {
+            inner: Arc::new(inner),
+            l: Arc::new(l),
+            r: Arc::new(r),
+        })))
+    }
+
+    fn create_math_expr(
+        inner: impl MathPair + 'static,
+        l: &Expr,
+        r: &Expr,
+        input: &LogicalPlan,
+    ) -> Result<Expression> {
+        let l = Self::create_physical_expr(l, input)?;
+        let r = Self::create_physical_expr(r, input)?;
+
+        Ok(Expression::Math(Arc::new(MathExpression {
+            inner: Arc::new(inner),
+            l: Arc::new(l),
+            r: Arc::new(r),
+        })))
+    }
+
+    fn create_literal(literal: &LogicalLiteral) -> LiteralExpression {
+        match literal {
+            LogicalLiteral::NullExpr(_) => LiteralExpression::Null(LiteralNullExpression),
+            LogicalLiteral::StringExpr(lit) => {
+                LiteralExpression::String(LiteralStringExpression::new(lit.value.clone()))
+            }
+            LogicalLiteral::Numeric(numeric) => match numeric {
+                NumericExpression::Integer8Expr(lit) => {
+                    LiteralExpression::Int8(LiteralSmallExpression::new(lit.value))
+                }
+                NumericExpression::Integer16Expr(lit) => {
+                    LiteralExpression::Int16(LiteralShortExpression::new(lit.value))
+                }
+                NumericExpression::Integer32Expr(lit) => {
+                    LiteralExpression::Int32(LiteralIntExpression::new(lit.value))
+                }
+                NumericExpression::Integer64Expr(lit) => {
+                    LiteralExpression::Int64(LiteralLongExpression::new(lit.value))
+                }
+                NumericExpression::UInteger8Expr(lit) => {
+                    LiteralExpression::UInt8(LiteralUSmallExpression::new(lit.value))
+                }
+                NumericExpression::UInteger16Expr(lit) => {
+                    LiteralExpression::UInt16(LiteralUShortExpression::new(lit.value))
+                }
+                NumericExpression::UInteger32Expr(lit) => {
+                    LiteralExpression::UInt32(LiteralUIntExpression::new(lit.value))
+                }
+                NumericExpression::UInteger64Expr(lit) => {
+                    LiteralExpression::UInt64(LiteralULongExpression::new(lit.value))
+                }
+                NumericExpression::FloatExpr(lit) => {
+                    LiteralExpression::Float32(LiteralFloatExpression::new(lit.value))
+                }
+                NumericExpression::DoubleExpr(lit) => {
+                    LiteralExpression::Float64(LiteralDoubleExpression::new(lit.value))
+                }
+            },
+        }
+    }
+}
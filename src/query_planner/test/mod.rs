@@ -0,0 +1,57 @@
+#[cfg(test)]
+#[allow(clippy::module_inception)]
+pub mod test {
+    use crate::{
+        logical_plan::{
+            data_frame::DataFrame,
+            helper::column,
+            macro_utils::{literal_double, literal_u64},
+        },
+        physical_plan::PhysicaPlan,
+        query_planner::QueryPlanner,
+        test_util::uk_cities,
+    };
+
+    #[test]
+    fn plan_projection_and_selection() {
+        let df = uk_cities()
+            .filter(column("lat").gt(literal_double(53.0)))
+            .project(vec![column("lng"), column("city")])
+            .limit(literal_u64(5))
+            .unwrap();
+
+        let plan = QueryPlanner::create_physical_plan(&df.plan).unwrap();
+
+        let PhysicaPlan::Limit(_) = &plan else {
+            panic!("Expected a LimitExec at the root")
+        };
+
+        let projection = plan.children()[0].clone();
+        let PhysicaPlan::Projection(_) = projection.as_ref() else {
+            panic!("Expected a ProjectionExec below the limit")
+        };
+
+        let names: Vec<String> = plan.schema().fields.into_iter().map(|it| it.name).collect();
+        assert_eq!(names, vec!["lng".to_string(), "city".to_string()]);
+
+        let selection = projection.children()[0].clone();
+        let PhysicaPlan::Selection(_) = selection.as_ref() else {
+            panic!("Expected a SelectionExec below the projection")
+        };
+
+        let scan = selection.children()[0].clone();
+        let PhysicaPlan::Scan(_) = scan.as_ref() else {
+            panic!("Expected a ScanExec at the leaf")
+        };
+        assert_eq!(scan.schema().fields.len(), 3);
+    }
+
+    #[test]
+    fn plan_unknown_column() {
+        let df = uk_cities().project(vec![column("population")]);
+
+        let plan = QueryPlanner::create_physical_plan(&df.plan);
+
+        assert!(plan.is_err());
+    }
+}
@@ -0,0 +1,114 @@
+use arrow::datatypes::DataType;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOperator {
+    Eq,
+    Neq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+    And,
+    Or,
+    Plus,
+    Minus,
+    Multiply,
+    Divide,
+    Modulo,
+    IsDistinctFrom,
+    IsNotDistinctFrom,
+}
+
+/** SQL expression as written by the user, before names are resolved against a schema */
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlExpr {
+    /** Column reference, possibly qualified as `table.column` */
+    Identifier(String),
+    Long(i64),
+    Double(f64),
+    String(String),
+    Null,
+    /** `*` in a select list or `COUNT(*)` */
+    Wildcard,
+    BinaryExpr {
+        l: Box<SqlExpr>,
+        op: BinaryOperator,
+        r: Box<SqlExpr>,
+    },
+    Not(Box<SqlExpr>),
+    /** `expr IS NULL`, or `expr IS NOT NULL` when negated */
+    IsNull {
+        expr: Box<SqlExpr>,
+        negated: bool,
+    },
+    Negative(Box<SqlExpr>),
+    /** `expr [NOT] IN (list)` */
+    InList {
+        expr: Box<SqlExpr>,
+        list: Vec<SqlExpr>,
+        negated: bool,
+    },
+    /** `expr [NOT] BETWEEN low AND high` */
+    Between {
+        expr: Box<SqlExpr>,
+        low: Box<SqlExpr>,
+        high: Box<SqlExpr>,
+        negated: bool,
+    },
+    /** `CASE [operand] WHEN .. THEN .. [ELSE ..] END`, without an operand the WHENs are conditions */
+    Case {
+        operand: Option<Box<SqlExpr>>,
+        when_then: Vec<(SqlExpr, SqlExpr)>,
+        else_result: Option<Box<SqlExpr>>,
+    },
+    Function {
+        name: String,
+        args: Vec<SqlExpr>,
+        distinct: bool,
+    },
+    /** `CAST(expr AS type)`, or `TRY_CAST(expr AS type)` which yields null where CAST fails */
+    Cast {
+        expr: Box<SqlExpr>,
+        data_type: DataType,
+        try_cast: bool,
+    },
+    Alias {
+        expr: Box<SqlExpr>,
+        alias: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlSort {
+    pub expr: SqlExpr,
+    pub asc: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlSelect {
+    pub projection: Vec<SqlExpr>,
+    pub table: String,
+    pub selection: Option<SqlExpr>,
+    pub group_by: Vec<SqlExpr>,
+    pub having: Option<SqlExpr>,
+    pub order_by: Vec<SqlSort>,
+    pub limit: Option<usize>,
+}
+
+/** `name value` or `name (value, ...)` in the WITH clause of a COPY statement */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlCopyOption {
+    pub name: String,
+    pub values: Vec<SqlExpr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SqlStatement {
+    Select(SqlSelect),
+    /** `COPY (query) TO 'path' [WITH (option, ...)]` */
+    Copy {
+        query: SqlSelect,
+        path: String,
+        options: Vec<SqlCopyOption>,
+    },
+}
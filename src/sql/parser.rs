@@ -0,0 +1,623 @@
+use arrow::datatypes::{DataType, TimeUnit};
+
+use crate::error::{Result, UnakiteError};
+use crate::sql::{
+    ast::{BinaryOperator, SqlCopyOption, SqlExpr, SqlSelect, SqlSort, SqlStatement},
+    tokenizer::{Keyword, Symbol, Token, Tokenizer},
+};
+
+// Binding power of operators, higher binds tighter
+const OR_PRECEDENCE: u8 = 10;
+const AND_PRECEDENCE: u8 = 20;
+const NOT_PRECEDENCE: u8 = 25;
+const COMPARISON_PRECEDENCE: u8 = 30;
+const ADDITIVE_PRECEDENCE: u8 = 40;
+const MULTIPLICATIVE_PRECEDENCE: u8 = 50;
+const UNARY_PRECEDENCE: u8 = 60;
+
+/**
+ * Recursive descent parser for SELECT and COPY statements. Expressions are parsed with a Pratt parser so
+ * that operator precedence is driven by the binding powers above.
+ */
+pub struct SqlParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl SqlParser {
+    pub fn new(tokens: Vec<Token>) -> Self {
+        SqlParser { tokens, pos: 0 }
+    }
+
+    /** Tokenize and parse a single SELECT statement */
+    pub fn parse_sql(sql: &str) -> Result<SqlSelect> {
+        let tokens = Tokenizer::new(sql).tokenize()?;
+        SqlParser::new(tokens).parse_select()
+    }
+
+    /** Tokenize and parse a single SELECT or COPY statement */
+    pub fn parse_statement_sql(sql: &str) -> Result<SqlStatement> {
+        let tokens = Tokenizer::new(sql).tokenize()?;
+        SqlParser::new(tokens).parse_statement()
+    }
+
+    pub fn parse_statement(&mut self) -> Result<SqlStatement> {
+        if !self.consume_keyword(Keyword::Copy) {
+            return Ok(SqlStatement::Select(self.parse_select()?));
+        }
+
+        self.expect_symbol(Symbol::LeftParen)?;
+        let query = self.parse_query()?;
+        self.expect_symbol(Symbol::RightParen)?;
+
+        self.expect_keyword(Keyword::To)?;
+        let path = match self.next() {
+            Some(Token::String(path)) => path,
+            other => {
+                return Err(UnakiteError::Plan(format!(
+                    "COPY expects a quoted path after TO, got {:?}",
+                    other
+                )));
+            }
+        };
+
+        let options = if self.consume_keyword(Keyword::With) {
+            self.parse_copy_options()?
+        } else {
+            vec![]
+        };
+
+        self.expect_end()?;
+        Ok(SqlStatement::Copy {
+            query,
+            path,
+            options,
+        })
+    }
+
+    pub fn parse_select(&mut self) -> Result<SqlSelect> {
+        let select = self.parse_query()?;
+        self.expect_end()?;
+        Ok(select)
+    }
+
+    /** A SELECT statement that may be followed by more tokens, e.g. inside COPY */
+    fn parse_query(&mut self) -> Result<SqlSelect> {
+        self.expect_keyword(Keyword::Select)?;
+        let projection = self.parse_select_list()?;
+
+        self.expect_keyword(Keyword::From)?;
+        let table = self.parse_identifier()?;
+
+        let selection = if self.consume_keyword(Keyword::Where) {
+            Some(self.parse_expr(0)?)
+        } else {
+            None
+        };
+
+        let group_by = if self.consume_keyword(Keyword::Group) {
+            self.expect_keyword(Keyword::By)?;
+            self.parse_expr_list()?
+        } else {
+            vec![]
+        };
+
+        let having = if self.consume_keyword(Keyword::Having) {
+            Some(self.parse_expr(0)?)
+        } else {
+            None
+        };
+
+        let order_by = if self.consume_keyword(Keyword::Order) {
+            self.expect_keyword(Keyword::By)?;
+            self.parse_order_by()?
+        } else {
+            vec![]
+        };
+
+        let limit = if self.consume_keyword(Keyword::Limit) {
+            match self.next() {
+                Some(Token::Number(n)) => Some(n.parse::<usize>().map_err(|_| {
+                    UnakiteError::Plan(format!("LIMIT expects a non-negative integer, got {}", n))
+                })?),
+                other => {
+                    return Err(UnakiteError::Plan(format!(
+                        "LIMIT expects a number, got {:?}",
+                        other
+                    )));
+                }
+            }
+        } else {
+            None
+        };
+
+        Ok(SqlSelect {
+            projection,
+            table,
+            selection,
+            group_by,
+            having,
+            order_by,
+            limit,
+        })
+    }
+
+    fn expect_end(&mut self) -> Result<()> {
+        self.consume_symbol(Symbol::Semicolon);
+        if let Some(token) = self.peek() {
+            return Err(UnakiteError::Plan(format!(
+                "Unexpected token {:?} after end of statement",
+                token
+            )));
+        }
+        Ok(())
+    }
+
+    /** `(name value, name (value, ...), ...)`, values are checked by the planner */
+    fn parse_copy_options(&mut self) -> Result<Vec<SqlCopyOption>> {
+        self.expect_symbol(Symbol::LeftParen)?;
+        let mut options = vec![];
+
+        loop {
+            let name = self.parse_identifier()?;
+            let values = if self.consume_symbol(Symbol::LeftParen) {
+                let values = self.parse_expr_list()?;
+                self.expect_symbol(Symbol::RightParen)?;
+                values
+            } else {
+                vec![self.parse_expr(0)?]
+            };
+            options.push(SqlCopyOption { name, values });
+
+            if !self.consume_symbol(Symbol::Comma) {
+                break;
+            }
+        }
+
+        self.expect_symbol(Symbol::RightParen)?;
+        Ok(options)
+    }
+
+    fn parse_select_list(&mut self) -> Result<Vec<SqlExpr>> {
+        let mut items = vec![];
+
+        loop {
+            let expr = self.parse_expr(0)?;
+
+            // The AS keyword is optional before an alias
+            let item = if self.consume_keyword(Keyword::As) {
+                SqlExpr::Alias {
+                    expr: Box::new(expr),
+                    alias: self.parse_identifier()?,
+                }
+            } else if let Some(Token::Identifier(alias)) = self.peek().cloned() {
+                self.pos += 1;
+                SqlExpr::Alias {
+                    expr: Box::new(expr),
+                    alias,
+                }
+            } else {
+                expr
+            };
+            items.push(item);
+
+            if !self.consume_symbol(Symbol::Comma) {
+                return Ok(items);
+            }
+        }
+    }
+
+    fn parse_expr_list(&mut self) -> Result<Vec<SqlExpr>> {
+        let mut items = vec![self.parse_expr(0)?];
+        while self.consume_symbol(Symbol::Comma) {
+            items.push(self.parse_expr(0)?);
+        }
+        Ok(items)
+    }
+
+    fn parse_order_by(&mut self) -> Result<Vec<SqlSort>> {
+        let mut items = vec![];
+
+        loop {
+            let expr = self.parse_expr(0)?;
+            let asc = if self.consume_keyword(Keyword::Desc) {
+                false
+            } else {
+                self.consume_keyword(Keyword::Asc);
+                true
+            };
+            items.push(SqlSort { expr, asc });
+
+            if !self.consume_symbol(Symbol::Comma) {
+                return Ok(items);
+            }
+        }
+    }
+
+    /** Parse an expression whose operators all bind tighter than `precedence` */
+    pub fn parse_expr(&mut self, precedence: u8) -> Result<SqlExpr> {
+        let mut expr = self.parse_prefix()?;
+
+        loop {
+            if COMPARISON_PRECEDENCE > precedence
+                && let Some(postfix) = self.parse_postfix(&expr)?
+            {
+                expr = postfix;
+                continue;
+            }
+
+            let Some((op, op_precedence)) = self.peek_infix() else {
+                break;
+            };
+            if op_precedence <= precedence {
+                break;
+            }
+            self.pos += 1;
+
+            let r = self.parse_expr(op_precedence)?;
+            expr = SqlExpr::BinaryExpr {
+                l: Box::new(expr),
+                op,
+                r: Box::new(r),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    /** `IS ..`, `[NOT] IN (..)` or `[NOT] BETWEEN .. AND ..` after `expr`, if one follows */
+    fn parse_postfix(&mut self, expr: &SqlExpr) -> Result<Option<SqlExpr>> {
+        let keyword = |token: Option<&Token>| match token {
+            Some(Token::Keyword(keyword)) => Some(*keyword),
+            _ => None,
+        };
+
+        let negated = keyword(self.peek()) == Some(Keyword::Not)
+            && matches!(
+                keyword(self.tokens.get(self.pos + 1)),
+                Some(Keyword::In | Keyword::Between)
+            );
+        let pos = self.pos + negated as usize;
+
+        let postfix = match keyword(self.tokens.get(pos)) {
+            Some(Keyword::Is) => {
+                self.pos = pos + 1;
+                self.parse_is(expr.clone())?
+            }
+            Some(Keyword::In) => {
+                self.pos = pos + 1;
+                self.expect_symbol(Symbol::LeftParen)?;
+                let list = self.parse_expr_list()?;
+                self.expect_symbol(Symbol::RightParen)?;
+                SqlExpr::InList {
+                    expr: Box::new(expr.clone()),
+                    list,
+                    negated,
+                }
+            }
+            Some(Keyword::Between) => {
+                self.pos = pos + 1;
+                // The bounds bind tighter than AND, which separates them
+                let low = self.parse_expr(COMPARISON_PRECEDENCE)?;
+                self.expect_keyword(Keyword::And)?;
+                let high = self.parse_expr(COMPARISON_PRECEDENCE)?;
+                SqlExpr::Between {
+                    expr: Box::new(expr.clone()),
+                    low: Box::new(low),
+                    high: Box::new(high),
+                    negated,
+                }
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(postfix))
+    }
+
+    /** `[NOT] NULL` or `[NOT] DISTINCT FROM expr` after IS */
+    fn parse_is(&mut self, expr: SqlExpr) -> Result<SqlExpr> {
+        let negated = self.consume_keyword(Keyword::Not);
+
+        if self.consume_keyword(Keyword::Null) {
+            return Ok(SqlExpr::IsNull {
+                expr: Box::new(expr),
+                negated,
+            });
+        }
+
+        if self.consume_keyword(Keyword::Distinct) {
+            self.expect_keyword(Keyword::From)?;
+            let op = match negated {
+                true => BinaryOperator::IsNotDistinctFrom,
+                false => BinaryOperator::IsDistinctFrom,
+            };
+            return Ok(SqlExpr::BinaryExpr {
+                l: Box::new(expr),
+                op,
+                r: Box::new(self.parse_expr(COMPARISON_PRECEDENCE)?),
+            });
+        }
+
+        Err(UnakiteError::Plan(format!(
+            "Expected NULL or DISTINCT FROM after IS, got {:?}",
+            self.peek()
+        )))
+    }
+
+    fn parse_prefix(&mut self) -> Result<SqlExpr> {
+        match self.next() {
+            Some(Token::Number(n)) => parse_number(&n),
+            Some(Token::String(s)) => Ok(SqlExpr::String(s)),
+            Some(Token::Keyword(Keyword::Null)) => Ok(SqlExpr::Null),
+            Some(Token::Symbol(Symbol::Star)) => Ok(SqlExpr::Wildcard),
+            Some(Token::Symbol(Symbol::Minus)) => Ok(SqlExpr::Negative(Box::new(
+                self.parse_expr(UNARY_PRECEDENCE)?,
+            ))),
+            Some(Token::Symbol(Symbol::LeftParen)) => {
+                let expr = self.parse_expr(0)?;
+                self.expect_symbol(Symbol::RightParen)?;
+                Ok(expr)
+            }
+            Some(Token::Keyword(Keyword::Case)) => self.parse_case(),
+            Some(Token::Keyword(Keyword::Cast)) => self.parse_cast(false),
+            Some(Token::Keyword(Keyword::TryCast)) => self.parse_cast(true),
+            Some(Token::Keyword(Keyword::Not)) => {
+                Ok(SqlExpr::Not(Box::new(self.parse_expr(NOT_PRECEDENCE)?)))
+            }
+            Some(Token::Identifier(name)) => {
+                if self.consume_symbol(Symbol::LeftParen) {
+                    return self.parse_function(name);
+                }
+
+                let mut name = name;
+                while self.consume_symbol(Symbol::Period) {
+                    name = format!("{}.{}", name, self.parse_identifier()?);
+                }
+                Ok(SqlExpr::Identifier(name))
+            }
+            Some(token) => Err(UnakiteError::Plan(format!(
+                "Unexpected token {:?} in expression",
+                token
+            ))),
+            None => Err(UnakiteError::Plan(
+                "Unexpected end of statement, expected an expression".to_string(),
+            )),
+        }
+    }
+
+    /** Arguments of a function call, the opening parenthesis has been consumed */
+    fn parse_function(&mut self, name: String) -> Result<SqlExpr> {
+        let distinct = self.consume_keyword(Keyword::Distinct);
+
+        let args = if self.consume_symbol(Symbol::RightParen) {
+            vec![]
+        } else {
+            let args = self.parse_expr_list()?;
+            self.expect_symbol(Symbol::RightParen)?;
+            args
+        };
+
+        Ok(SqlExpr::Function {
+            name,
+            args,
+            distinct,
+        })
+    }
+
+    /** `[operand] WHEN .. THEN .. [ELSE ..] END` after CASE */
+    fn parse_case(&mut self) -> Result<SqlExpr> {
+        let operand = match self.peek() {
+            Some(Token::Keyword(Keyword::When)) => None,
+            _ => Some(Box::new(self.parse_expr(0)?)),
+        };
+
+        let mut when_then = vec![];
+        self.expect_keyword(Keyword::When)?;
+        loop {
+            let when = self.parse_expr(0)?;
+            self.expect_keyword(Keyword::Then)?;
+            when_then.push((when, self.parse_expr(0)?));
+
+            if !self.consume_keyword(Keyword::When) {
+                break;
+            }
+        }
+
+        let else_result = if self.consume_keyword(Keyword::Else) {
+            Some(Box::new(self.parse_expr(0)?))
+        } else {
+            None
+        };
+        self.expect_keyword(Keyword::End)?;
+
+        Ok(SqlExpr::Case {
+            operand,
+            when_then,
+            else_result,
+        })
+    }
+
+    /** `(expr AS type)` after CAST or TRY_CAST */
+    fn parse_cast(&mut self, try_cast: bool) -> Result<SqlExpr> {
+        self.expect_symbol(Symbol::LeftParen)?;
+        let expr = self.parse_expr(0)?;
+        self.expect_keyword(Keyword::As)?;
+        let data_type = self.parse_data_type()?;
+        self.expect_symbol(Symbol::RightParen)?;
+
+        Ok(SqlExpr::Cast {
+            expr: Box::new(expr),
+            data_type,
+            try_cast,
+        })
+    }
+
+    /** A SQL type name, with the length of a string type or the precision and scale of a decimal */
+    fn parse_data_type(&mut self) -> Result<DataType> {
+        let name = self.parse_identifier()?.to_ascii_uppercase();
+        let params = if self.consume_symbol(Symbol::LeftParen) {
+            let mut params = vec![self.parse_type_param()?];
+            while self.consume_symbol(Symbol::Comma) {
+                params.push(self.parse_type_param()?);
+            }
+            self.expect_symbol(Symbol::RightParen)?;
+            params
+        } else {
+            vec![]
+        };
+
+        let data_type = match (name.as_str(), params.as_slice()) {
+            ("BOOLEAN" | "BOOL", []) => DataType::Boolean,
+            ("TINYINT", []) => DataType::Int8,
+            ("SMALLINT", []) => DataType::Int16,
+            ("INT" | "INTEGER", []) => DataType::Int32,
+            ("BIGINT", []) => DataType::Int64,
+            ("UTINYINT", []) => DataType::UInt8,
+            ("USMALLINT", []) => DataType::UInt16,
+            ("UINTEGER", []) => DataType::UInt32,
+            ("UBIGINT", []) => DataType::UInt64,
+            ("REAL" | "FLOAT", []) => DataType::Float32,
+            ("DOUBLE", []) => {
+                if let Some(Token::Identifier(word)) = self.peek()
+                    && word.eq_ignore_ascii_case("PRECISION")
+                {
+                    self.pos += 1;
+                }
+                DataType::Float64
+            }
+            ("VARCHAR" | "CHAR" | "TEXT" | "STRING", [] | [_]) => DataType::Utf8,
+            ("DATE", []) => DataType::Date32,
+            ("TIMESTAMP", []) => DataType::Timestamp(TimeUnit::Microsecond, None),
+            ("DECIMAL" | "NUMERIC", []) => DataType::Decimal128(18, 3),
+            ("DECIMAL" | "NUMERIC", [precision]) => decimal_type(*precision, 0)?,
+            ("DECIMAL" | "NUMERIC", [precision, scale]) => decimal_type(*precision, *scale)?,
+            _ => {
+                return Err(UnakiteError::Plan(format!(
+                    "Unknown data type {}{}",
+                    name,
+                    match params.is_empty() {
+                        true => String::new(),
+                        false => format!("{:?}", params),
+                    }
+                )));
+            }
+        };
+        Ok(data_type)
+    }
+
+    fn parse_type_param(&mut self) -> Result<u64> {
+        match self.next() {
+            Some(Token::Number(n)) => n
+                .parse::<u64>()
+                .map_err(|_| UnakiteError::Plan(format!("Invalid type parameter {}", n))),
+            other => Err(UnakiteError::Plan(format!(
+                "Expected a type parameter, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    fn peek_infix(&self) -> Option<(BinaryOperator, u8)> {
+        let infix = match self.peek()? {
+            Token::Keyword(Keyword::Or) => (BinaryOperator::Or, OR_PRECEDENCE),
+            Token::Keyword(Keyword::And) => (BinaryOperator::And, AND_PRECEDENCE),
+            Token::Symbol(symbol) => match symbol {
+                Symbol::Eq => (BinaryOperator::Eq, COMPARISON_PRECEDENCE),
+                Symbol::Neq => (BinaryOperator::Neq, COMPARISON_PRECEDENCE),
+                Symbol::Lt => (BinaryOperator::Lt, COMPARISON_PRECEDENCE),
+                Symbol::LtEq => (BinaryOperator::LtEq, COMPARISON_PRECEDENCE),
+                Symbol::Gt => (BinaryOperator::Gt, COMPARISON_PRECEDENCE),
+                Symbol::GtEq => (BinaryOperator::GtEq, COMPARISON_PRECEDENCE),
+                Symbol::Plus => (BinaryOperator::Plus, ADDITIVE_PRECEDENCE),
+                Symbol::Minus => (BinaryOperator::Minus, ADDITIVE_PRECEDENCE),
+                Symbol::Star => (BinaryOperator::Multiply, MULTIPLICATIVE_PRECEDENCE),
+                Symbol::Slash => (BinaryOperator::Divide, MULTIPLICATIVE_PRECEDENCE),
+                Symbol::Percent => (BinaryOperator::Modulo, MULTIPLICATIVE_PRECEDENCE),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some(infix)
+    }
+
+    fn parse_identifier(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Identifier(name)) => Ok(name),
+            other => Err(UnakiteError::Plan(format!(
+                "Expected an identifier, got {:?}",
+                other
+            ))),
+        }
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn consume_keyword(&mut self, keyword: Keyword) -> bool {
+        if self.peek() == Some(&Token::Keyword(keyword)) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn consume_symbol(&mut self, symbol: Symbol) -> bool {
+        if self.peek() == Some(&Token::Symbol(symbol)) {
+            self.pos += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect_keyword(&mut self, keyword: Keyword) -> Result<()> {
+        if self.consume_keyword(keyword) {
+            return Ok(());
+        }
+        Err(UnakiteError::Plan(format!(
+            "Expected {:?}, got {:?}",
+            keyword,
+            self.peek()
+        )))
+    }
+
+    fn expect_symbol(&mut self, symbol: Symbol) -> Result<()> {
+        if self.consume_symbol(symbol) {
+            return Ok(());
+        }
+        Err(UnakiteError::Plan(format!(
+            "Expected {:?}, got {:?}",
+            symbol,
+            self.peek()
+        )))
+    }
+}
+
+/** Decimals wider than 38 digits are stored in 256 bits */
+fn decimal_type(precision: u64, scale: u64) -> Result<DataType> {
+    if precision == 0 || precision > 76 || scale > precision {
+        return Err(UnakiteError::Plan(format!(
+            "Invalid decimal precision and scale ({}, {})",
+            precision, scale
+        )));
+    }
+
+    Ok(match precision <= 38 {
+        true => DataType::Decimal128(precision as u8, scale as i8),
+        false => DataType::Decimal256(precision as u8, scale as i8),
+    })
+}
+
+fn parse_number(n: &str) -> Result<SqlExpr> {
+    if let Ok(value) = n.parse::<i64>() {
+        return Ok(SqlExpr::Long(value));
+    }
+    n.parse::<f64>()
+        .map(SqlExpr::Double)
+        .map_err(|_| UnakiteError::Plan(format!("Invalid number literal {}", n)))
+}
@@ -0,0 +1,648 @@
+use std::{collections::HashMap, sync::Arc};
+
+use arrow::datatypes::DataType;
+
+use crate::{
+    datasource::sink::{Compression, WriteFormat, WriteOptions},
+    error::{Result, UnakiteError},
+    logical_plan::{
+        AggregateExpr, LogicalPlan,
+        data_frame::{DataFrame, Frame},
+        expr::{AsAlias, ExprRef},
+        helper::{call_function, case, coalesce, column, nullif},
+        macro_utils::{
+            AggregateAvg, AggregateCount, AggregateCountDistinct, AggregateMax, AggregateMin,
+            AggregateSum, literal_double, literal_float, literal_i8, literal_i16, literal_i32,
+            literal_i64, literal_null, literal_string, literal_u8, literal_u16, literal_u32,
+            literal_u64,
+        },
+        sort::SortExpr,
+    },
+    sql::{
+        ast::{BinaryOperator, SqlCopyOption, SqlExpr, SqlSelect, SqlSort, SqlStatement},
+        parser::SqlParser,
+    },
+};
+
+const AGGREGATE_FUNCTIONS: [&str; 5] = ["SUM", "MIN", "MAX", "AVG", "COUNT"];
+
+/**
+ * Creates a logical plan from a parsed SELECT statement. Table names are resolved against the
+ * given catalog of data frames.
+ */
+pub struct SqlPlanner;
+
+/** What SQL names resolve to while translating one clause */
+struct Scope<'a> {
+    plan: Arc<LogicalPlan>,
+    table: &'a str,
+    /**
+     * After aggregation, the GROUP BY and aggregate expressions of the query paired with the
+     * name of the aggregate output column that holds their value.
+     */
+    aggregated: Option<&'a [(SqlExpr, String)]>,
+}
+
+impl SqlPlanner {
+    /**
+     * Parse a SQL statement and plan it against the given tables. A COPY statement plans to a
+     * frame that writes the query result when executed and yields the number of rows written.
+     */
+    pub fn plan(sql: &str, tables: &HashMap<String, Frame>) -> Result<Frame> {
+        match SqlParser::parse_statement_sql(sql)? {
+            SqlStatement::Select(select) => Self::create_data_frame(&select, tables),
+            SqlStatement::Copy {
+                query,
+                path,
+                options,
+            } => {
+                let df = Self::create_data_frame(&query, tables)?;
+                let (format, options) = copy_options(&path, &options)?;
+                Ok(df.copy_to(&path, format, options))
+            }
+        }
+    }
+
+    pub fn create_data_frame(select: &SqlSelect, tables: &HashMap<String, Frame>) -> Result<Frame> {
+        let table = select.table.as_str();
+        let mut df = tables
+            .get(table)
+            .ok_or_else(|| UnakiteError::Plan(format!("No table named '{}'", table)))?
+            .clone();
+
+        if let Some(selection) = &select.selection {
+            let predicate = translate(selection, &Scope::new(&df, table))?;
+            df = df.filter(predicate);
+        }
+
+        let projection = expand_wildcards(&select.projection, &df)?;
+
+        // ORDER BY may refer to select list aliases
+        let order_by: Vec<SqlSort> = select
+            .order_by
+            .iter()
+            .map(|it| SqlSort {
+                expr: resolve_alias(&it.expr, &projection),
+                asc: it.asc,
+            })
+            .collect();
+
+        let mut aggregates: Vec<SqlExpr> = vec![];
+        for expr in projection
+            .iter()
+            .chain(select.having.iter())
+            .chain(order_by.iter().map(|it| &it.expr))
+        {
+            collect_aggregates(expr, &mut aggregates);
+        }
+
+        if aggregates.is_empty() && select.group_by.is_empty() {
+            if select.having.is_some() {
+                return Err(UnakiteError::Plan(
+                    "HAVING requires a GROUP BY clause or an aggregate function".to_string(),
+                ));
+            }
+
+            // Sorting before the projection lets ORDER BY use columns that are not selected
+            if !order_by.is_empty() {
+                let sort = translate_sort(&order_by, &Scope::new(&df, table))?;
+                df = df.sort(sort);
+            }
+
+            let expr = translate_all(&projection, &Scope::new(&df, table))?;
+            df = df.project(expr);
+        } else {
+            let scope = Scope::new(&df, table);
+            let group_expr = translate_all(&select.group_by, &scope)?;
+            let aggregate_expr = aggregates
+                .iter()
+                .map(|it| aggregate(it, &scope))
+                .collect::<Result<Vec<AggregateExpr>>>()?;
+            df = df.aggregate(group_expr, aggregate_expr);
+
+            let aggregated: Vec<(SqlExpr, String)> = select
+                .group_by
+                .iter()
+                .chain(aggregates.iter())
+                .cloned()
+                .zip(df.schema()?.fields.iter().map(|it| it.name.clone()))
+                .collect();
+
+            if let Some(having) = &select.having {
+                let predicate = translate(having, &Scope::aggregated(&df, table, &aggregated))?;
+                df = df.filter(predicate);
+            }
+
+            if !order_by.is_empty() {
+                let sort = translate_sort(&order_by, &Scope::aggregated(&df, table, &aggregated))?;
+                df = df.sort(sort);
+            }
+
+            let expr = translate_all(&projection, &Scope::aggregated(&df, table, &aggregated))?;
+            df = df.project(expr);
+        }
+
+        if let Some(limit) = select.limit {
+            df = df.limit(literal_u64(limit as u64))?;
+        }
+
+        Ok(df)
+    }
+}
+
+impl<'a> Scope<'a> {
+    fn new(df: &Frame, table: &'a str) -> Self {
+        Scope {
+            plan: df.plan.clone(),
+            table,
+            aggregated: None,
+        }
+    }
+
+    fn aggregated(df: &Frame, table: &'a str, aggregated: &'a [(SqlExpr, String)]) -> Self {
+        Scope {
+            plan: df.plan.clone(),
+            table,
+            aggregated: Some(aggregated),
+        }
+    }
+
+    /** Name of the input column a possibly qualified identifier refers to */
+    fn resolve_column(&self, name: &str) -> Result<String> {
+        let schema = self.plan.schema()?;
+        let exists = |name: &str| schema.fields.iter().any(|it| it.name == name);
+
+        if exists(name) {
+            return Ok(name.to_string());
+        }
+        if let Some((qualifier, unqualified)) = name.split_once('.')
+            && qualifier == self.table
+            && exists(unqualified)
+        {
+            return Ok(unqualified.to_string());
+        }
+        Err(UnakiteError::Schema(format!("No column named '{}'", name)))
+    }
+
+    fn data_type(&self, expr: &ExprRef) -> Result<DataType> {
+        Ok(expr.to_field(self.plan.clone())?.data_type)
+    }
+}
+
+fn translate_all(exprs: &[SqlExpr], scope: &Scope) -> Result<Vec<ExprRef>> {
+    exprs.iter().map(|it| translate(it, scope)).collect()
+}
+
+fn translate_sort(order_by: &[SqlSort], scope: &Scope) -> Result<Vec<SortExpr>> {
+    order_by
+        .iter()
+        .map(|it| Ok(SortExpr::new(translate(&it.expr, scope)?, it.asc)))
+        .collect()
+}
+
+fn translate(expr: &SqlExpr, scope: &Scope) -> Result<ExprRef> {
+    if let Some(aggregated) = scope.aggregated
+        && let Some((_, name)) = aggregated.iter().find(|(it, _)| it == expr)
+    {
+        return Ok(column(name));
+    }
+
+    match expr {
+        SqlExpr::Identifier(name) => {
+            let name = scope.resolve_column(name)?;
+            if scope.aggregated.is_some() {
+                return Err(UnakiteError::Plan(format!(
+                    "Column '{}' must appear in the GROUP BY clause or be used in an aggregate function",
+                    name
+                )));
+            }
+            Ok(column(&name))
+        }
+        SqlExpr::Long(value) => Ok(literal_i64(*value)),
+        SqlExpr::Double(value) => Ok(literal_double(*value)),
+        SqlExpr::String(value) => Ok(literal_string(value)),
+        SqlExpr::Null => Ok(literal_null()),
+        SqlExpr::Wildcard => Err(UnakiteError::Plan(
+            "'*' is only valid as a select item or in COUNT(*)".to_string(),
+        )),
+        SqlExpr::BinaryExpr { l, op, r } => translate_binary(l, *op, r, scope),
+        SqlExpr::Negative(inner) => match inner.as_ref() {
+            SqlExpr::Long(value) => Ok(literal_i64(-value)),
+            SqlExpr::Double(value) => Ok(literal_double(-value)),
+            _ => {
+                let expr = -translate(inner, scope)?;
+                scope.data_type(&expr)?;
+                Ok(expr)
+            }
+        },
+        SqlExpr::Not(inner) => {
+            let expr = !translate(inner, scope)?;
+            scope.data_type(&expr)?;
+            Ok(expr)
+        }
+        SqlExpr::InList {
+            expr,
+            list,
+            negated,
+        } => {
+            let value = translate(expr, scope)?;
+            let data_type = scope.data_type(&value)?;
+            let list = list
+                .iter()
+                .map(|it| translate_as(it, &data_type, scope))
+                .collect::<Result<Vec<ExprRef>>>()?;
+            let expr = value.in_list(list, *negated);
+            // Values that can never equal the expression are rejected while planning
+            scope.data_type(&expr)?;
+            Ok(expr)
+        }
+        SqlExpr::Between {
+            expr,
+            low,
+            high,
+            negated,
+        } => {
+            let value = translate(expr, scope)?;
+            let data_type = scope.data_type(&value)?;
+            let low = translate_as(low, &data_type, scope)?;
+            let high = translate_as(high, &data_type, scope)?;
+            let expr = match negated {
+                true => value.not_between(low, high),
+                false => value.between(low, high),
+            };
+            scope.data_type(&expr)?;
+            Ok(expr)
+        }
+        SqlExpr::Case {
+            operand,
+            when_then,
+            else_result,
+        } => {
+            let operand = operand
+                .as_ref()
+                .map(|it| translate(it, scope))
+                .transpose()?;
+            let operand_type = match &operand {
+                Some(operand) => Some(scope.data_type(operand)?),
+                None => None,
+            };
+
+            let when_then = when_then
+                .iter()
+                .map(|(when, then)| {
+                    let when = match &operand_type {
+                        Some(data_type) => translate_as(when, data_type, scope)?,
+                        None => translate(when, scope)?,
+                    };
+                    Ok((when, translate(then, scope)?))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let else_result = else_result
+                .as_ref()
+                .map(|it| translate(it, scope))
+                .transpose()?;
+
+            let expr = case(operand, when_then, else_result)?;
+            // Conditions and results of different types are rejected while planning
+            scope.data_type(&expr)?;
+            Ok(expr)
+        }
+        SqlExpr::IsNull { expr, negated } => {
+            let expr = translate(expr, scope)?;
+            Ok(match negated {
+                true => expr.is_not_null(),
+                false => expr.is_null(),
+            })
+        }
+        SqlExpr::Function { name, .. } if is_aggregate(name) => Err(UnakiteError::Plan(format!(
+            "Aggregate function {} is not allowed here",
+            name
+        ))),
+        SqlExpr::Function { distinct: true, .. } => Err(UnakiteError::Plan(
+            "DISTINCT is only supported with COUNT".to_string(),
+        )),
+        SqlExpr::Function { name, args, .. } => {
+            let args = translate_all(args, scope)?;
+            let expr = match name.to_ascii_uppercase().as_str() {
+                "COALESCE" => coalesce(args)?,
+                "NULLIF" => match <[ExprRef; 2]>::try_from(args) {
+                    Ok([l, r]) => nullif(l, r),
+                    Err(args) => {
+                        return Err(UnakiteError::Plan(format!(
+                            "NULLIF expects 2 arguments, got {}",
+                            args.len()
+                        )));
+                    }
+                },
+                _ => call_function(name, args)?,
+            };
+            // Argument types are checked while planning, not on the first batch
+            scope.data_type(&expr)?;
+            Ok(expr)
+        }
+        SqlExpr::Cast {
+            expr,
+            data_type,
+            try_cast,
+        } => {
+            let expr = translate(expr, scope)?;
+            let expr = match try_cast {
+                true => expr.try_cast(data_type.clone()),
+                false => expr.cast(data_type.clone()),
+            };
+            // Casts between types that do not convert are rejected while planning
+            scope.data_type(&expr)?;
+            Ok(expr)
+        }
+        SqlExpr::Alias { expr, alias } => Ok(translate(expr, scope)?.alias(alias)),
+    }
+}
+
+fn translate_binary(
+    l: &SqlExpr,
+    op: BinaryOperator,
+    r: &SqlExpr,
+    scope: &Scope,
+) -> Result<ExprRef> {
+    let mut l_expr = translate(l, scope)?;
+    let mut r_expr = translate(r, scope)?;
+
+    // Numeric literals take the type of the column they are compared with
+    if let Some(literal) = coerce_literal(l, &scope.data_type(&r_expr)?) {
+        l_expr = literal;
+    } else if let Some(literal) = coerce_literal(r, &scope.data_type(&l_expr)?) {
+        r_expr = literal;
+    }
+
+    let expr = match op {
+        BinaryOperator::Eq => l_expr.eq(r_expr),
+        BinaryOperator::Neq => l_expr.neq(r_expr),
+        BinaryOperator::Lt => l_expr.lt(r_expr),
+        BinaryOperator::LtEq => l_expr.lteq(r_expr),
+        BinaryOperator::Gt => l_expr.gt(r_expr),
+        BinaryOperator::GtEq => l_expr.gteq(r_expr),
+        BinaryOperator::And => l_expr.and(r_expr),
+        BinaryOperator::Or => l_expr.or(r_expr),
+        BinaryOperator::Plus => l_expr + r_expr,
+        BinaryOperator::Minus => l_expr - r_expr,
+        BinaryOperator::Multiply => l_expr * r_expr,
+        BinaryOperator::Divide => l_expr / r_expr,
+        BinaryOperator::Modulo => l_expr % r_expr,
+        BinaryOperator::IsDistinctFrom => l_expr.is_distinct_from(r_expr),
+        BinaryOperator::IsNotDistinctFrom => l_expr.is_not_distinct_from(r_expr),
+    };
+
+    // Operands that do not meet in a common type are rejected while planning, not on the first
+    // batch
+    scope.data_type(&expr)?;
+    Ok(expr)
+}
+
+/** Translate `expr`, a numeric literal takes `data_type` when it can represent the value exactly */
+fn translate_as(expr: &SqlExpr, data_type: &DataType, scope: &Scope) -> Result<ExprRef> {
+    match coerce_literal(expr, data_type) {
+        Some(literal) => Ok(literal),
+        None => translate(expr, scope),
+    }
+}
+
+/**
+ * A numeric literal of the given type with the value of `expr`, if `expr` is a numeric literal
+ * that can be represented exactly in that type.
+ */
+fn coerce_literal(expr: &SqlExpr, data_type: &DataType) -> Option<ExprRef> {
+    let (long, double) = match expr {
+        SqlExpr::Long(value) => (Some(*value), *value as f64),
+        SqlExpr::Double(value) => (None, *value),
+        SqlExpr::Negative(inner) => match inner.as_ref() {
+            SqlExpr::Long(value) => (Some(-value), -(*value as f64)),
+            SqlExpr::Double(value) => (None, -value),
+            _ => return None,
+        },
+        _ => return None,
+    };
+
+    let literal = match data_type {
+        DataType::Int8 => literal_i8(long?.try_into().ok()?),
+        DataType::Int16 => literal_i16(long?.try_into().ok()?),
+        DataType::Int32 => literal_i32(long?.try_into().ok()?),
+        DataType::Int64 => literal_i64(long?),
+        DataType::UInt8 => literal_u8(long?.try_into().ok()?),
+        DataType::UInt16 => literal_u16(long?.try_into().ok()?),
+        DataType::UInt32 => literal_u32(long?.try_into().ok()?),
+        DataType::UInt64 => literal_u64(long?.try_into().ok()?),
+        DataType::Float32 => literal_float(double as f32),
+        DataType::Float64 => literal_double(double),
+        _ => return None,
+    };
+    Some(literal)
+}
+
+fn aggregate(expr: &SqlExpr, scope: &Scope) -> Result<AggregateExpr> {
+    let SqlExpr::Function {
+        name,
+        args,
+        distinct,
+    } = expr
+    else {
+        return Err(UnakiteError::Plan(format!(
+            "{:?} is not an aggregate function",
+            expr
+        )));
+    };
+    let name = name.to_ascii_uppercase();
+
+    if args.len() != 1 {
+        return Err(UnakiteError::Plan(format!(
+            "{} expects exactly one argument",
+            name
+        )));
+    }
+
+    let arg = match &args[0] {
+        SqlExpr::Wildcard if name == "COUNT" && !distinct => literal_i64(1),
+        arg => translate(arg, scope)?,
+    };
+
+    Ok(match (name.as_str(), distinct) {
+        ("COUNT", true) => AggregateExpr::CountDistinct(AggregateCountDistinct::new(arg)),
+        (_, true) => {
+            return Err(UnakiteError::Plan(
+                "DISTINCT is only supported with COUNT".to_string(),
+            ));
+        }
+        ("SUM", _) => AggregateExpr::Sum(AggregateSum::new(arg)),
+        ("MIN", _) => AggregateExpr::Min(AggregateMin::new(arg)),
+        ("MAX", _) => AggregateExpr::Max(AggregateMax::new(arg)),
+        ("AVG", _) => AggregateExpr::Avg(AggregateAvg::new(arg)),
+        ("COUNT", _) => AggregateExpr::Count(AggregateCount::new(arg)),
+        _ => {
+            return Err(UnakiteError::Plan(format!(
+                "{} is not an aggregate function",
+                name
+            )));
+        }
+    })
+}
+
+fn is_aggregate(name: &str) -> bool {
+    AGGREGATE_FUNCTIONS.contains(&name.to_ascii_uppercase().as_str())
+}
+
+/** Gather the distinct aggregate function calls in `expr` */
+fn collect_aggregates(expr: &SqlExpr, aggregates: &mut Vec<SqlExpr>) {
+    match expr {
+        SqlExpr::Function { name, .. } if is_aggregate(name) => {
+            if aggregates.contains(expr) {
+                return;
+            }
+            aggregates.push(expr.clone());
+        }
+        SqlExpr::Function { args, .. } => {
+            for arg in args {
+                collect_aggregates(arg, aggregates);
+            }
+        }
+        SqlExpr::BinaryExpr { l, r, .. } => {
+            collect_aggregates(l, aggregates);
+            collect_aggregates(r, aggregates);
+        }
+        SqlExpr::Not(inner) | SqlExpr::Negative(inner) => collect_aggregates(inner, aggregates),
+        SqlExpr::InList { expr, list, .. } => {
+            collect_aggregates(expr, aggregates);
+            for item in list {
+                collect_aggregates(item, aggregates);
+            }
+        }
+        SqlExpr::Between {
+            expr, low, high, ..
+        } => {
+            for it in [expr, low, high] {
+                collect_aggregates(it, aggregates);
+            }
+        }
+        SqlExpr::Case {
+            operand,
+            when_then,
+            else_result,
+        } => {
+            for it in operand.iter().chain(else_result) {
+                collect_aggregates(it, aggregates);
+            }
+            for (when, then) in when_then {
+                collect_aggregates(when, aggregates);
+                collect_aggregates(then, aggregates);
+            }
+        }
+        SqlExpr::Alias { expr, .. } | SqlExpr::Cast { expr, .. } | SqlExpr::IsNull { expr, .. } => {
+            collect_aggregates(expr, aggregates)
+        }
+        _ => {}
+    }
+}
+
+fn expand_wildcards(projection: &[SqlExpr], df: &Frame) -> Result<Vec<SqlExpr>> {
+    let mut expanded = vec![];
+
+    for expr in projection {
+        match expr {
+            SqlExpr::Wildcard => expanded.extend(
+                df.schema()?
+                    .fields
+                    .iter()
+                    .map(|it| SqlExpr::Identifier(it.name.clone())),
+            ),
+            expr => expanded.push(expr.clone()),
+        }
+    }
+
+    Ok(expanded)
+}
+
+fn resolve_alias(expr: &SqlExpr, projection: &[SqlExpr]) -> SqlExpr {
+    if let SqlExpr::Identifier(name) = expr {
+        for item in projection {
+            if let SqlExpr::Alias { expr, alias } = item
+                && alias == name
+            {
+                return expr.as_ref().clone();
+            }
+        }
+    }
+    expr.clone()
+}
+
+/**
+ * The format and write options of a COPY statement. Without a FORMAT option the format follows
+ * the extension of the path.
+ */
+fn copy_options(path: &str, options: &[SqlCopyOption]) -> Result<(WriteFormat, WriteOptions)> {
+    let mut format = WriteFormat::from_path(path);
+    let mut write_options = WriteOptions::new();
+
+    for option in options.iter() {
+        let name = option.name.to_ascii_uppercase();
+        match name.as_str() {
+            "FORMAT" => format = Some(WriteFormat::from_name(&option_word(&name, option)?)?),
+            "COMPRESSION" => {
+                write_options.compression = Compression::from_name(&option_word(&name, option)?)?
+            }
+            "ROW_GROUP_SIZE" => match option.values.as_slice() {
+                [SqlExpr::Long(size)] if *size > 0 => {
+                    write_options.row_group_size = Some(*size as usize)
+                }
+                _ => return Err(invalid_option(&name, "a positive integer")),
+            },
+            "PARTITION_BY" => {
+                write_options.partition_by = option
+                    .values
+                    .iter()
+                    .map(|it| match it {
+                        SqlExpr::Identifier(column) => Ok(column.clone()),
+                        _ => Err(invalid_option(&name, "column names")),
+                    })
+                    .collect::<Result<Vec<String>>>()?
+            }
+            "HEADER" => {
+                write_options.has_header =
+                    match option_word(&name, option)?.to_ascii_lowercase().as_str() {
+                        "true" => true,
+                        "false" => false,
+                        _ => return Err(invalid_option(&name, "true or false")),
+                    }
+            }
+            "DELIMITER" => match option.values.as_slice() {
+                [SqlExpr::String(delimiter)] if delimiter.len() == 1 => {
+                    write_options.delimiter = delimiter.as_bytes()[0]
+                }
+                _ => return Err(invalid_option(&name, "a single character string")),
+            },
+            _ => {
+                return Err(UnakiteError::Plan(format!(
+                    "Unknown COPY option {}",
+                    option.name
+                )));
+            }
+        }
+    }
+
+    let format = format.ok_or_else(|| {
+        UnakiteError::Plan(format!(
+            "Can not tell the format of {} from its extension, add a FORMAT option",
+            path
+        ))
+    })?;
+    Ok((format, write_options))
+}
+
+/** A single unquoted word or string, e.g. `FORMAT parquet` */
+fn option_word(name: &str, option: &SqlCopyOption) -> Result<String> {
+    match option.values.as_slice() {
+        [SqlExpr::Identifier(word)] | [SqlExpr::String(word)] => Ok(word.clone()),
+        _ => Err(invalid_option(name, "a single word")),
+    }
+}
+
+fn invalid_option(name: &str, expected: &str) -> UnakiteError {
+    UnakiteError::Plan(format!("COPY option {} expects {}", name, expected))
+}
@@ -0,0 +1,318 @@
+#[cfg(test)]
+pub mod test {
+    use arrow::datatypes::DataType;
+
+    use crate::sql::{
+        ast::{BinaryOperator, SqlCopyOption, SqlExpr, SqlSort, SqlStatement},
+        parser::SqlParser,
+    };
+
+    #[test]
+    fn parse_full_select() {
+        let select = SqlParser::parse_sql(
+            "SELECT region, SUM(quantity) AS total FROM sales WHERE price > 2 \
+             GROUP BY region HAVING SUM(quantity) > 10 ORDER BY total DESC, region LIMIT 5;",
+        )
+        .unwrap();
+
+        assert_eq!(select.table, "sales");
+        assert_eq!(select.projection.len(), 2);
+        assert_eq!(
+            select.projection[1],
+            SqlExpr::Alias {
+                expr: Box::new(function("SUM", "quantity")),
+                alias: "total".into(),
+            }
+        );
+        assert_eq!(select.group_by, vec![ident("region")]);
+        assert_eq!(
+            select.having,
+            Some(binary(
+                function("SUM", "quantity"),
+                BinaryOperator::Gt,
+                SqlExpr::Long(10)
+            ))
+        );
+        assert_eq!(
+            select.order_by,
+            vec![
+                SqlSort {
+                    expr: ident("total"),
+                    asc: false
+                },
+                SqlSort {
+                    expr: ident("region"),
+                    asc: true
+                },
+            ]
+        );
+        assert_eq!(select.limit, Some(5));
+    }
+
+    #[test]
+    fn operator_precedence() {
+        let select =
+            SqlParser::parse_sql("SELECT a FROM t WHERE a + b * 2 > 3 OR c = 1 AND d < 2").unwrap();
+
+        let sum = binary(
+            ident("a"),
+            BinaryOperator::Plus,
+            binary(ident("b"), BinaryOperator::Multiply, SqlExpr::Long(2)),
+        );
+        let and = binary(
+            binary(ident("c"), BinaryOperator::Eq, SqlExpr::Long(1)),
+            BinaryOperator::And,
+            binary(ident("d"), BinaryOperator::Lt, SqlExpr::Long(2)),
+        );
+
+        assert_eq!(
+            select.selection,
+            Some(binary(
+                binary(sum, BinaryOperator::Gt, SqlExpr::Long(3)),
+                BinaryOperator::Or,
+                and
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_count_forms_and_implicit_alias() {
+        let select = SqlParser::parse_sql("SELECT COUNT(*) n, COUNT(DISTINCT t.b) FROM t").unwrap();
+
+        assert_eq!(
+            select.projection,
+            vec![
+                SqlExpr::Alias {
+                    expr: Box::new(SqlExpr::Function {
+                        name: "COUNT".into(),
+                        args: vec![SqlExpr::Wildcard],
+                        distinct: false,
+                    }),
+                    alias: "n".into(),
+                },
+                SqlExpr::Function {
+                    name: "COUNT".into(),
+                    args: vec![ident("t.b")],
+                    distinct: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(SqlParser::parse_sql("SELECT a").is_err());
+        assert!(SqlParser::parse_sql("SELECT a FROM t WHERE").is_err());
+        assert!(SqlParser::parse_sql("SELECT (a FROM t").is_err());
+        assert!(SqlParser::parse_sql("SELECT a FROM t LIMIT x").is_err());
+        assert!(SqlParser::parse_sql("SELECT a FROM t extra tokens").is_err());
+    }
+
+    #[test]
+    fn parse_copy() {
+        let statement = SqlParser::parse_statement_sql(
+            "COPY (SELECT region, quantity FROM sales WHERE quantity > 2) TO 'out/sales' \
+             WITH (FORMAT parquet, ROW_GROUP_SIZE 100, PARTITION_BY (region));",
+        )
+        .unwrap();
+
+        let SqlStatement::Copy {
+            query,
+            path,
+            options,
+        } = statement
+        else {
+            panic!("Expected a COPY statement, got {:?}", statement);
+        };
+        assert_eq!(query.table, "sales");
+        assert_eq!(path, "out/sales");
+        assert_eq!(
+            options,
+            vec![
+                SqlCopyOption {
+                    name: "FORMAT".into(),
+                    values: vec![ident("parquet")],
+                },
+                SqlCopyOption {
+                    name: "ROW_GROUP_SIZE".into(),
+                    values: vec![SqlExpr::Long(100)],
+                },
+                SqlCopyOption {
+                    name: "PARTITION_BY".into(),
+                    values: vec![ident("region")],
+                },
+            ]
+        );
+
+        let select = SqlParser::parse_statement_sql("SELECT a FROM t").unwrap();
+        assert!(matches!(select, SqlStatement::Select(_)));
+
+        assert!(SqlParser::parse_statement_sql("COPY SELECT a FROM t TO 'x.csv'").is_err());
+        assert!(SqlParser::parse_statement_sql("COPY (SELECT a FROM t) TO x").is_err());
+        assert!(SqlParser::parse_statement_sql("COPY (SELECT a FROM t) TO 'x.csv' WITH").is_err());
+    }
+
+    #[test]
+    fn parse_casts() {
+        let select = SqlParser::parse_sql(
+            "SELECT CAST(price * 2 AS DECIMAL(10, 2)), try_cast(day AS date), \
+             CAST(a AS double precision), CAST(b AS VARCHAR(20)) FROM t",
+        )
+        .unwrap();
+
+        let cast = |expr: SqlExpr, data_type: DataType, try_cast: bool| SqlExpr::Cast {
+            expr: Box::new(expr),
+            data_type,
+            try_cast,
+        };
+        assert_eq!(
+            select.projection,
+            vec![
+                cast(
+                    binary(ident("price"), BinaryOperator::Multiply, SqlExpr::Long(2)),
+                    DataType::Decimal128(10, 2),
+                    false
+                ),
+                cast(ident("day"), DataType::Date32, true),
+                cast(ident("a"), DataType::Float64, false),
+                cast(ident("b"), DataType::Utf8, false),
+            ]
+        );
+
+        let select = SqlParser::parse_sql("SELECT CAST(a AS NUMERIC(50)) FROM t").unwrap();
+        assert!(matches!(
+            select.projection[0],
+            SqlExpr::Cast {
+                data_type: DataType::Decimal256(50, 0),
+                ..
+            }
+        ));
+
+        assert!(SqlParser::parse_sql("SELECT CAST(a INT) FROM t").is_err());
+        assert!(SqlParser::parse_sql("SELECT CAST(a AS blob) FROM t").is_err());
+        assert!(SqlParser::parse_sql("SELECT CAST(a AS DECIMAL(5, 6)) FROM t").is_err());
+    }
+
+    #[test]
+    fn parse_null_predicates() {
+        let select = SqlParser::parse_sql(
+            "SELECT a FROM t WHERE a IS NULL OR b + 1 IS NOT DISTINCT FROM c AND d IS NOT NULL",
+        )
+        .unwrap();
+
+        let is_null = |expr: SqlExpr, negated: bool| SqlExpr::IsNull {
+            expr: Box::new(expr),
+            negated,
+        };
+        assert_eq!(
+            select.selection,
+            Some(binary(
+                is_null(ident("a"), false),
+                BinaryOperator::Or,
+                binary(
+                    binary(
+                        binary(ident("b"), BinaryOperator::Plus, SqlExpr::Long(1)),
+                        BinaryOperator::IsNotDistinctFrom,
+                        ident("c")
+                    ),
+                    BinaryOperator::And,
+                    is_null(ident("d"), true)
+                )
+            ))
+        );
+
+        let select = SqlParser::parse_sql("SELECT a IS DISTINCT FROM NULL FROM t").unwrap();
+        assert_eq!(
+            select.projection,
+            vec![binary(
+                ident("a"),
+                BinaryOperator::IsDistinctFrom,
+                SqlExpr::Null
+            )]
+        );
+
+        assert!(SqlParser::parse_sql("SELECT a IS 1 FROM t").is_err());
+        assert!(SqlParser::parse_sql("SELECT a IS DISTINCT b FROM t").is_err());
+    }
+
+    #[test]
+    fn parse_conditionals() {
+        let select = SqlParser::parse_sql(
+            "SELECT CASE WHEN a > 1 THEN 'big' ELSE 'small' END, \
+             CASE b WHEN 1 THEN 'one' WHEN 2 THEN 'two' END FROM t \
+             WHERE a NOT IN (1, 2) AND b BETWEEN 1 + 1 AND 5 AND NOT c",
+        )
+        .unwrap();
+
+        assert_eq!(
+            select.projection,
+            vec![
+                SqlExpr::Case {
+                    operand: None,
+                    when_then: vec![(
+                        binary(ident("a"), BinaryOperator::Gt, SqlExpr::Long(1)),
+                        SqlExpr::String("big".into())
+                    )],
+                    else_result: Some(Box::new(SqlExpr::String("small".into()))),
+                },
+                SqlExpr::Case {
+                    operand: Some(Box::new(ident("b"))),
+                    when_then: vec![
+                        (SqlExpr::Long(1), SqlExpr::String("one".into())),
+                        (SqlExpr::Long(2), SqlExpr::String("two".into())),
+                    ],
+                    else_result: None,
+                },
+            ]
+        );
+
+        let in_list = SqlExpr::InList {
+            expr: Box::new(ident("a")),
+            list: vec![SqlExpr::Long(1), SqlExpr::Long(2)],
+            negated: true,
+        };
+        let between = SqlExpr::Between {
+            expr: Box::new(ident("b")),
+            low: Box::new(binary(
+                SqlExpr::Long(1),
+                BinaryOperator::Plus,
+                SqlExpr::Long(1),
+            )),
+            high: Box::new(SqlExpr::Long(5)),
+            negated: false,
+        };
+        assert_eq!(
+            select.selection,
+            Some(binary(
+                binary(in_list, BinaryOperator::And, between),
+                BinaryOperator::And,
+                SqlExpr::Not(Box::new(ident("c")))
+            ))
+        );
+
+        assert!(SqlParser::parse_sql("SELECT CASE a END FROM t").is_err());
+        assert!(SqlParser::parse_sql("SELECT CASE WHEN a THEN b FROM t").is_err());
+        assert!(SqlParser::parse_sql("SELECT a BETWEEN 1 OR 2 FROM t").is_err());
+        assert!(SqlParser::parse_sql("SELECT a IN 1 FROM t").is_err());
+    }
+
+    fn ident(name: &str) -> SqlExpr {
+        SqlExpr::Identifier(name.into())
+    }
+
+    fn function(name: &str, arg: &str) -> SqlExpr {
+        SqlExpr::Function {
+            name: name.into(),
+            args: vec![ident(arg)],
+            distinct: false,
+        }
+    }
+
+    fn binary(l: SqlExpr, op: BinaryOperator, r: SqlExpr) -> SqlExpr {
+        SqlExpr::BinaryExpr {
+            l: Box::new(l),
+            op,
+            r: Box::new(r),
+        }
+    }
+}
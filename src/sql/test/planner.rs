@@ -0,0 +1,603 @@
+#[cfg(test)]
+pub mod test {
+    use std::{collections::HashMap, sync::Arc};
+
+    use arrow::{
+        array::{Array, Decimal128Array},
+        datatypes::{DataType, Field, Schema},
+    };
+
+    use crate::{
+        datasource::{DataSource, csv::CsvDataSource},
+        datatypes::record_batch::RecordBatch,
+        error::{Result, UnakiteError},
+        logical_plan::{LogicalPlan, data_frame::Frame, scan::Scan},
+        query_planner::QueryPlanner,
+        sql::planner::SqlPlanner,
+    };
+
+    #[test]
+    fn select_where_order_limit() {
+        let batches = execute(
+            "SELECT product, quantity FROM sales WHERE quantity > 4 ORDER BY quantity DESC LIMIT 3",
+        );
+
+        let rows = rows(&batches, |batch, i| {
+            let product: String = batch
+                .field(0)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let quantity: i64 = batch
+                .field(1)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            (product, quantity)
+        });
+
+        assert_eq!(
+            rows,
+            vec![
+                ("Gizmo".to_string(), 12),
+                ("Widget".to_string(), 10),
+                ("Widget".to_string(), 8),
+            ]
+        );
+    }
+
+    #[test]
+    fn select_wildcard_with_qualified_filter() {
+        let batches = execute("SELECT * FROM sales WHERE sales.region = 'South'");
+
+        assert_eq!(batches[0].column_count(), 4);
+        let total: usize = batches.iter().map(|it| it.row_count()).sum();
+        assert_eq!(total, 3);
+    }
+
+    #[test]
+    fn comparisons_promote_numeric_operands() {
+        let count = |sql: &str| execute(sql).iter().map(|it| it.row_count()).sum::<usize>();
+
+        // An Int64 column meets a Float64 literal or column in Float64
+        assert_eq!(count("SELECT region FROM sales WHERE quantity > 2.5"), 8);
+        assert_eq!(count("SELECT region FROM sales WHERE quantity < price"), 3);
+        assert_eq!(
+            count("SELECT region FROM sales WHERE quantity BETWEEN 2.5 AND 5"),
+            3
+        );
+    }
+
+    #[test]
+    fn group_by_having_order_by_alias() {
+        let batches = execute(
+            "SELECT region, SUM(quantity) AS total, COUNT(*) AS n FROM sales \
+             GROUP BY region HAVING SUM(quantity) > 15 ORDER BY total DESC",
+        );
+
+        let rows = rows(&batches, |batch, i| {
+            let region: String = batch
+                .field(0)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let total: i64 = batch
+                .field(1)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let n: i32 = batch
+                .field(2)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            (region, total, n)
+        });
+
+        assert_eq!(
+            rows,
+            vec![("East".to_string(), 27, 3), ("North".to_string(), 20, 4)]
+        );
+    }
+
+    #[test]
+    fn aggregate_in_expression() {
+        let df = plan("SELECT MAX(price) AS highest, MIN(price) FROM sales").unwrap();
+
+        let names: Vec<String> = df
+            .plan
+            .schema()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|it| it.name.clone())
+            .collect();
+        assert_eq!(names, vec!["highest", "MIN(price)"]);
+    }
+
+    #[test]
+    fn computed_columns() {
+        let batches = execute(
+            "SELECT product, price * quantity AS revenue, quantity % 4 + 1 AS bucket FROM sales \
+             WHERE price * quantity > 24 ORDER BY price * quantity DESC",
+        );
+
+        let df =
+            plan("SELECT price * quantity AS revenue, quantity / 2 AS half FROM sales").unwrap();
+        let types: Vec<DataType> = df
+            .plan
+            .schema()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|it| it.data_type.clone())
+            .collect();
+        assert_eq!(types, vec![DataType::Float64, DataType::Int64]);
+
+        let rows = rows(&batches, |batch, i| {
+            let product: String = batch
+                .field(0)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let revenue: f64 = batch
+                .field(1)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let bucket: i64 = batch
+                .field(2)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            (product, revenue, bucket)
+        });
+        assert_eq!(
+            rows,
+            vec![
+                ("Gadget".to_string(), 30.0, 4),
+                ("Widget".to_string(), 25.0, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn scalar_functions() {
+        let batches = execute(
+            "SELECT product, ROUND(price * quantity / 3, 1) AS third, sqrt(quantity) FROM sales \
+             WHERE ABS(quantity - 10) <= 2 ORDER BY POWER(quantity, 2)",
+        );
+
+        let rows = rows(&batches, |batch, i| {
+            let product: String = batch
+                .field(0)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let third: f64 = batch
+                .field(1)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let root: f64 = batch
+                .field(2)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            (product, third, root)
+        });
+        assert_eq!(
+            rows,
+            vec![
+                ("Widget".to_string(), 7.3, 8f64.sqrt()),
+                ("Widget".to_string(), 8.3, 10f64.sqrt()),
+                ("Gizmo".to_string(), 5.0, 12f64.sqrt()),
+            ]
+        );
+
+        let df = plan("SELECT ABS(quantity), CEIL(price) FROM sales").unwrap();
+        let types: Vec<DataType> = df
+            .plan
+            .schema()
+            .unwrap()
+            .fields
+            .iter()
+            .map(|it| it.data_type.clone())
+            .collect();
+        assert_eq!(types, vec![DataType::Int64, DataType::Float64]);
+
+        // A NULL argument makes the result NULL rather than a type error
+        let batches = execute("SELECT ABS(NULL), ROUND(quantity, NULL), SQRT(NULL) FROM sales");
+        let types: Vec<DataType> = (0..3)
+            .map(|i| batches[0].field(i).unwrap().to_array().data_type().clone())
+            .collect();
+        assert_eq!(
+            types,
+            vec![DataType::Null, DataType::Int64, DataType::Float64]
+        );
+        for i in 0..3 {
+            let values = batches[0].field(i).unwrap().to_array();
+            assert_eq!(values.logical_null_count(), values.len());
+        }
+    }
+
+    #[test]
+    fn casts() {
+        let sql = "SELECT product, CAST(price AS INT) AS whole, CAST(quantity AS VARCHAR) AS text, \
+                   CAST(price * quantity AS DECIMAL(6, 1)) AS revenue, \
+                   TRY_CAST(region AS DATE) AS day FROM sales \
+                   WHERE CAST(quantity AS DOUBLE) / 4 > 2.5";
+
+        let schema = plan(sql).unwrap().plan.schema().unwrap();
+        let fields: Vec<(DataType, bool)> = schema
+            .fields
+            .iter()
+            .map(|it| (it.data_type.clone(), it.nullable))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (DataType::Utf8, false),
+                (DataType::Int32, false),
+                (DataType::Utf8, false),
+                (DataType::Decimal128(6, 1), false),
+                (DataType::Date32, true),
+            ]
+        );
+
+        let batches = execute(sql);
+        assert_eq!(batches.iter().map(|it| it.row_count()).sum::<usize>(), 1);
+        let batch = &batches[0];
+        let whole: i32 = batch
+            .field(1)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let text: String = batch
+            .field(2)
+            .unwrap()
+            .get_value(0)
+            .unwrap()
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!((whole, text.as_str()), (1, "12"));
+
+        let revenue = batch.field(3).unwrap().to_array();
+        let revenue = revenue.as_any().downcast_ref::<Decimal128Array>().unwrap();
+        assert_eq!(revenue.value_as_string(0), "15.0");
+        assert!(batch.field(4).unwrap().to_array().is_null(0));
+
+        // A plain CAST fails on the first value it cannot convert
+        let df = plan("SELECT CAST(region AS DATE) FROM sales").unwrap();
+        let physical = QueryPlanner::create_physical_plan(&df.plan).unwrap();
+        let result = physical.execute().unwrap().collect::<Result<Vec<_>>>();
+        assert!(matches!(result, Err(UnakiteError::Execution(_))));
+
+        // An aggregate over no rows is a null of the aggregate's type
+        let batches = execute("SELECT MIN(CAST('2026-01-01' AS DATE)) FROM sales WHERE 1 = 0");
+        let day = batches[0].field(0).unwrap().to_array();
+        assert_eq!((day.data_type(), day.len()), (&DataType::Date32, 1));
+        assert!(day.is_null(0));
+    }
+
+    #[test]
+    fn null_semantics() {
+        let count = |sql: &str| execute(sql).iter().map(|it| it.row_count()).sum::<usize>();
+
+        // Comparisons with NULL are null, and filters drop null predicates
+        assert_eq!(count("SELECT region FROM sales WHERE quantity = NULL"), 0);
+        assert_eq!(
+            count("SELECT region FROM sales WHERE NULL OR quantity > 7"),
+            3
+        );
+        assert_eq!(
+            count("SELECT region FROM sales WHERE NULLIF(region, 'North') <> 'South'"),
+            3
+        );
+        assert_eq!(
+            count("SELECT region FROM sales WHERE NULLIF(region, 'North') IS NULL"),
+            4
+        );
+        assert_eq!(
+            count(
+                "SELECT region FROM sales WHERE NULLIF(region, 'North') IS DISTINCT FROM 'South'"
+            ),
+            7
+        );
+        assert_eq!(
+            count("SELECT region FROM sales WHERE quantity IS NOT DISTINCT FROM NULL"),
+            0
+        );
+
+        let sql = "SELECT COALESCE(NULLIF(region, 'North'), 'n/a') AS area, \
+                   NULLIF(quantity, 10) IS NULL AS ten, COALESCE(NULL, quantity, price) AS amount \
+                   FROM sales LIMIT 2";
+        let schema = plan(sql).unwrap().plan.schema().unwrap();
+        let fields: Vec<(DataType, bool)> = schema
+            .fields
+            .iter()
+            .map(|it| (it.data_type.clone(), it.nullable))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (DataType::Utf8, false),
+                (DataType::Boolean, false),
+                (DataType::Float64, false),
+            ]
+        );
+
+        let rows = rows(&execute(sql), |batch, i| {
+            let area: String = batch
+                .field(0)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let ten: bool = batch
+                .field(1)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let amount: f64 = batch
+                .field(2)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            (area, ten, amount)
+        });
+        assert_eq!(
+            rows,
+            vec![
+                ("n/a".to_string(), true, 10.0),
+                ("South".to_string(), false, 4.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn conditional_expressions() {
+        let sql = "SELECT product, \
+                   CASE WHEN quantity >= 10 THEN 'bulk' WHEN quantity >= 5 THEN 'regular' \
+                   ELSE 'small' END AS size, \
+                   CASE region WHEN 'North' THEN 1 WHEN 'South' THEN 2 END AS zone, \
+                   -quantity AS returned \
+                   FROM sales \
+                   WHERE region IN ('North', 'East') AND price BETWEEN 2 AND 3 \
+                   AND NOT product NOT IN ('Widget') \
+                   ORDER BY quantity DESC";
+
+        let schema = plan(sql).unwrap().plan.schema().unwrap();
+        let fields: Vec<(DataType, bool)> = schema
+            .fields
+            .iter()
+            .map(|it| (it.data_type.clone(), it.nullable))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                (DataType::Utf8, false),
+                (DataType::Utf8, false),
+                (DataType::Int64, true),
+                (DataType::Int64, false),
+            ]
+        );
+
+        let sizes = rows(&execute(sql), |batch, i| {
+            let size: String = batch
+                .field(1)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            let zone: Option<i64> = batch
+                .field(2)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .map(|it| it.try_into().unwrap());
+            let returned: i64 = batch
+                .field(3)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            (size, zone, returned)
+        });
+        assert_eq!(
+            sizes,
+            vec![
+                ("bulk".to_string(), Some(1), -10),
+                ("regular".to_string(), None, -8),
+                ("regular".to_string(), None, -7),
+                ("regular".to_string(), Some(1), -5),
+            ]
+        );
+
+        // Guarded branches do not fail on the rows they exclude
+        let batches = execute(
+            "SELECT CASE WHEN quantity >= 5 THEN 0 ELSE 100 / (quantity - 5) END FROM sales",
+        );
+        let values = rows(&batches, |batch, i| {
+            let value: i64 = batch
+                .field(0)
+                .unwrap()
+                .get_value(i)
+                .unwrap()
+                .unwrap()
+                .try_into()
+                .unwrap();
+            value
+        });
+        assert_eq!(values, vec![0, -100, -50, 0, -25, 0, 0, 0, -33, 0]);
+    }
+
+    #[test]
+    fn planning_errors() {
+        assert!(plan("SELECT a FROM missing").is_err());
+        assert!(matches!(
+            plan("SELECT missing FROM sales"),
+            Err(UnakiteError::Schema(_))
+        ));
+        assert!(plan("SELECT product, SUM(quantity) FROM sales GROUP BY region").is_err());
+        assert!(plan("SELECT region FROM sales WHERE SUM(quantity) > 1").is_err());
+        assert!(plan("SELECT region FROM sales HAVING region = 'North'").is_err());
+        assert!(matches!(
+            plan("SELECT region + 1 FROM sales"),
+            Err(UnakiteError::Type(_))
+        ));
+        assert!(matches!(
+            plan("SELECT region FROM sales WHERE region > 1"),
+            Err(UnakiteError::Type(_))
+        ));
+        for aggregate in ["AVG", "SUM"] {
+            assert!(matches!(
+                plan(&format!("SELECT {}(region) FROM sales", aggregate)),
+                Err(UnakiteError::Type(_))
+            ));
+        }
+        assert!(matches!(
+            plan("SELECT SQRT(region) FROM sales"),
+            Err(UnakiteError::Type(_))
+        ));
+        assert!(matches!(
+            plan("SELECT POWER(price) FROM sales"),
+            Err(UnakiteError::Plan(_))
+        ));
+        assert!(matches!(
+            plan("SELECT UPPER(region) FROM sales"),
+            Err(UnakiteError::Plan(_))
+        ));
+        assert!(matches!(
+            plan("SELECT CAST(price AS DATE) FROM sales"),
+            Err(UnakiteError::Type(_))
+        ));
+        assert!(matches!(
+            plan("SELECT COALESCE(region, price) FROM sales"),
+            Err(UnakiteError::Type(_))
+        ));
+        assert!(matches!(
+            plan("SELECT COALESCE() FROM sales"),
+            Err(UnakiteError::Plan(_))
+        ));
+        assert!(matches!(
+            plan("SELECT NULLIF(region) FROM sales"),
+            Err(UnakiteError::Plan(_))
+        ));
+        assert!(matches!(
+            plan("SELECT NOT quantity FROM sales"),
+            Err(UnakiteError::Type(_))
+        ));
+        assert!(matches!(
+            plan("SELECT -region FROM sales"),
+            Err(UnakiteError::Type(_))
+        ));
+        assert!(matches!(
+            plan("SELECT CASE WHEN quantity THEN 1 END FROM sales"),
+            Err(UnakiteError::Type(_))
+        ));
+        assert!(matches!(
+            plan("SELECT CASE WHEN quantity > 1 THEN region ELSE price END FROM sales"),
+            Err(UnakiteError::Type(_))
+        ));
+        assert!(matches!(
+            plan("SELECT region FROM sales WHERE quantity IN ('North')"),
+            Err(UnakiteError::Type(_))
+        ));
+    }
+
+    fn rows<T>(batches: &[RecordBatch], row: impl Fn(&RecordBatch, usize) -> T) -> Vec<T> {
+        batches
+            .iter()
+            .flat_map(|batch| {
+                (0..batch.row_count())
+                    .map(|i| row(batch, i))
+                    .collect::<Vec<T>>()
+            })
+            .collect()
+    }
+
+    fn execute(sql: &str) -> Vec<RecordBatch> {
+        let df = plan(sql).unwrap();
+        let plan = QueryPlanner::create_physical_plan(&df.plan).unwrap();
+        plan.execute().unwrap().collect::<Result<_>>().unwrap()
+    }
+
+    fn plan(sql: &str) -> Result<Frame> {
+        let file_path = String::from(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/test_data/sales.csv"
+        ));
+
+        let data = CsvDataSource::new(
+            file_path,
+            true,
+            Schema::new(vec![
+                Field::new("region", DataType::Utf8, false),
+                Field::new("product", DataType::Utf8, false),
+                Field::new("quantity", DataType::Int64, false),
+                Field::new("price", DataType::Float64, false),
+            ]),
+        );
+        let scan = Scan::new("sales".to_string(), DataSource::CSV(data), Arc::new(vec![]));
+
+        let mut tables = HashMap::new();
+        tables.insert(
+            "sales".to_string(),
+            Frame {
+                plan: Arc::new(LogicalPlan::ScanPlan(scan)),
+            },
+        );
+
+        SqlPlanner::plan(sql, &tables)
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+pub mod test {
+    use crate::sql::tokenizer::{Keyword, Symbol, Token, Tokenizer};
+
+    #[test]
+    fn tokenize_select() {
+        let tokens = Tokenizer::new("select a, b_2 FROM t WHERE a >= 1.5 -- trailing comment")
+            .tokenize()
+            .unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Keyword(Keyword::Select),
+                Token::Identifier("a".into()),
+                Token::Symbol(Symbol::Comma),
+                Token::Identifier("b_2".into()),
+                Token::Keyword(Keyword::From),
+                Token::Identifier("t".into()),
+                Token::Keyword(Keyword::Where),
+                Token::Identifier("a".into()),
+                Token::Symbol(Symbol::GtEq),
+                Token::Number("1.5".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_quoted_text() {
+        let tokens = Tokenizer::new(r#"'it''s' "Mixed Case" <> !="#)
+            .tokenize()
+            .unwrap();
+
+        assert_eq!(
+            tokens,
+            vec![
+                Token::String("it's".into()),
+                Token::Identifier("Mixed Case".into()),
+                Token::Symbol(Symbol::Neq),
+                Token::Symbol(Symbol::Neq),
+            ]
+        );
+    }
+
+    #[test]
+    fn tokenize_errors() {
+        assert!(Tokenizer::new("'unterminated").tokenize().is_err());
+        assert!(Tokenizer::new("a ? b").tokenize().is_err());
+    }
+}
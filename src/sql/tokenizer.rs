@@ -0,0 +1,272 @@
+use crate::error::{Result, UnakiteError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keyword {
+    Select,
+    Distinct,
+    From,
+    Where,
+    Group,
+    By,
+    Having,
+    Order,
+    Asc,
+    Desc,
+    Limit,
+    As,
+    And,
+    Or,
+    Not,
+    Copy,
+    To,
+    With,
+    Cast,
+    TryCast,
+    Null,
+    Is,
+    Case,
+    When,
+    Then,
+    Else,
+    End,
+    In,
+    Between,
+}
+
+impl Keyword {
+    fn from_word(word: &str) -> Option<Keyword> {
+        let keyword = match word.to_ascii_uppercase().as_str() {
+            "SELECT" => Keyword::Select,
+            "DISTINCT" => Keyword::Distinct,
+            "FROM" => Keyword::From,
+            "WHERE" => Keyword::Where,
+            "GROUP" => Keyword::Group,
+            "BY" => Keyword::By,
+            "HAVING" => Keyword::Having,
+            "ORDER" => Keyword::Order,
+            "ASC" => Keyword::Asc,
+            "DESC" => Keyword::Desc,
+            "LIMIT" => Keyword::Limit,
+            "AS" => Keyword::As,
+            "AND" => Keyword::And,
+            "OR" => Keyword::Or,
+            "NOT" => Keyword::Not,
+            "COPY" => Keyword::Copy,
+            "TO" => Keyword::To,
+            "WITH" => Keyword::With,
+            "CAST" => Keyword::Cast,
+            "TRY_CAST" => Keyword::TryCast,
+            "NULL" => Keyword::Null,
+            "IS" => Keyword::Is,
+            "CASE" => Keyword::Case,
+            "WHEN" => Keyword::When,
+            "THEN" => Keyword::Then,
+            "ELSE" => Keyword::Else,
+            "END" => Keyword::End,
+            "IN" => Keyword::In,
+            "BETWEEN" => Keyword::Between,
+            _ => return None,
+        };
+        Some(keyword)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Symbol {
+    Comma,
+    Period,
+    Semicolon,
+    LeftParen,
+    RightParen,
+    Star,
+    Plus,
+    Minus,
+    Slash,
+    Percent,
+    Eq,
+    Neq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    Keyword(Keyword),
+    /** Unquoted identifiers keep their case, double quoted identifiers keep their content */
+    Identifier(String),
+    /** Numeric literal as written, parsed into a concrete type by the parser */
+    Number(String),
+    /** Single quoted string literal with `''` escapes resolved */
+    String(String),
+    Symbol(Symbol),
+}
+
+/**
+ * Splits a SQL statement into tokens. Keywords are case insensitive, `--` starts a comment that
+ * runs to the end of the line.
+ */
+pub struct Tokenizer<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+}
+
+impl<'a> Tokenizer<'a> {
+    pub fn new(sql: &'a str) -> Self {
+        Tokenizer {
+            chars: sql.char_indices().peekable(),
+        }
+    }
+
+    pub fn tokenize(mut self) -> Result<Vec<Token>> {
+        let mut tokens = vec![];
+
+        while let Some(&(offset, c)) = self.chars.peek() {
+            let token = match c {
+                c if c.is_whitespace() => {
+                    self.chars.next();
+                    continue;
+                }
+                '-' if self.next_is('-', 1) => {
+                    self.skip_line();
+                    continue;
+                }
+                c if c.is_ascii_digit() => Token::Number(self.number()),
+                '.' if self.next_is_digit() => Token::Number(self.number()),
+                c if c.is_alphabetic() || c == '_' => {
+                    let word = self.word();
+                    match Keyword::from_word(&word) {
+                        Some(keyword) => Token::Keyword(keyword),
+                        None => Token::Identifier(word),
+                    }
+                }
+                '\'' => Token::String(self.quoted('\'', offset)?),
+                '"' => Token::Identifier(self.quoted('"', offset)?),
+                _ => Token::Symbol(self.symbol(offset)?),
+            };
+
+            tokens.push(token);
+        }
+
+        Ok(tokens)
+    }
+
+    /** Whether the character `ahead` positions after the current one is `expected` */
+    fn next_is(&self, expected: char, ahead: usize) -> bool {
+        self.chars.clone().nth(ahead).map(|(_, c)| c) == Some(expected)
+    }
+
+    fn next_is_digit(&self) -> bool {
+        self.chars
+            .clone()
+            .nth(1)
+            .is_some_and(|(_, c)| c.is_ascii_digit())
+    }
+
+    fn skip_line(&mut self) {
+        for (_, c) in self.chars.by_ref() {
+            if c == '\n' {
+                break;
+            }
+        }
+    }
+
+    fn number(&mut self) -> String {
+        let mut number = String::new();
+        let mut seen_exponent = false;
+
+        while let Some(&(_, c)) = self.chars.peek() {
+            let sign_after_exponent = (c == '+' || c == '-')
+                && seen_exponent
+                && matches!(number.chars().last(), Some('e') | Some('E'));
+
+            if c.is_ascii_digit() || c == '.' || sign_after_exponent {
+                number.push(c);
+            } else if (c == 'e' || c == 'E') && !seen_exponent {
+                seen_exponent = true;
+                number.push(c);
+            } else {
+                break;
+            }
+            self.chars.next();
+        }
+
+        number
+    }
+
+    fn word(&mut self) -> String {
+        let mut word = String::new();
+
+        while let Some(&(_, c)) = self.chars.peek() {
+            if !(c.is_alphanumeric() || c == '_') {
+                break;
+            }
+            word.push(c);
+            self.chars.next();
+        }
+
+        word
+    }
+
+    /** Content between `quote` characters, where a doubled quote stands for the quote itself */
+    fn quoted(&mut self, quote: char, start: usize) -> Result<String> {
+        self.chars.next();
+        let mut value = String::new();
+
+        loop {
+            match self.chars.next() {
+                Some((_, c)) if c == quote => {
+                    if self.chars.peek().map(|(_, c)| *c) == Some(quote) {
+                        self.chars.next();
+                        value.push(quote);
+                    } else {
+                        return Ok(value);
+                    }
+                }
+                Some((_, c)) => value.push(c),
+                None => {
+                    return Err(UnakiteError::Plan(format!(
+                        "Unterminated quoted text starting at position {}",
+                        start
+                    )));
+                }
+            }
+        }
+    }
+
+    fn symbol(&mut self, offset: usize) -> Result<Symbol> {
+        let (_, c) = self.chars.next().unwrap();
+        let next = self.chars.peek().map(|(_, c)| *c);
+
+        let (symbol, two_chars) = match (c, next) {
+            ('<', Some('=')) => (Symbol::LtEq, true),
+            ('<', Some('>')) => (Symbol::Neq, true),
+            ('>', Some('=')) => (Symbol::GtEq, true),
+            ('!', Some('=')) => (Symbol::Neq, true),
+            ('<', _) => (Symbol::Lt, false),
+            ('>', _) => (Symbol::Gt, false),
+            ('=', _) => (Symbol::Eq, false),
+            (',', _) => (Symbol::Comma, false),
+            ('.', _) => (Symbol::Period, false),
+            (';', _) => (Symbol::Semicolon, false),
+            ('(', _) => (Symbol::LeftParen, false),
+            (')', _) => (Symbol::RightParen, false),
+            ('*', _) => (Symbol::Star, false),
+            ('+', _) => (Symbol::Plus, false),
+            ('-', _) => (Symbol::Minus, false),
+            ('/', _) => (Symbol::Slash, false),
+            ('%', _) => (Symbol::Percent, false),
+            _ => {
+                return Err(UnakiteError::Plan(format!(
+                    "Unexpected character '{}' at position {}",
+                    c, offset
+                )));
+            }
+        };
+
+        if two_chars {
+            self.chars.next();
+        }
+        Ok(symbol)
+    }
+}
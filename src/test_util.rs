@@ -0,0 +1,126 @@
+/*
+ * Fixtures shared by the tests of several modules: the CSV tables under src/test_data and helpers
+ * to run a scan or plan and read its results back.
+ */
+
+use std::sync::Arc;
+
+use arrow::{
+    array::{Float64Array, Int64Array},
+    datatypes::{DataType, Field, Schema},
+};
+
+use crate::{
+    datasource::{
+        DataSource, Iterators,
+        csv::{CsvDataSource, CsvOptions},
+        listing::{FileFormat, ListingOptions},
+    },
+    datatypes::record_batch::RecordBatch,
+    error::Result,
+    logical_plan::{LogicalPlan, data_frame::Frame, scan::Scan},
+    query_planner::QueryPlanner,
+};
+
+/** Every batch of a scan */
+pub fn collect(scan: Result<Iterators>) -> Vec<RecordBatch> {
+    scan.unwrap().collect::<Result<_>>().unwrap()
+}
+
+/** Every batch produced by the physical plan of `df` */
+pub fn execute(df: &Frame) -> Vec<RecordBatch> {
+    let plan = QueryPlanner::create_physical_plan(&df.plan).unwrap();
+    plan.execute().unwrap().collect::<Result<_>>().unwrap()
+}
+
+/** The values of the Int64 column `i` across all batches */
+pub fn int_values(batches: &[RecordBatch], i: usize) -> Vec<i64> {
+    batches
+        .iter()
+        .flat_map(|batch| {
+            let array = batch.field(i).unwrap().to_array();
+            let values = array.as_any().downcast_ref::<Int64Array>().unwrap();
+            values.values().to_vec()
+        })
+        .collect()
+}
+
+/** The values of the Float64 column `i` across all batches */
+pub fn float_values(batches: &[RecordBatch], i: usize) -> Vec<f64> {
+    batches
+        .iter()
+        .flat_map(|batch| {
+            let array = batch.field(i).unwrap().to_array();
+            let values = array.as_any().downcast_ref::<Float64Array>().unwrap();
+            values.values().to_vec()
+        })
+        .collect()
+}
+
+/** Listing options for CSV files with a header row */
+pub fn csv_options() -> ListingOptions {
+    ListingOptions::new(FileFormat::Csv(CsvOptions::new().with_header(true)))
+}
+
+/** A scan of `file` in src/test_data, a CSV file with a header row */
+pub fn csv(name: &str, file: &str, fields: Vec<Field>) -> Frame {
+    scan_csv(name, file, true, fields)
+}
+
+fn scan_csv(name: &str, file: &str, has_header: bool, fields: Vec<Field>) -> Frame {
+    let file_path = format!("{}/src/test_data/{}", env!("CARGO_MANIFEST_DIR"), file);
+
+    let data = CsvDataSource::new(file_path, has_header, Schema::new(fields));
+    let scan = Scan::new(name.to_string(), DataSource::CSV(data), Arc::new(vec![]));
+
+    Frame {
+        plan: Arc::new(LogicalPlan::ScanPlan(scan)),
+    }
+}
+
+pub fn sales() -> Frame {
+    sales_named("sales")
+}
+
+/** The sales table scanned under another name, e.g. to join it with itself */
+pub fn sales_named(name: &str) -> Frame {
+    csv(
+        name,
+        "sales.csv",
+        vec![
+            Field::new("region", DataType::Utf8, false),
+            Field::new("product", DataType::Utf8, false),
+            Field::new("quantity", DataType::Int64, false),
+            Field::new("price", DataType::Float64, false),
+        ],
+    )
+}
+
+pub fn regions() -> Frame {
+    csv(
+        "regions",
+        "regions.csv",
+        vec![
+            Field::new("region", DataType::Utf8, false),
+            Field::new("manager", DataType::Utf8, false),
+        ],
+    )
+}
+
+/** The cities of uk_cities.csv, a file without a header row */
+pub fn uk_cities() -> Frame {
+    uk_cities_named("uk_cities")
+}
+
+pub fn uk_cities_named(name: &str) -> Frame {
+    scan_csv(
+        name,
+        "uk_cities.csv",
+        false,
+        vec![
+            Field::new("city", DataType::Utf8, false),
+            Field::new("lat", DataType::Float64, false),
+            Field::new("lng", DataType::Float64, false),
+        ],
+    )
+}